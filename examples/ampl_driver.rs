@@ -0,0 +1,96 @@
+//! An AMPL solver driver, which AMPL runs as `ampl_driver stub -AMPL`.
+//!
+//! The problem is read from `stub.nl` and the result is written to `stub.sol`. If the problem
+//! cannot be read, e.g. because it uses an unsupported feature, or the optimization fails to run, a
+//! `.sol` file with a failure solve result number is written instead, so that AMPL reports the
+//! error.
+//!
+//! Ipopt options are read from the `ipopt_options` environment variable, which AMPL sets with
+//! `option ipopt_options 'max_iter=100 tol=1e-6';`. Options are given as `name=value` or
+//! `name value`, and are passed to Ipopt through an options file, so that Ipopt parses each value
+//! with the type of its option.
+
+use ipopt_bindgen::{Application, NlProblem, OptimizationResult};
+use std::{
+    env,
+    error::Error,
+    fs::{self, File},
+    io::{BufWriter, Write},
+};
+use tempfile::NamedTempFile;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let stub = env::args()
+        .nth(1)
+        .ok_or("Usage: ampl_driver stub [-AMPL]")?;
+    let stub = stub.strip_suffix(".nl").unwrap_or(&stub);
+
+    let sol = BufWriter::new(File::create(format!("{stub}.sol"))?);
+    let nl = fs::read(format!("{stub}.nl"));
+    let problem = match &nl {
+        Ok(bytes) => NlProblem::parse(bytes),
+        Err(error) => Err(error.to_string().into()),
+    };
+    let problem = match problem {
+        Ok(problem) => problem,
+        Err(error) => {
+            println!("ipopt_bindgen: {error}");
+            let bytes = nl.as_deref().unwrap_or_default();
+            NlProblem::write_parse_failure_sol(sol, bytes, &error.to_string())?;
+            return Ok(());
+        }
+    };
+    match optimize(&problem) {
+        Ok(result) => {
+            println!("ipopt_bindgen: {}", result.status_name());
+            problem.write_sol(sol, &result)?;
+        }
+        Err(error) => {
+            println!("ipopt_bindgen: {error}");
+            problem.write_failure_sol(sol, &error.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Optimizes the problem with the options of the `ipopt_options` environment variable.
+fn optimize(problem: &NlProblem) -> Result<OptimizationResult, Box<dyn Error>> {
+    let mut application = Application::new();
+    // The options file must outlive the optimization, and is deleted when dropped.
+    let options = options_file()?;
+    if let Some(options) = &options {
+        let path = options
+            .path()
+            .to_str()
+            .ok_or("The options file path is not valid UTF-8.")?;
+        application.set_string_option("option_file_name", path);
+    }
+
+    application.optimize_tnlp(problem.clone())
+}
+
+/// Writes the options of the `ipopt_options` environment variable to an Ipopt options file.
+fn options_file() -> Result<Option<NamedTempFile>, Box<dyn Error>> {
+    let Ok(options) = env::var("ipopt_options") else {
+        return Ok(None);
+    };
+
+    let mut file = NamedTempFile::new()?;
+    let mut tokens = options.split_whitespace();
+    while let Some(token) = tokens.next() {
+        let (name, value) = match token.split_once('=') {
+            Some(option) => option,
+            None => (
+                token,
+                tokens
+                    .next()
+                    .ok_or_else(|| format!("The option `{token}` has no value."))?,
+            ),
+        };
+        writeln!(file, "{name} {value}")?;
+    }
+    file.flush()?;
+
+    Ok(Some(file))
+}
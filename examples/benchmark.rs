@@ -0,0 +1,29 @@
+//! Compares `mu_strategy` settings on the Hock-Schittkowski test problems, and writes the
+//! iteration performance profile to `profile.csv`.
+
+use ipopt_bindgen::{Application, Benchmark, BenchmarkMetric, hock_schittkowski_problems};
+use std::{error::Error, fs::File};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut benchmark = Benchmark::new();
+    for problem in hock_schittkowski_problems() {
+        benchmark.add_problem(problem.name().to_string(), move || problem.clone());
+    }
+
+    for mu_strategy in ["monotone", "adaptive"] {
+        let mut application = Application::new();
+        application
+            .set_string_option("mu_strategy", mu_strategy)
+            .set_integer_option("print_level", 0);
+        benchmark.add_configuration(mu_strategy, application);
+    }
+
+    let results = benchmark.run();
+    println!("{results}");
+
+    results
+        .performance_profile(BenchmarkMetric::Iterations)
+        .write_csv(File::create("profile.csv")?)?;
+
+    Ok(())
+}
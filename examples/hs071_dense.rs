@@ -0,0 +1,101 @@
+//! HS071 example using the `DenseTnlp` trait, which does not require sparsity patterns.
+
+use ipopt_bindgen::{Application, Bounds, DenseAdapter, DenseTnlp, InitialSolution};
+use std::error::Error;
+
+struct HS071;
+
+const N: usize = 4;
+
+impl DenseTnlp for HS071 {
+    fn number_of_variables(&self) -> usize {
+        N
+    }
+
+    fn number_of_constraints(&self) -> usize {
+        2
+    }
+
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
+        Bounds::range(1.0, 5.0).fill(x_l, x_u);
+        Bounds::lower(25.0).write_to(&mut g_l[0], &mut g_u[0]);
+        Bounds::equal(40.0).write_to(&mut g_l[1], &mut g_u[1]);
+    }
+
+    fn get_starting_point(&self) -> InitialSolution {
+        InitialSolution::from_variables(vec![1.0, 5.0, 5.0, 1.0])
+    }
+
+    fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+        *obj_value = x[0] * x[3] * (x[0] + x[1] + x[2]) + x[2];
+        true
+    }
+
+    fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+        grad_f[0] = x[0] * x[3] + x[3] * (x[0] + x[1] + x[2]);
+        grad_f[1] = x[0] * x[3];
+        grad_f[2] = x[0] * x[3] + 1.0;
+        grad_f[3] = x[0] * (x[0] + x[1] + x[2]);
+        true
+    }
+
+    fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+        g[0] = x[0] * x[1] * x[2] * x[3];
+        g[1] = x[0] * x[0] + x[1] * x[1] + x[2] * x[2] + x[3] * x[3];
+        true
+    }
+
+    fn eval_jac_g(&mut self, x: &[f64], jacobian: &mut [f64]) -> bool {
+        let (first, second) = jacobian.split_at_mut(N);
+        first.copy_from_slice(&[
+            x[1] * x[2] * x[3],
+            x[0] * x[2] * x[3],
+            x[0] * x[1] * x[3],
+            x[0] * x[1] * x[2],
+        ]);
+        for (value, x) in second.iter_mut().zip(x) {
+            *value = 2.0 * x;
+        }
+        true
+    }
+
+    fn eval_h(&mut self, x: &[f64], obj_factor: f64, lambda: &[f64], hessian: &mut [f64]) -> bool {
+        let mut set = |row: usize, col: usize, value: f64| hessian[row * N + col] += value;
+
+        // the objective
+        set(0, 0, obj_factor * 2.0 * x[3]);
+        set(1, 0, obj_factor * x[3]);
+        set(2, 0, obj_factor * x[3]);
+        set(3, 0, obj_factor * (2.0 * x[0] + x[1] + x[2]));
+        set(3, 1, obj_factor * x[0]);
+        set(3, 2, obj_factor * x[0]);
+
+        // the first constraint
+        set(1, 0, lambda[0] * x[2] * x[3]);
+        set(2, 0, lambda[0] * x[1] * x[3]);
+        set(2, 1, lambda[0] * x[0] * x[3]);
+        set(3, 0, lambda[0] * x[1] * x[2]);
+        set(3, 1, lambda[0] * x[0] * x[2]);
+        set(3, 2, lambda[0] * x[0] * x[1]);
+
+        // the second constraint
+        for i in 0..N {
+            set(i, i, lambda[1] * 2.0);
+        }
+
+        true
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut application = Application::new();
+    application
+        .set_string_option("mu_strategy", "adaptive")
+        .set_numeric_option("tol", 3.82e-6);
+    let results = application.optimize_tnlp(DenseAdapter::new(HS071))?;
+
+    println!("Objective value: {}", results.solution.objective);
+    println!("Solution: {:?}", results.solution.x);
+
+    Ok(())
+}
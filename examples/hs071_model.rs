@@ -0,0 +1,28 @@
+//! HS071 example using the `Model` modeling layer, which derives the derivatives symbolically.
+
+use ipopt_bindgen::{Application, Expr, Model};
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut model = Model::new();
+    let x: Vec<_> = [1.0, 5.0, 5.0, 1.0]
+        .into_iter()
+        .enumerate()
+        .map(|(j, initial)| model.add_var(format!("x{}", j + 1), 1.0, 5.0, initial))
+        .collect();
+
+    model.minimize(x[0] * x[3] * (x[0] + x[1] + x[2]) + x[2]);
+    model.add_constraint(x[0] * x[1] * x[2] * x[3], 25.0, f64::INFINITY);
+    model.add_constraint(Expr::sum(x.iter().map(|x| x.powi(2))), 40.0, 40.0);
+
+    let mut application = Application::new();
+    application
+        .set_string_option("mu_strategy", "adaptive")
+        .set_numeric_option("tol", 3.82e-6);
+    let results = application.optimize_tnlp(model.compile()?)?;
+
+    println!("Objective value: {}", results.solution.objective);
+    println!("Solution: {:?}", results.solution.x);
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use ipopt_bindgen::{Application, InitialSolution, ProblemSize, Tnlp};
+use ipopt_bindgen::{Application, Bounds, InitialSolution, ProblemSize, Tnlp};
 use std::error::Error;
 
 struct HS071;
@@ -19,12 +19,9 @@ impl Tnlp for HS071 {
     }
 
     fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
-        x_l.fill(1.0);
-        x_u.fill(5.0);
-        g_l[0] = 25.0;
-        g_u[0] = 2e19;
-        g_l[1] = 40.0;
-        g_u[1] = 40.0;
+        Bounds::range(1.0, 5.0).fill(x_l, x_u);
+        Bounds::lower(25.0).write_to(&mut g_l[0], &mut g_u[0]);
+        Bounds::equal(40.0).write_to(&mut g_l[1], &mut g_u[1]);
     }
 
     fn get_starting_point(&self) -> InitialSolution {
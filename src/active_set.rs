@@ -0,0 +1,349 @@
+//! # Ipopt Bindgen Active Set
+//!
+//! Defines the identification of the active variable bounds and constraints at the solution of an
+//! optimization.
+//!
+//! Since Ipopt is an interior point method, the final iterate never lies exactly on a bound, so
+//! activity is decided by comparing the distance to each bound to a tolerance, using the sign of
+//! the multipliers where this is ambiguous.
+
+use crate::{
+    analysis::check_dimensions,
+    bounds::{Bound, BoundInfinities},
+    results::OptimizationResult,
+    tnlp::ProblemBounds,
+};
+use std::error::Error;
+
+/// The status of a variable with respect to its bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariableStatus {
+    /// The variable is strictly between its bounds.
+    Free,
+
+    /// The variable is at its lower bound.
+    AtLower,
+
+    /// The variable is at its upper bound.
+    AtUpper,
+
+    /// The lower and upper bounds of the variable are equal.
+    Fixed,
+}
+
+/// The status of a constraint with respect to its bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintStatus {
+    /// The constraint is strictly between its bounds.
+    Inactive,
+
+    /// The constraint is at its lower bound.
+    ActiveLower,
+
+    /// The constraint is at its upper bound.
+    ActiveUpper,
+
+    /// The lower and upper bounds of the constraint are equal.
+    Equality,
+}
+
+/// The tolerances used to identify the active set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActiveSetTolerances {
+    /// A bound is active if the distance to it is at most `bound * max(1, |bound value|)`.
+    pub bound: f64,
+
+    /// An active bound is degenerate if the magnitude of its multiplier is at most this value.
+    pub multiplier: f64,
+
+    /// The values at or beyond which bounds are infinite, and so never active. These should match
+    /// `Application::bound_infinities` of the application that optimized the problem.
+    pub bound_infinities: BoundInfinities,
+}
+
+impl Default for ActiveSetTolerances {
+    fn default() -> Self {
+        ActiveSetTolerances {
+            bound: 1e-6,
+            multiplier: 1e-8,
+            bound_infinities: BoundInfinities::default(),
+        }
+    }
+}
+
+/// The active set at the solution of an optimization.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ActiveSet {
+    /// The status of each variable.
+    pub variables: Vec<VariableStatus>,
+
+    /// The status of each constraint.
+    pub constraints: Vec<ConstraintStatus>,
+
+    /// The indices of the variables that are at a bound but whose bound multiplier is zero.
+    pub degenerate_variables: Vec<usize>,
+
+    /// The indices of the inequality constraints that are at a bound but whose multiplier is zero.
+    pub degenerate_constraints: Vec<usize>,
+}
+
+impl ActiveSet {
+    /// Gets the indices of the variables that are at one of their bounds (excluding fixed
+    /// variables).
+    #[must_use]
+    pub fn active_variables(&self) -> Vec<usize> {
+        indices_where(&self.variables, |status| {
+            matches!(status, VariableStatus::AtLower | VariableStatus::AtUpper)
+        })
+    }
+
+    /// Gets the indices of the inequality constraints that are at one of their bounds.
+    #[must_use]
+    pub fn active_constraints(&self) -> Vec<usize> {
+        indices_where(&self.constraints, |status| {
+            matches!(
+                status,
+                ConstraintStatus::ActiveLower | ConstraintStatus::ActiveUpper
+            )
+        })
+    }
+}
+
+impl OptimizationResult {
+    /// Identifies the active variable bounds and constraints at the solution.
+    ///
+    /// The constraint values reported by Ipopt in `Solution::constraints` are used, so the problem
+    /// does not need to be evaluated again. Bounds are classified as infinite with
+    /// `ActiveSetTolerances::bound_infinities`.
+    ///
+    /// # Parameters
+    ///
+    /// - `bounds` - The bounds of the problem that was optimized.
+    /// - `tolerances` - The tolerances used to decide activity and degeneracy.
+    ///
+    /// # Errors
+    ///
+    /// The dimensions of the bounds do not match the solution.
+    pub fn active_set(
+        &self,
+        bounds: &ProblemBounds,
+        tolerances: &ActiveSetTolerances,
+    ) -> Result<ActiveSet, Box<dyn Error>> {
+        let solution = &self.solution;
+        let (n, m) = (solution.x.len(), solution.lambda.len());
+        check_dimensions(n, m, bounds, solution)?;
+        if solution.constraints.len() != m {
+            return Err(format!(
+                "`constraints` has length {0}, but the problem expects {m}.",
+                solution.constraints.len()
+            )
+            .into());
+        }
+
+        let infinities = tolerances.bound_infinities;
+        let mut active_set = ActiveSet::default();
+
+        for j in 0..n {
+            let (x, lower, upper) = (solution.x[j], bounds.x_l[j], bounds.x_u[j]);
+            let (z_l, z_u) = (solution.z_l[j], solution.z_u[j]);
+            let at_lower = is_at_bound(x - lower, infinities.lower_bound(lower), tolerances);
+            let at_upper = is_at_bound(upper - x, infinities.upper_bound(upper), tolerances);
+
+            #[allow(clippy::float_cmp)]
+            let status = if lower == upper {
+                VariableStatus::Fixed
+            } else {
+                match (at_lower, at_upper) {
+                    (true, true) if z_u > z_l => VariableStatus::AtUpper,
+                    (true, _) => VariableStatus::AtLower,
+                    (false, true) => VariableStatus::AtUpper,
+                    (false, false) => VariableStatus::Free,
+                }
+            };
+
+            active_set.variables.push(status);
+
+            let multiplier = match status {
+                VariableStatus::AtLower => z_l,
+                VariableStatus::AtUpper => z_u,
+                VariableStatus::Free | VariableStatus::Fixed => continue,
+            };
+            if multiplier.abs() <= tolerances.multiplier {
+                active_set.degenerate_variables.push(j);
+            }
+        }
+
+        for i in 0..m {
+            let (g, lower, upper) = (solution.constraints[i], bounds.g_l[i], bounds.g_u[i]);
+            let lambda = solution.lambda[i];
+            let at_lower = is_at_bound(g - lower, infinities.lower_bound(lower), tolerances);
+            let at_upper = is_at_bound(upper - g, infinities.upper_bound(upper), tolerances);
+
+            // With the Ipopt sign convention, the multiplier of an active lower bound is negative.
+            #[allow(clippy::float_cmp)]
+            let status = if lower == upper {
+                ConstraintStatus::Equality
+            } else {
+                match (at_lower, at_upper) {
+                    (true, true) if lambda > 0.0 => ConstraintStatus::ActiveUpper,
+                    (true, _) => ConstraintStatus::ActiveLower,
+                    (false, true) => ConstraintStatus::ActiveUpper,
+                    (false, false) => ConstraintStatus::Inactive,
+                }
+            };
+
+            let is_active = matches!(
+                status,
+                ConstraintStatus::ActiveLower | ConstraintStatus::ActiveUpper
+            );
+            if is_active && lambda.abs() <= tolerances.multiplier {
+                active_set.degenerate_constraints.push(i);
+            }
+            active_set.constraints.push(status);
+        }
+
+        Ok(active_set)
+    }
+}
+
+/// Checks whether the distance to a bound is within the tolerance. Infinite bounds are never
+/// active.
+fn is_at_bound(distance: f64, bound: Bound, tolerances: &ActiveSetTolerances) -> bool {
+    match bound {
+        Bound::Finite(value) => distance <= tolerances.bound * value.abs().max(1.0),
+        Bound::Infinite => false,
+    }
+}
+
+/// Gets the indices of the statuses that match the predicate.
+fn indices_where<T: Copy>(statuses: &[T], predicate: impl Fn(T) -> bool) -> Vec<usize> {
+    statuses
+        .iter()
+        .enumerate()
+        .filter(|&(_, &status)| predicate(status))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        results::{PerformanceResults, Solution},
+        tnlp::ProblemNames,
+    };
+
+    fn bounds() -> ProblemBounds {
+        ProblemBounds {
+            x_l: vec![0.0, 0.0, 1.0, -2e19],
+            x_u: vec![1.0, 1.0, 1.0, 2e19],
+            g_l: vec![1.0, -2e19, 0.0, 2.0],
+            g_u: vec![2e19, 4.0, 0.0, 3.0],
+        }
+    }
+
+    fn result(
+        x: Vec<f64>,
+        z_l: Vec<f64>,
+        z_u: Vec<f64>,
+        constraints: Vec<f64>,
+        lambda: Vec<f64>,
+    ) -> OptimizationResult {
+        OptimizationResult {
+            solution: Solution {
+                x,
+                constraints,
+                lambda,
+                z_l,
+                z_u,
+                objective: 0.0,
+            },
+            performance: PerformanceResults::default(),
+            status: 0,
+            stop_reason: None,
+            output: None,
+            final_statistics: None,
+            names: ProblemNames::default(),
+        }
+    }
+
+    #[test]
+    fn optimization_result_active_set_classifies_variables_and_constraints() {
+        let result = result(
+            vec![1e-9, 1.0 - 1e-9, 1.0, 5.0],
+            vec![2.0, 0.0, 0.0, 0.0],
+            vec![0.0, 1e-12, 0.0, 0.0],
+            vec![1.0, 4.0, 0.0, 2.5],
+            vec![-3.0, 0.0, 1.0, 0.0],
+        );
+
+        let active_set = result
+            .active_set(&bounds(), &ActiveSetTolerances::default())
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                VariableStatus::AtLower,
+                VariableStatus::AtUpper,
+                VariableStatus::Fixed,
+                VariableStatus::Free
+            ],
+            active_set.variables
+        );
+        assert_eq!(
+            vec![
+                ConstraintStatus::ActiveLower,
+                ConstraintStatus::ActiveUpper,
+                ConstraintStatus::Equality,
+                ConstraintStatus::Inactive
+            ],
+            active_set.constraints
+        );
+        assert_eq!(vec![1], active_set.degenerate_variables);
+        assert_eq!(vec![1], active_set.degenerate_constraints);
+        assert_eq!(vec![0, 1], active_set.active_variables());
+        assert_eq!(vec![0, 1], active_set.active_constraints());
+    }
+
+    #[test]
+    fn optimization_result_active_set_ignores_bounds_beyond_bound_infinities() {
+        let result = result(vec![1e10], vec![0.0], vec![0.0], vec![-1e10], vec![0.0]);
+        let bounds = ProblemBounds {
+            x_l: vec![0.0],
+            x_u: vec![1e10],
+            g_l: vec![-1e10],
+            g_u: vec![0.0],
+        };
+        let tolerances = ActiveSetTolerances {
+            bound_infinities: BoundInfinities {
+                lower: -1e10,
+                upper: 1e10,
+            },
+            ..Default::default()
+        };
+
+        let default_active_set = result
+            .active_set(&bounds, &ActiveSetTolerances::default())
+            .unwrap();
+        let active_set = result.active_set(&bounds, &tolerances).unwrap();
+
+        assert_eq!(vec![VariableStatus::AtUpper], default_active_set.variables);
+        assert_eq!(
+            vec![ConstraintStatus::ActiveLower],
+            default_active_set.constraints
+        );
+        assert_eq!(vec![VariableStatus::Free], active_set.variables);
+        assert_eq!(vec![ConstraintStatus::Inactive], active_set.constraints);
+    }
+
+    #[test]
+    fn optimization_result_active_set_rejects_mismatched_bounds() {
+        let result = result(vec![0.0], vec![0.0], vec![0.0], vec![], vec![]);
+
+        assert!(
+            result
+                .active_set(&bounds(), &ActiveSetTolerances::default())
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,1155 @@
+//! # Ipopt Bindgen AMPL
+//!
+//! Defines a reader for AMPL `.nl` files and a writer for AMPL `.sol` files, so that the crate can
+//! be used as the solver of an AMPL session.
+//!
+//! An `.nl` file is read into a `Model`, with the expression graphs of the objective, constraints
+//! and defined variables as `Expr` trees. The model is compiled into a `NlProblem`, which
+//! implements `Tnlp` with the derivatives derived by the modeling layer.
+//!
+//! Both the text (`g`) and binary (`b`) formats are supported. Binary files are read as
+//! little-endian. Integrality of variables is ignored, i.e. the continuous relaxation is solved.
+//! Imported functions, logical and complementarity constraints, and non-smooth operators such as
+//! `abs`, `min`, `max` and `if` are not supported and are reported as errors. The hyperbolic and
+//! inverse hyperbolic functions are written with `exp`, `ln` and `sqrt`, and `atan2(y, x)` as
+//! `2 atan(y / (sqrt(x² + y²) + x))`, which is undefined on the negative x-axis.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use ipopt_bindgen::{Application, NlProblem};
+//! use std::fs::File;
+//!
+//! let problem = NlProblem::from_file("model.nl").unwrap();
+//! let application = Application::new();
+//! let result = application.optimize_tnlp(problem.clone()).unwrap();
+//! problem
+//!     .write_sol(File::create("model.sol").unwrap(), &result)
+//!     .unwrap();
+//! ```
+
+#[allow(clippy::wildcard_imports)]
+use crate::c_interface::*;
+use crate::{
+    modeling::{CompiledModel, Expr, Model, Var},
+    results::OptimizationResult,
+    tnlp::{InitialSolution, IntermediateData, OptionValue, ProblemSize, Tnlp, UserScaling},
+};
+use std::{error::Error, f64::consts::LN_10, fs, io, io::Write, path::Path};
+
+/// A problem read from an AMPL `.nl` file, which implements `Tnlp`.
+///
+/// Only the first objective of the file is optimized. A maximization objective is optimized by
+/// setting the `obj_scaling_factor` option to `-1` through `Tnlp::default_options`, so the
+/// reported objective keeps the sign of the original objective.
+#[derive(Debug, Clone)]
+pub struct NlProblem {
+    model: CompiledModel,
+    maximize: bool,
+    initial_duals: Option<Vec<f64>>,
+    options: Vec<i32>,
+    vbtol: Option<f64>,
+}
+
+impl NlProblem {
+    /// Reads a problem from an `.nl` file.
+    ///
+    /// # Parameters
+    ///
+    /// - `path` - The path of the `.nl` file.
+    ///
+    /// # Errors
+    ///
+    /// - The file cannot be read.
+    /// - The file is not a valid `.nl` file or uses an unsupported feature.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        Self::parse(&fs::read(path)?)
+    }
+
+    /// Reads a problem from the contents of an `.nl` file.
+    ///
+    /// # Parameters
+    ///
+    /// - `bytes` - The contents of the `.nl` file, in the text or binary format.
+    ///
+    /// # Errors
+    ///
+    /// The contents are not a valid `.nl` file or use an unsupported feature.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let (header, body) = Header::parse(bytes)?;
+        header.check_supported()?;
+        let mut segments = Segments::new(&header);
+        let mut reader = Reader {
+            bytes: &bytes[body..],
+            position: body,
+            binary: header.binary,
+        };
+        segments.read(&mut reader, &header)?;
+
+        let (model, maximize, initial_duals) = segments.into_model();
+        Ok(NlProblem {
+            model: model.compile()?,
+            maximize,
+            initial_duals,
+            options: header.options,
+            vbtol: header.vbtol,
+        })
+    }
+
+    /// Gets the number of variables.
+    #[must_use]
+    pub fn number_of_variables(&self) -> usize {
+        self.model.jacobian_pattern().columns()
+    }
+
+    /// Gets the number of constraints.
+    #[must_use]
+    pub fn number_of_constraints(&self) -> usize {
+        self.model.jacobian_pattern().rows()
+    }
+
+    /// Checks whether the objective is maximized.
+    #[must_use]
+    pub fn is_maximization(&self) -> bool {
+        self.maximize
+    }
+
+    /// Writes the result of the optimization as an AMPL `.sol` file in the text format.
+    ///
+    /// The duals are written with the AMPL sign convention, i.e. as `-λ`, and the solve result
+    /// number is given by `solve_result_number`.
+    ///
+    /// # Parameters
+    ///
+    /// - `writer` - The destination of the `.sol` file.
+    /// - `result` - The result of optimizing this problem.
+    ///
+    /// # Errors
+    ///
+    /// Writing to `writer` fails.
+    pub fn write_sol(&self, mut writer: impl Write, result: &OptimizationResult) -> io::Result<()> {
+        let solution = &result.solution;
+        write_sol_header(&mut writer, result.status_name(), &self.options, self.vbtol)?;
+        let (m, n) = (solution.lambda.len(), solution.x.len());
+        writeln!(writer, "{m}\n{m}\n{n}\n{n}")?;
+        for lambda in &solution.lambda {
+            writeln!(writer, "{}", -lambda)?;
+        }
+        for x in &solution.x {
+            writeln!(writer, "{x}")?;
+        }
+        writeln!(writer, "objno 0 {}", solve_result_number(result.status))
+    }
+
+    /// Writes an AMPL `.sol` file without a solution, for when the optimization failed to run.
+    ///
+    /// The solve result number is `SOLVE_RESULT_FAILURE`, so AMPL reports the failure instead of
+    /// waiting for a `.sol` file or reading a stale one.
+    ///
+    /// # Parameters
+    ///
+    /// - `writer` - The destination of the `.sol` file.
+    /// - `message` - The message that AMPL shows, e.g. the error of the optimization.
+    ///
+    /// # Errors
+    ///
+    /// Writing to `writer` fails.
+    pub fn write_failure_sol(&self, mut writer: impl Write, message: &str) -> io::Result<()> {
+        write_sol_header(&mut writer, message, &self.options, self.vbtol)?;
+        let (m, n) = (self.number_of_constraints(), self.number_of_variables());
+        writeln!(writer, "{m}\n0\n{n}\n0")?;
+        writeln!(writer, "objno 0 {SOLVE_RESULT_FAILURE}")
+    }
+
+    /// Writes an AMPL `.sol` file without a solution for an `.nl` file that could not be read,
+    /// e.g. because it uses an unsupported feature.
+    ///
+    /// The options and dimensions are taken from the header of the `.nl` file if it can be read,
+    /// and are otherwise omitted and zero. The solve result number is `SOLVE_RESULT_FAILURE`.
+    ///
+    /// # Parameters
+    ///
+    /// - `writer` - The destination of the `.sol` file.
+    /// - `bytes` - The contents of the `.nl` file, which may be empty if it could not be read.
+    /// - `message` - The message that AMPL shows, e.g. the error of reading the `.nl` file.
+    ///
+    /// # Errors
+    ///
+    /// Writing to `writer` fails.
+    pub fn write_parse_failure_sol(
+        mut writer: impl Write,
+        bytes: &[u8],
+        message: &str,
+    ) -> io::Result<()> {
+        let header = Header::parse(bytes).ok().map(|(header, _)| header);
+        let (options, vbtol) = header.as_ref().map_or((&[][..], None), |header| {
+            (&header.options[..], header.vbtol)
+        });
+        write_sol_header(&mut writer, message, options, vbtol)?;
+        let (m, n) = header.map_or((0, 0), |header| (header.m, header.n));
+        writeln!(writer, "{m}\n0\n{n}\n0")?;
+        writeln!(writer, "objno 0 {SOLVE_RESULT_FAILURE}")
+    }
+}
+
+/// Writes the message and options of a `.sol` file.
+fn write_sol_header(
+    writer: &mut impl Write,
+    message: &str,
+    options: &[i32],
+    vbtol: Option<f64>,
+) -> io::Result<()> {
+    // The message ends at the first empty line, so it cannot contain one.
+    let lines: Vec<_> = message
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    write!(writer, "ipopt_bindgen: {}\n\n", lines.join("\n"))?;
+    if !options.is_empty() {
+        writeln!(writer, "Options\n{}", options.len())?;
+        for option in options {
+            writeln!(writer, "{option}")?;
+        }
+        if let Some(vbtol) = vbtol {
+            writeln!(writer, "{vbtol}")?;
+        }
+    }
+    Ok(())
+}
+
+/// The AMPL solve result number of an optimization that failed to run, e.g. because the options
+/// were invalid, in the failure range 500-599.
+pub const SOLVE_RESULT_FAILURE: i32 = 520;
+
+/// Gets the AMPL solve result number for an Ipopt return status.
+///
+/// The numbers follow the AMPL ranges: 0-99 solved, 200-299 infeasible, 300-399 unbounded,
+/// 400-499 limit reached and 500-599 failure.
+///
+/// # Parameters
+///
+/// - `status` - The Ipopt return status, e.g. `OptimizationResult::status`.
+#[must_use]
+pub fn solve_result_number(status: i32) -> i32 {
+    const SOLVE_RESULT_NUMBERS: [(ApplicationReturnStatus, i32); 10] = [
+        (ApplicationReturnStatus_Solve_Succeeded, 0),
+        (ApplicationReturnStatus_Solved_To_Acceptable_Level, 1),
+        (ApplicationReturnStatus_Feasible_Point_Found, 2),
+        (ApplicationReturnStatus_Infeasible_Problem_Detected, 200),
+        (ApplicationReturnStatus_Diverging_Iterates, 300),
+        (ApplicationReturnStatus_Maximum_Iterations_Exceeded, 400),
+        (ApplicationReturnStatus_Maximum_CpuTime_Exceeded, 401),
+        (ApplicationReturnStatus_Maximum_WallTime_Exceeded, 402),
+        (ApplicationReturnStatus_User_Requested_Stop, 403),
+        (ApplicationReturnStatus_Restoration_Failed, 500),
+    ];
+
+    SOLVE_RESULT_NUMBERS
+        .iter()
+        .find(|(known, _)| *known == status)
+        .map_or(510, |(_, number)| *number)
+}
+
+impl Tnlp for NlProblem {
+    fn get_nlp_info(&self) -> ProblemSize {
+        self.model.get_nlp_info()
+    }
+
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
+        self.model.get_bounds_info(x_l, x_u, g_l, g_u);
+    }
+
+    fn get_scaling(&self) -> UserScaling {
+        self.model.get_scaling()
+    }
+
+    fn get_starting_point(&self) -> InitialSolution {
+        InitialSolution {
+            lambda: self.initial_duals.clone(),
+            ..self.model.get_starting_point()
+        }
+    }
+
+    fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+        self.model.eval_f(x, obj_value)
+    }
+
+    fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+        self.model.eval_grad_f(x, grad_f)
+    }
+
+    fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+        self.model.eval_g(x, g)
+    }
+
+    fn get_jacobian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.model.get_jacobian_sparsity(n, m, i_row, j_col);
+    }
+
+    fn eval_jac_g(&mut self, x: &[f64], m: i32, values: &mut [f64]) -> bool {
+        self.model.eval_jac_g(x, m, values)
+    }
+
+    fn get_hessian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.model.get_hessian_sparsity(n, m, i_row, j_col);
+    }
+
+    fn eval_h(
+        &mut self,
+        x: &[f64],
+        obj_factor: f64,
+        lambda: &[f64],
+        m: i32,
+        values: &mut [f64],
+    ) -> bool {
+        self.model.eval_h(x, obj_factor, lambda, m, values)
+    }
+
+    fn intermediate(&mut self, data: IntermediateData) -> bool {
+        self.model.intermediate(data)
+    }
+
+    fn variable_names(&self) -> Option<Vec<String>> {
+        self.model.variable_names()
+    }
+
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        if self.maximize {
+            vec![("obj_scaling_factor".to_string(), OptionValue::Numeric(-1.0))]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// The header of an `.nl` file, which is always in the text format.
+#[derive(Debug)]
+struct Header {
+    binary: bool,
+    options: Vec<i32>,
+    vbtol: Option<f64>,
+    n: usize,
+    m: usize,
+    objectives: usize,
+    defined_variables: usize,
+    logical_constraints: usize,
+    imported_functions: usize,
+}
+
+impl Header {
+    /// The number of lines of the header.
+    const LINES: usize = 10;
+
+    /// Parses the header, returning it and the offset of the first segment.
+    fn parse(bytes: &[u8]) -> Result<(Header, usize), String> {
+        let mut lines = Vec::with_capacity(Self::LINES);
+        let mut start = 0;
+        while lines.len() < Self::LINES {
+            let length = bytes[start..]
+                .iter()
+                .position(|&byte| byte == b'\n')
+                .ok_or("The .nl header is truncated.")?;
+            let line = std::str::from_utf8(&bytes[start..start + length])
+                .map_err(|_| "The .nl header is not valid text.")?;
+            lines.push(line.split('#').next().unwrap_or_default());
+            start += length + 1;
+        }
+
+        let binary = match lines[0].bytes().next() {
+            Some(b'g') => false,
+            Some(b'b') => true,
+            _ => return Err("The .nl file does not start with 'g' or 'b'.".to_string()),
+        };
+        let first: Vec<&str> = lines[0][1..].split_whitespace().collect();
+        let count = first.first().map_or(Ok(0), |count| parse_number(count))?;
+        let options = first
+            .iter()
+            .skip(1)
+            .take(count)
+            .map(|option| parse_number(option))
+            .collect::<Result<Vec<i32>, _>>()?;
+        let vbtol = match (options.get(1), first.get(count + 1)) {
+            (Some(3), Some(vbtol)) => Some(parse_number(vbtol)?),
+            _ => None,
+        };
+
+        let numbers = |line: &str| {
+            line.split_whitespace()
+                .map(parse_number)
+                .collect::<Result<Vec<usize>, String>>()
+        };
+        let sizes = numbers(lines[1])?;
+        let functions = numbers(lines[5])?;
+        if sizes.len() < 3 {
+            return Err("The .nl header does not contain the problem dimensions.".to_string());
+        }
+
+        Ok((
+            Header {
+                binary,
+                options,
+                vbtol,
+                n: sizes[0],
+                m: sizes[1],
+                objectives: sizes[2],
+                defined_variables: numbers(lines[9])?.iter().sum(),
+                logical_constraints: sizes.get(5).copied().unwrap_or(0),
+                imported_functions: functions.get(1).copied().unwrap_or(0),
+            },
+            start,
+        ))
+    }
+
+    /// Checks that the problem does not use features that are not supported.
+    fn check_supported(&self) -> Result<(), String> {
+        if self.logical_constraints > 0 {
+            return Err("Logical constraints are not supported.".to_string());
+        }
+        if self.imported_functions > 0 {
+            return Err("Imported functions are not supported.".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Parses a number in the text format.
+fn parse_number<T: std::str::FromStr>(token: &str) -> Result<T, String> {
+    token
+        .parse()
+        .map_err(|_| format!("Invalid number '{token}' in the .nl file."))
+}
+
+/// A reader of the segments of an `.nl` file in the text or binary format.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    binary: bool,
+}
+
+impl Reader<'_> {
+    /// Skips whitespace and comments in the text format.
+    fn skip_whitespace(&mut self) {
+        if self.binary {
+            return;
+        }
+        while let Some(&byte) = self.bytes.first() {
+            if byte == b'#' {
+                let length = self
+                    .bytes
+                    .iter()
+                    .position(|&byte| byte == b'\n')
+                    .unwrap_or(self.bytes.len());
+                self.advance(length);
+            } else if byte.is_ascii_whitespace() {
+                self.advance(1);
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn advance(&mut self, length: usize) {
+        self.bytes = &self.bytes[length..];
+        self.position += length;
+    }
+
+    /// Checks whether all segments have been read.
+    fn at_end(&mut self) -> bool {
+        self.skip_whitespace();
+        self.bytes.is_empty()
+    }
+
+    /// Reads a single character, e.g. a segment or expression letter.
+    fn letter(&mut self) -> Result<u8, String> {
+        self.skip_whitespace();
+        let letter = *self
+            .bytes
+            .first()
+            .ok_or("Unexpected end of the .nl file.")?;
+        self.advance(1);
+        Ok(letter)
+    }
+
+    /// Reads `N` bytes in the binary format.
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let bytes = self
+            .bytes
+            .get(..N)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or("Unexpected end of the .nl file.")?;
+        self.advance(N);
+        Ok(bytes)
+    }
+
+    /// Reads a whitespace separated token in the text format.
+    fn token<T: std::str::FromStr>(&mut self) -> Result<T, String> {
+        self.skip_whitespace();
+        let length = self
+            .bytes
+            .iter()
+            .position(|byte| byte.is_ascii_whitespace() || *byte == b'#')
+            .unwrap_or(self.bytes.len());
+        let token = String::from_utf8_lossy(&self.bytes[..length]).into_owned();
+        self.advance(length);
+        parse_number(&token).map_err(|error| format!("{error} (at byte {}).", self.position))
+    }
+
+    /// Reads a name, which is a token in the text format and a string prefixed by its length in
+    /// the binary format.
+    fn name(&mut self) -> Result<String, String> {
+        if !self.binary {
+            return self.token();
+        }
+        let length = self.count()?;
+        let name = self
+            .bytes
+            .get(..length)
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+            .ok_or("Unexpected end of the .nl file.")?;
+        self.advance(length);
+        Ok(name)
+    }
+
+    fn integer(&mut self) -> Result<i64, String> {
+        if self.binary {
+            Ok(i32::from_le_bytes(self.take()?).into())
+        } else {
+            self.token()
+        }
+    }
+
+    fn short(&mut self) -> Result<i64, String> {
+        if self.binary {
+            Ok(i16::from_le_bytes(self.take()?).into())
+        } else {
+            self.token()
+        }
+    }
+
+    fn real(&mut self) -> Result<f64, String> {
+        if self.binary {
+            Ok(f64::from_le_bytes(self.take()?))
+        } else {
+            self.token()
+        }
+    }
+
+    /// Reads an index, which must be less than `limit`.
+    fn index(&mut self, limit: usize, what: &str) -> Result<usize, String> {
+        let index = self.integer()?;
+        usize::try_from(index)
+            .ok()
+            .filter(|&index| index < limit)
+            .ok_or_else(|| format!("Invalid {what} index {index} in the .nl file."))
+    }
+
+    /// Reads a count of entries.
+    fn count(&mut self) -> Result<usize, String> {
+        let count = self.integer()?;
+        usize::try_from(count).map_err(|_| format!("Invalid count {count} in the .nl file."))
+    }
+
+    /// Reads a list of `(index, value)` pairs.
+    fn pairs(&mut self, limit: usize, what: &str) -> Result<Vec<(usize, f64)>, String> {
+        let count = self.count()?;
+        (0..count)
+            .map(|_| Ok((self.index(limit, what)?, self.real()?)))
+            .collect()
+    }
+
+    /// Reads the bounds of a `b` or `r` segment.
+    fn bounds(&mut self, count: usize) -> Result<Vec<(f64, f64)>, String> {
+        (0..count)
+            .map(|_| match self.letter()? {
+                b'0' => Ok((self.real()?, self.real()?)),
+                b'1' => Ok((f64::NEG_INFINITY, self.real()?)),
+                b'2' => Ok((self.real()?, f64::INFINITY)),
+                b'3' => Ok((f64::NEG_INFINITY, f64::INFINITY)),
+                b'4' => {
+                    let value = self.real()?;
+                    Ok((value, value))
+                }
+                b'5' => Err("Complementarity constraints are not supported.".to_string()),
+                other => Err(format!("Invalid bound type '{}'.", char::from(other))),
+            })
+            .collect()
+    }
+
+    /// Reads an expression graph in prefix notation.
+    fn expression(&mut self, context: &Segments) -> Result<Expr, String> {
+        match self.letter()? {
+            b'n' => Ok(Expr::constant(self.real()?)),
+            #[allow(clippy::cast_precision_loss)]
+            b's' => Ok(Expr::constant(self.short()? as f64)),
+            #[allow(clippy::cast_precision_loss)]
+            b'l' => Ok(Expr::constant(self.integer()? as f64)),
+            b'v' => {
+                let limit = context.variables.len() + context.defined.len();
+                let index = self.index(limit, "variable")?;
+                match index.checked_sub(context.variables.len()) {
+                    None => Ok(context.variables[index].into()),
+                    Some(defined) => context.defined[defined].clone().ok_or_else(|| {
+                        format!("Defined variable {index} is used before it is defined.")
+                    }),
+                }
+            }
+            b'o' => {
+                let opcode = self.integer()?;
+                self.operation(opcode, context)
+            }
+            b'f' | b'h' => Err("Imported functions are not supported.".to_string()),
+            other => Err(format!(
+                "Unexpected '{}' in an expression at byte {}.",
+                char::from(other),
+                self.position
+            )),
+        }
+    }
+
+    /// Reads the operands of an operator and applies it.
+    fn operation(&mut self, opcode: i64, context: &Segments) -> Result<Expr, String> {
+        let unary = |reader: &mut Self, function: fn(&Expr) -> Expr| {
+            Ok(function(&reader.expression(context)?))
+        };
+        let binary = |reader: &mut Self, function: fn(Expr, Expr) -> Expr| {
+            let left = reader.expression(context)?;
+            Ok(function(left, reader.expression(context)?))
+        };
+
+        match opcode {
+            0 => binary(self, |a, b| a + b),
+            1 => binary(self, |a, b| a - b),
+            2 => binary(self, |a, b| a * b),
+            3 => binary(self, |a, b| a / b),
+            5 | 76 | 78 => binary(self, |a, b| power(&a, b)),
+            16 => unary(self, |a| -a),
+            37 => unary(self, |a| 1.0 - 2.0 / ((2.0 * a).exp() + 1.0)),
+            38 => unary(self, |a| a.sin() / a.cos()),
+            39 => unary(self, Expr::sqrt),
+            40 => unary(self, |a| (a.exp() - (-a).exp()) / 2.0),
+            41 => unary(self, Expr::sin),
+            42 => unary(self, |a| a.ln() / LN_10),
+            43 => unary(self, Expr::ln),
+            44 => unary(self, Expr::exp),
+            45 => unary(self, |a| (a.exp() + (-a).exp()) / 2.0),
+            46 => unary(self, Expr::cos),
+            47 => unary(self, |a| 0.5 * ((1.0 + a) / (1.0 - a)).ln()),
+            // The half-angle form of atan2(y, x), which is smooth away from the negative x-axis.
+            48 => binary(self, |y, x| {
+                2.0 * (&y / ((x.powi(2) + y.powi(2)).sqrt() + x)).atan()
+            }),
+            49 => unary(self, Expr::atan),
+            50 => unary(self, |a| (a + (a.powi(2) + 1.0).sqrt()).ln()),
+            51 => unary(self, Expr::asin),
+            52 => unary(self, |a| (a + (a.powi(2) - 1.0).sqrt()).ln()),
+            53 => unary(self, Expr::acos),
+            54 => {
+                let count = self.count()?;
+                let terms = (0..count)
+                    .map(|_| self.expression(context))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Expr::sum(terms))
+            }
+            77 => unary(self, |a| a.powi(2)),
+            _ => Err(format!("Unsupported .nl operator o{opcode}.")),
+        }
+    }
+}
+
+/// Raises `base` to the power `exponent`, using `powi` and `sqrt` for constant exponents where
+/// possible and `exp(exponent ln(base))` otherwise.
+fn power(base: &Expr, exponent: Expr) -> Expr {
+    match exponent.as_constant() {
+        Some(0.5) => base.sqrt(),
+        #[allow(clippy::cast_possible_truncation)]
+        Some(power) if power.fract() == 0.0 && power.abs() <= f64::from(i32::MAX) => {
+            base.powi(power as i32)
+        }
+        _ => (exponent * base.ln()).exp(),
+    }
+}
+
+/// The segments of an `.nl` file, gathered before the model is assembled.
+struct Segments {
+    variables: Vec<Var>,
+    defined: Vec<Option<Expr>>,
+    variable_bounds: Vec<(f64, f64)>,
+    constraint_bounds: Vec<(f64, f64)>,
+    initial_x: Vec<f64>,
+    initial_duals: Option<Vec<f64>>,
+    constraints: Vec<Expr>,
+    constraint_linear: Vec<Vec<(usize, f64)>>,
+    objective: Option<Expr>,
+    objective_linear: Vec<(usize, f64)>,
+    maximize: bool,
+}
+
+impl Segments {
+    fn new(header: &Header) -> Self {
+        Segments {
+            variables: (0..header.n).map(Var::from_index).collect(),
+            defined: vec![None; header.defined_variables],
+            variable_bounds: vec![(f64::NEG_INFINITY, f64::INFINITY); header.n],
+            constraint_bounds: vec![(f64::NEG_INFINITY, f64::INFINITY); header.m],
+            initial_x: vec![0.0; header.n],
+            initial_duals: None,
+            constraints: vec![Expr::constant(0.0); header.m],
+            constraint_linear: vec![Vec::new(); header.m],
+            objective: None,
+            objective_linear: Vec::new(),
+            maximize: false,
+        }
+    }
+
+    /// Reads all segments of the file.
+    fn read(&mut self, reader: &mut Reader, header: &Header) -> Result<(), String> {
+        let (n, m) = (header.n, header.m);
+        while !reader.at_end() {
+            match reader.letter()? {
+                b'V' => {
+                    let index = reader.index(n + self.defined.len(), "defined variable")?;
+                    let defined = index
+                        .checked_sub(n)
+                        .ok_or_else(|| format!("Invalid defined variable index {index}."))?;
+                    let linear_terms = reader.count()?;
+                    reader.integer()?;
+                    let linear = (0..linear_terms)
+                        .map(|_| Ok((reader.index(n, "variable")?, reader.real()?)))
+                        .collect::<Result<Vec<_>, String>>()?;
+                    let nonlinear = reader.expression(self)?;
+                    self.defined[defined] = Some(nonlinear + self.linear(&linear));
+                }
+                b'C' => {
+                    let index = reader.index(m, "constraint")?;
+                    self.constraints[index] = reader.expression(self)?;
+                }
+                b'O' => {
+                    let index = reader.index(header.objectives, "objective")?;
+                    let sense = reader.integer()?;
+                    let expression = reader.expression(self)?;
+                    if index == 0 {
+                        self.objective = Some(expression);
+                        self.maximize = sense != 0;
+                    }
+                }
+                b'd' => {
+                    let mut duals = vec![0.0; m];
+                    for (i, value) in reader.pairs(m, "constraint")? {
+                        // AMPL duals y satisfy ∇f - Jᵀy = 0, whereas Ipopt uses ∇f + Jᵀλ = 0.
+                        duals[i] = -value;
+                    }
+                    self.initial_duals = Some(duals);
+                }
+                b'x' => {
+                    for (j, value) in reader.pairs(n, "variable")? {
+                        self.initial_x[j] = value;
+                    }
+                }
+                b'r' => self.constraint_bounds = reader.bounds(m)?,
+                b'b' => self.variable_bounds = reader.bounds(n)?,
+                b'k' => {
+                    for _ in 0..reader.count()? {
+                        reader.integer()?;
+                    }
+                }
+                b'J' => {
+                    let index = reader.index(m, "constraint")?;
+                    self.constraint_linear[index] = reader.pairs(n, "variable")?;
+                }
+                b'G' => {
+                    let index = reader.index(header.objectives, "objective")?;
+                    let linear = reader.pairs(n, "variable")?;
+                    if index == 0 {
+                        self.objective_linear = linear;
+                    }
+                }
+                b'S' => Self::skip_suffix(reader)?,
+                b'F' => return Err("Imported functions are not supported.".to_string()),
+                b'L' => return Err("Logical constraints are not supported.".to_string()),
+                other => {
+                    return Err(format!(
+                        "Unexpected segment '{}' at byte {}.",
+                        char::from(other),
+                        reader.position
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Skips a suffix segment, which Ipopt has no use for.
+    fn skip_suffix(reader: &mut Reader) -> Result<(), String> {
+        let kind = reader.integer()?;
+        let count = reader.count()?;
+        reader.name()?;
+        for _ in 0..count {
+            reader.integer()?;
+            if kind & 4 == 0 {
+                reader.integer()?;
+            } else {
+                reader.real()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds the linear part of a function.
+    fn linear(&self, terms: &[(usize, f64)]) -> Expr {
+        Expr::sum(
+            terms
+                .iter()
+                .map(|&(j, coefficient)| coefficient * self.variables[j]),
+        )
+    }
+
+    /// Assembles the model, returning it with the objective sense and the initial duals.
+    fn into_model(self) -> (Model, bool, Option<Vec<f64>>) {
+        let mut model = Model::new();
+        for (j, (&(lower, upper), &initial)) in
+            self.variable_bounds.iter().zip(&self.initial_x).enumerate()
+        {
+            model.add_var(format!("_svar[{}]", j + 1), lower, upper, initial);
+        }
+        for (i, (lower, upper)) in self.constraint_bounds.iter().copied().enumerate() {
+            let body = &self.constraints[i] + self.linear(&self.constraint_linear[i]);
+            model.add_constraint(body, lower, upper);
+        }
+        let objective = self
+            .objective
+            .clone()
+            .unwrap_or_else(|| Expr::constant(0.0));
+        model.minimize(objective + self.linear(&self.objective_linear));
+
+        (model, self.maximize, self.initial_duals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::Solution;
+
+    /// The HS071 problem as written by AMPL, with the linear `x2` term of the objective in the
+    /// `G` segment.
+    const HS071: &str = "g3 1 1 0\t# problem hs071
+ 4 2 1 0 1\t# vars, constraints, objectives, ranges, eqns
+ 2 1\t# nonlinear constraints, objectives
+ 0 0\t# network constraints: nonlinear, linear
+ 4 4 4\t# nonlinear vars in constraints, objectives, both
+ 0 0 0 1\t# linear network variables; functions; arith, flags
+ 0 0 0 0 0\t# discrete variables: binary, integer, nonlinear (b,c,o)
+ 8 4\t# nonzeros in Jacobian, gradients
+ 0 0\t# max name lengths: constraints, variables
+ 0 0 0 0 0\t# common exprs: b,c,o,c1,o1
+C0\t#c0
+o2\t#*
+v0
+o2
+v1
+o2
+v2
+v3
+C1
+o54\t#sumlist
+4
+o5
+v0
+n2
+o5
+v1
+n2
+o5
+v2
+n2
+o5
+v3
+n2
+O0 0
+o2
+o2
+v0
+v3
+o54
+3
+v0
+v1
+v2
+x4
+0 1
+1 5
+2 5
+3 1
+r
+2 25
+4 40
+b
+0 1 5
+0 1 5
+0 1 5
+0 1 5
+k3
+2
+4
+6
+J0 4
+0 0
+1 0
+2 0
+3 0
+J1 4
+0 0
+1 0
+2 0
+3 0
+G0 4
+0 0
+1 0
+2 1
+3 0
+";
+
+    /// Builds a binary `.nl` file that maximizes `-(3 x0)²` over `-1 ≤ x0 ≤ 3`, where `3 x0` is a
+    /// defined variable.
+    fn binary_problem() -> Vec<u8> {
+        let mut bytes = b"b3 1 1 0
+ 1 0 1 0 0
+ 0 1
+ 0 0
+ 0 1 0
+ 0 0 0 1
+ 0 0 0 0 0
+ 0 1
+ 0 0
+ 0 0 1 0 0
+"
+        .to_vec();
+        let int = |bytes: &mut Vec<u8>, value: i32| bytes.extend(value.to_le_bytes());
+        let real = |bytes: &mut Vec<u8>, value: f64| bytes.extend(value.to_le_bytes());
+
+        bytes.push(b'V');
+        for value in [1, 1, 0, 0] {
+            int(&mut bytes, value);
+        }
+        real(&mut bytes, 3.0);
+        bytes.push(b'n');
+        real(&mut bytes, 0.0);
+
+        bytes.push(b'O');
+        int(&mut bytes, 0);
+        int(&mut bytes, 1);
+        bytes.push(b'o');
+        int(&mut bytes, 16);
+        bytes.push(b'o');
+        int(&mut bytes, 5);
+        bytes.push(b'v');
+        int(&mut bytes, 1);
+        bytes.push(b's');
+        bytes.extend(2_i16.to_le_bytes());
+
+        bytes.extend(b"b0");
+        real(&mut bytes, -1.0);
+        real(&mut bytes, 3.0);
+
+        bytes.push(b'x');
+        int(&mut bytes, 1);
+        int(&mut bytes, 0);
+        real(&mut bytes, 1.0);
+        bytes
+    }
+
+    #[test]
+    fn nl_problem_parses_text_hs071() {
+        let mut problem = NlProblem::parse(HS071.as_bytes()).unwrap();
+        let x = [1.0, 5.0, 5.0, 1.0];
+        let (mut f, mut grad_f, mut g) = (0.0, vec![0.0; 4], vec![0.0; 2]);
+        let mut bounds = (vec![0.0; 4], vec![0.0; 4], vec![0.0; 2], vec![0.0; 2]);
+
+        assert!(problem.eval_f(&x, &mut f));
+        assert!(problem.eval_grad_f(&x, &mut grad_f));
+        assert!(problem.eval_g(&x, &mut g));
+        problem.get_bounds_info(&mut bounds.0, &mut bounds.1, &mut bounds.2, &mut bounds.3);
+
+        assert_eq!(
+            ProblemSize {
+                n: 4,
+                m: 2,
+                nnz_jac: 8,
+                nnz_hess: 10,
+            },
+            problem.get_nlp_info()
+        );
+        assert_eq!(
+            vec![16.0, 12.0, 1.0, 2.0, 11.0, 25.0, 52.0],
+            [vec![f], grad_f, g].concat()
+        );
+        assert_eq!(vec![1.0; 4], bounds.0);
+        assert_eq!(vec![25.0, 40.0], bounds.2);
+        assert_eq!(vec![f64::INFINITY, 40.0], bounds.3);
+        assert_eq!(x.to_vec(), problem.get_starting_point().x);
+        assert!(!problem.is_maximization());
+    }
+
+    #[test]
+    fn nl_problem_parses_binary_maximization_with_defined_variable() {
+        let mut problem = NlProblem::parse(&binary_problem()).unwrap();
+        let (mut f, mut grad_f) = (0.0, vec![0.0]);
+        let (mut x_l, mut x_u) = (vec![0.0], vec![0.0]);
+
+        assert!(problem.eval_f(&[1.0], &mut f));
+        assert!(problem.eval_grad_f(&[1.0], &mut grad_f));
+        problem.get_bounds_info(&mut x_l, &mut x_u, &mut [], &mut []);
+
+        assert_eq!(
+            vec![-9.0, -18.0, -1.0, 3.0],
+            [vec![f], grad_f, x_l, x_u].concat()
+        );
+        assert_eq!(vec![1.0], problem.get_starting_point().x);
+        assert!(problem.is_maximization());
+        assert_eq!(
+            vec![("obj_scaling_factor".to_string(), OptionValue::Numeric(-1.0))],
+            problem.default_options()
+        );
+    }
+
+    #[test]
+    fn nl_problem_skips_binary_suffixes() {
+        let mut bytes = binary_problem();
+        let int = |bytes: &mut Vec<u8>, value: i32| bytes.extend(value.to_le_bytes());
+        for (kind, name) in [(0, "sstatus"), (4, "scale")] {
+            bytes.push(b'S');
+            int(&mut bytes, kind);
+            int(&mut bytes, 1);
+            int(&mut bytes, i32::try_from(name.len()).unwrap());
+            bytes.extend(name.as_bytes());
+            int(&mut bytes, 0);
+            if kind & 4 == 0 {
+                int(&mut bytes, 2);
+            } else {
+                bytes.extend(0.5_f64.to_le_bytes());
+            }
+        }
+        let mut problem = NlProblem::parse(&bytes).unwrap();
+        let mut f = 0.0;
+
+        assert!(problem.eval_f(&[1.0], &mut f));
+
+        assert_eq!(vec![-9.0], vec![f]);
+    }
+
+    #[test]
+    fn nl_problem_parses_inverse_trigonometric_operators() {
+        type Function = fn(&[f64]) -> f64;
+        let x = [0.5, 0.25, 1.5, 1.0];
+        let cases: [(&str, Function); 8] = [
+            ("o47\nv0", |x| x[0].atanh()),
+            ("o48\nv1\nv0", |x| x[1].atan2(x[0])),
+            ("o48\nv1\no16\nv0", |x| x[1].atan2(-x[0])),
+            ("o49\nv0", |x| x[0].atan()),
+            ("o50\nv0", |x| x[0].asinh()),
+            ("o51\nv0", |x| x[0].asin()),
+            ("o52\nv2", |x| x[2].acosh()),
+            ("o53\nv0", |x| x[0].acos()),
+        ];
+
+        for (expression, expected) in cases {
+            let nl = HS071.replacen(
+                "C0\t#c0\no2\t#*\nv0\no2\nv1\no2\nv2\nv3",
+                &format!("C0\n{expression}"),
+                1,
+            );
+            let mut problem = NlProblem::parse(nl.as_bytes()).unwrap();
+            let size = problem.get_nlp_info();
+            let nnz = usize::try_from(size.nnz_jac).unwrap();
+            let (mut g, mut values) = (vec![0.0; 2], vec![0.0; nnz]);
+            let (mut i_row, mut j_col) = (vec![0; nnz], vec![0; nnz]);
+
+            assert!(problem.eval_g(&x, &mut g));
+            assert!(problem.eval_jac_g(&x, size.m, &mut values));
+            problem.get_jacobian_sparsity(size.n, size.m, &mut i_row, &mut j_col);
+
+            assert!((g[0] - expected(&x)).abs() < 1e-12, "{expression}");
+            for j in 0..4 {
+                let derivative: f64 = (0..nnz)
+                    .filter(|&k| i_row[k] == 0 && j_col[k] == i32::try_from(j).unwrap())
+                    .map(|k| values[k])
+                    .sum();
+                let (mut plus, mut minus) = (x, x);
+                plus[j] += 1e-6;
+                minus[j] -= 1e-6;
+                let difference = (expected(&plus) - expected(&minus)) / 2e-6;
+                assert!((derivative - difference).abs() < 1e-6, "{expression}");
+            }
+        }
+    }
+
+    #[test]
+    fn nl_problem_rejects_unsupported_operators() {
+        let nl = HS071.replacen("C0\t#c0\no2\t#*", "C0\no15\no2", 1);
+
+        let error = NlProblem::parse(nl.as_bytes()).unwrap_err();
+
+        assert_eq!("Unsupported .nl operator o15.", error.to_string());
+    }
+
+    #[test]
+    fn nl_problem_writes_failure_sol_without_solution() {
+        let problem = NlProblem::parse(HS071.as_bytes()).unwrap();
+        let mut sol = Vec::new();
+
+        problem
+            .write_failure_sol(&mut sol, "Invalid option.\n\nUnknown option `tool`.")
+            .unwrap();
+
+        assert_eq!(
+            "ipopt_bindgen: Invalid option.\nUnknown option `tool`.\n\nOptions\n3\n1\n1\n0\n\
+             2\n0\n4\n0\nobjno 0 520\n",
+            String::from_utf8(sol).unwrap()
+        );
+    }
+
+    #[test]
+    fn nl_problem_writes_parse_failure_sol_from_header() {
+        let unsupported = HS071.replacen(" 4 2 1 0 1\t", " 4 2 1 0 1 1\t", 1);
+        let error = NlProblem::parse(unsupported.as_bytes()).unwrap_err();
+        let (mut sol, mut empty_sol) = (Vec::new(), Vec::new());
+
+        NlProblem::write_parse_failure_sol(&mut sol, unsupported.as_bytes(), &error.to_string())
+            .unwrap();
+        NlProblem::write_parse_failure_sol(&mut empty_sol, b"", "No such file.").unwrap();
+
+        assert_eq!(
+            "ipopt_bindgen: Logical constraints are not supported.\n\nOptions\n3\n1\n1\n0\n\
+             2\n0\n4\n0\nobjno 0 520\n",
+            String::from_utf8(sol).unwrap()
+        );
+        assert_eq!(
+            "ipopt_bindgen: No such file.\n\n0\n0\n0\n0\nobjno 0 520\n",
+            String::from_utf8(empty_sol).unwrap()
+        );
+    }
+
+    #[test]
+    fn nl_problem_writes_sol_with_ampl_duals() {
+        let problem = NlProblem::parse(HS071.as_bytes()).unwrap();
+        let result = OptimizationResult {
+            solution: Solution {
+                x: vec![1.0, 4.75, 3.75, 1.5],
+                lambda: vec![-0.5, 0.25],
+                ..Default::default()
+            },
+            status: ApplicationReturnStatus_Solve_Succeeded,
+            ..Default::default()
+        };
+        let mut sol = Vec::new();
+
+        problem.write_sol(&mut sol, &result).unwrap();
+
+        assert_eq!(
+            "ipopt_bindgen: Solve_Succeeded\n\nOptions\n3\n1\n1\n0\n2\n2\n4\n4\n0.5\n-0.25\n\
+             1\n4.75\n3.75\n1.5\nobjno 0 0\n",
+            String::from_utf8(sol).unwrap()
+        );
+    }
+}
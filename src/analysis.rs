@@ -0,0 +1,488 @@
+//! # Ipopt Bindgen Analysis
+//!
+//! Defines a post-solve analysis of a `Solution`, which computes the residuals of the KKT
+//! optimality conditions directly from the problem. This allows solutions to be audited
+//! independently of the termination status reported by Ipopt.
+//!
+//! The multipliers follow the Ipopt sign convention, where the gradient of the Lagrangian is
+//! `grad_f + J^T lambda - z_l + z_u`, with `z_l, z_u >= 0`.
+
+use crate::{
+    bounds::{Bound, BoundInfinities},
+    results::Solution,
+    tnlp::{ProblemBounds, ProblemNames, Tnlp},
+};
+use std::error::Error;
+
+/// Summary norms of a residual vector.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ResidualNorms {
+    /// The maximum absolute value (infinity norm).
+    pub max: f64,
+
+    /// The Euclidean norm.
+    pub two: f64,
+}
+
+impl ResidualNorms {
+    /// Computes the norms of the residuals.
+    fn of<'a>(residuals: impl IntoIterator<Item = &'a f64>) -> Self {
+        let (max, sum_of_squares) = residuals.into_iter().fold((0.0_f64, 0.0), |(max, sum), r| {
+            (max.max(r.abs()), sum + r * r)
+        });
+        ResidualNorms {
+            max,
+            two: sum_of_squares.sqrt(),
+        }
+    }
+}
+
+/// Summary norms of each of the KKT residuals.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct KktSummary {
+    /// The violation of the constraint bounds.
+    pub primal_infeasibility: ResidualNorms,
+
+    /// The violation of the variable bounds.
+    pub bound_violation: ResidualNorms,
+
+    /// The gradient of the Lagrangian.
+    pub dual_infeasibility: ResidualNorms,
+
+    /// The complementarity of the variable bounds and constraints combined.
+    pub complementarity: ResidualNorms,
+}
+
+/// The residuals of the KKT optimality conditions at a solution.
+///
+/// Every residual is non-negative, except for the dual infeasibility which is the signed gradient
+/// of the Lagrangian.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SolutionAnalysis {
+    /// The amount by which each constraint function violates its bounds.
+    pub constraint_violation: Vec<f64>,
+
+    /// The amount by which each variable is below its lower bound.
+    pub lower_bound_violation: Vec<f64>,
+
+    /// The amount by which each variable is above its upper bound.
+    pub upper_bound_violation: Vec<f64>,
+
+    /// The gradient of the Lagrangian with respect to each variable.
+    pub dual_infeasibility: Vec<f64>,
+
+    /// The complementarity of each variable lower bound, `|z_l * (x - x_l)|`.
+    pub lower_bound_complementarity: Vec<f64>,
+
+    /// The complementarity of each variable upper bound, `|z_u * (x_u - x)|`.
+    pub upper_bound_complementarity: Vec<f64>,
+
+    /// The complementarity of each constraint with the bound that its multiplier corresponds to.
+    pub constraint_complementarity: Vec<f64>,
+
+    /// Summary norms of the residuals.
+    pub summary: KktSummary,
+
+    /// The names of the variables and constraints, as supplied by the problem.
+    pub names: ProblemNames,
+}
+
+impl SolutionAnalysis {
+    /// Analyzes a solution of a problem.
+    ///
+    /// The constraints, objective gradient and constraint Jacobian are evaluated by the problem at
+    /// the solution, rather than taken from the solution itself. Bounds are classified as infinite
+    /// with the default `BoundInfinities`.
+    ///
+    /// # Parameters
+    ///
+    /// - `problem` - The problem that was optimized.
+    /// - `bounds` - The bounds of the problem.
+    /// - `solution` - The solution to analyze.
+    ///
+    /// # Errors
+    ///
+    /// - The problem dimensions are not representable as a usize.
+    /// - The dimensions of the bounds or solution do not match the problem.
+    /// - The problem returns a different number of names than it has variables or constraints.
+    /// - The problem fails to evaluate at the solution.
+    /// - The Jacobian sparsity pattern contains an index that is out of range.
+    pub fn new<P: Tnlp + ?Sized>(
+        problem: &mut P,
+        bounds: &ProblemBounds,
+        solution: &Solution,
+    ) -> Result<Self, Box<dyn Error>> {
+        SolutionAnalysis::with_bound_infinities(
+            problem,
+            bounds,
+            solution,
+            BoundInfinities::default(),
+        )
+    }
+
+    /// Analyzes a solution of a problem, classifying bounds as infinite with the given values.
+    ///
+    /// Use this when the `nlp_lower_bound_inf` or `nlp_upper_bound_inf` options have been
+    /// overridden, passing `Application::bound_infinities`.
+    ///
+    /// # Parameters
+    ///
+    /// - `problem` - The problem that was optimized.
+    /// - `bounds` - The bounds of the problem.
+    /// - `solution` - The solution to analyze.
+    /// - `infinities` - The values at or beyond which bounds are infinite.
+    ///
+    /// # Errors
+    ///
+    /// See `SolutionAnalysis::new`.
+    pub fn with_bound_infinities<P: Tnlp + ?Sized>(
+        problem: &mut P,
+        bounds: &ProblemBounds,
+        solution: &Solution,
+        infinities: BoundInfinities,
+    ) -> Result<Self, Box<dyn Error>> {
+        let problem_size = problem.get_nlp_info();
+        let n = usize::try_from(problem_size.n)?;
+        let m = usize::try_from(problem_size.m)?;
+        let nnz_jac = usize::try_from(problem_size.nnz_jac)?;
+        check_dimensions(n, m, bounds, solution)?;
+        let names = ProblemNames::from_problem(problem)?;
+
+        let x = &solution.x;
+        let mut g = vec![0.0; m];
+        let mut grad_lagrangian = vec![0.0; n];
+        let mut i_row = vec![0; nnz_jac];
+        let mut j_col = vec![0; nnz_jac];
+        let mut jacobian = vec![0.0; nnz_jac];
+
+        problem.get_jacobian_sparsity(problem_size.n, problem_size.m, &mut i_row, &mut j_col);
+        if !(problem.eval_g(x, &mut g)
+            && problem.eval_grad_f(x, &mut grad_lagrangian)
+            && problem.eval_jac_g(x, problem_size.m, &mut jacobian))
+        {
+            return Err("The problem could not be evaluated at the solution.".into());
+        }
+
+        for ((&row, &col), &value) in i_row.iter().zip(&j_col).zip(&jacobian) {
+            let (row, col) = (usize::try_from(row)?, usize::try_from(col)?);
+            if row >= m || col >= n {
+                return Err(format!("Jacobian entry ({row}, {col}) is out of range.").into());
+            }
+            grad_lagrangian[col] += value * solution.lambda[row];
+        }
+        for (j, grad) in grad_lagrangian.iter_mut().enumerate() {
+            *grad += solution.z_u[j] - solution.z_l[j];
+        }
+
+        let constraint_violation = (0..m)
+            .map(|i| (bounds.g_l[i] - g[i]).max(g[i] - bounds.g_u[i]).max(0.0))
+            .collect();
+        let lower_bound_violation = (0..n).map(|j| (bounds.x_l[j] - x[j]).max(0.0)).collect();
+        let upper_bound_violation = (0..n).map(|j| (x[j] - bounds.x_u[j]).max(0.0)).collect();
+        let lower_bound_complementarity = (0..n)
+            .map(|j| {
+                let bound = infinities.lower_bound(bounds.x_l[j]);
+                complementarity(solution.z_l[j], x[j] - bounds.x_l[j], bound)
+            })
+            .collect();
+        let upper_bound_complementarity = (0..n)
+            .map(|j| {
+                let bound = infinities.upper_bound(bounds.x_u[j]);
+                complementarity(solution.z_u[j], bounds.x_u[j] - x[j], bound)
+            })
+            .collect();
+        let constraint_complementarity = (0..m)
+            .map(|i| {
+                #[allow(clippy::float_cmp)]
+                if bounds.g_l[i] == bounds.g_u[i] {
+                    return 0.0;
+                }
+                let lambda = solution.lambda[i];
+                let (lower, upper) = (
+                    infinities.lower_bound(bounds.g_l[i]),
+                    infinities.upper_bound(bounds.g_u[i]),
+                );
+                complementarity(lambda.max(0.0), bounds.g_u[i] - g[i], upper)
+                    + complementarity((-lambda).max(0.0), g[i] - bounds.g_l[i], lower)
+            })
+            .collect();
+
+        let mut analysis = SolutionAnalysis {
+            constraint_violation,
+            lower_bound_violation,
+            upper_bound_violation,
+            dual_infeasibility: grad_lagrangian,
+            lower_bound_complementarity,
+            upper_bound_complementarity,
+            constraint_complementarity,
+            summary: KktSummary::default(),
+            names,
+        };
+        analysis.summary = KktSummary {
+            primal_infeasibility: ResidualNorms::of(&analysis.constraint_violation),
+            bound_violation: ResidualNorms::of(
+                analysis
+                    .lower_bound_violation
+                    .iter()
+                    .chain(&analysis.upper_bound_violation),
+            ),
+            dual_infeasibility: ResidualNorms::of(&analysis.dual_infeasibility),
+            complementarity: ResidualNorms::of(
+                analysis
+                    .lower_bound_complementarity
+                    .iter()
+                    .chain(&analysis.upper_bound_complementarity)
+                    .chain(&analysis.constraint_complementarity),
+            ),
+        };
+
+        Ok(analysis)
+    }
+
+    /// Gets the constraints with the largest violations, along with their names.
+    ///
+    /// # Parameters
+    ///
+    /// - `count` - The maximum number of constraints to return.
+    ///
+    /// # Returns
+    ///
+    /// The names and violations of up to `count` violated constraints, largest first.
+    #[must_use]
+    pub fn largest_constraint_violations(&self, count: usize) -> Vec<(String, f64)> {
+        let mut violated: Vec<_> = self
+            .constraint_violation
+            .iter()
+            .enumerate()
+            .filter(|&(_, &violation)| violation > 0.0)
+            .collect();
+        violated.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        violated
+            .into_iter()
+            .take(count)
+            .map(|(i, &violation)| (self.names.constraint(i), violation))
+            .collect()
+    }
+
+    /// Gets the largest residual over all of the KKT conditions.
+    #[must_use]
+    pub fn max_error(&self) -> f64 {
+        let summary = &self.summary;
+        summary
+            .primal_infeasibility
+            .max
+            .max(summary.bound_violation.max)
+            .max(summary.dual_infeasibility.max)
+            .max(summary.complementarity.max)
+    }
+}
+
+impl Solution {
+    /// Analyzes the KKT residuals of this solution.
+    ///
+    /// See `SolutionAnalysis::new`. To reuse a problem after optimizing it, pass `&mut problem`
+    /// to `Application::optimize_tnlp`.
+    ///
+    /// # Parameters
+    ///
+    /// - `problem` - The problem that was optimized.
+    /// - `bounds` - The bounds of the problem.
+    ///
+    /// # Errors
+    ///
+    /// See `SolutionAnalysis::new`.
+    pub fn analyze<P: Tnlp + ?Sized>(
+        &self,
+        problem: &mut P,
+        bounds: &ProblemBounds,
+    ) -> Result<SolutionAnalysis, Box<dyn Error>> {
+        SolutionAnalysis::new(problem, bounds, self)
+    }
+}
+
+/// Computes the complementarity of a multiplier with a slack, ignoring infinite bounds.
+fn complementarity(multiplier: f64, slack: f64, bound: Bound) -> f64 {
+    if bound.is_infinite() {
+        0.0
+    } else {
+        (multiplier * slack).abs()
+    }
+}
+
+/// Checks that the dimensions of the bounds and solution match the problem.
+pub(crate) fn check_dimensions(
+    n: usize,
+    m: usize,
+    bounds: &ProblemBounds,
+    solution: &Solution,
+) -> Result<(), String> {
+    let lengths = [
+        ("x_l", bounds.x_l.len(), n),
+        ("x_u", bounds.x_u.len(), n),
+        ("g_l", bounds.g_l.len(), m),
+        ("g_u", bounds.g_u.len(), m),
+        ("x", solution.x.len(), n),
+        ("z_l", solution.z_l.len(), n),
+        ("z_u", solution.z_u.len(), n),
+        ("lambda", solution.lambda.len(), m),
+    ];
+    for (name, len, expected) in lengths {
+        if len != expected {
+            return Err(format!(
+                "`{name}` has length {len}, but the problem expects {expected}."
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::tnlp::{InitialSolution, ProblemSize};
+
+    /// min x0^2 + x1^2 s.t. x0 + x1 >= 1, x >= 0. The solution is x = (0.5, 0.5), lambda = -1.
+    pub(crate) struct SumConstrained;
+
+    impl Tnlp for SumConstrained {
+        fn get_nlp_info(&self) -> ProblemSize {
+            ProblemSize {
+                n: 2,
+                m: 1,
+                nnz_jac: 2,
+                nnz_hess: 2,
+            }
+        }
+
+        fn get_bounds_info(
+            &self,
+            x_l: &mut [f64],
+            x_u: &mut [f64],
+            g_l: &mut [f64],
+            g_u: &mut [f64],
+        ) {
+            x_l.fill(0.0);
+            x_u.fill(2e19);
+            g_l[0] = 1.0;
+            g_u[0] = 2e19;
+        }
+
+        fn get_starting_point(&self) -> InitialSolution {
+            InitialSolution::from_variables(vec![1.0, 1.0])
+        }
+
+        fn constraint_names(&self) -> Option<Vec<String>> {
+            Some(vec!["demand".to_string()])
+        }
+
+        fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+            *obj_value = x[0] * x[0] + x[1] * x[1];
+            true
+        }
+
+        fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+            grad_f[0] = 2.0 * x[0];
+            grad_f[1] = 2.0 * x[1];
+            true
+        }
+
+        fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+            g[0] = x[0] + x[1];
+            true
+        }
+
+        fn get_jacobian_sparsity(
+            &mut self,
+            _n: i32,
+            _m: i32,
+            i_row: &mut [i32],
+            j_col: &mut [i32],
+        ) {
+            i_row.copy_from_slice(&[0, 0]);
+            j_col.copy_from_slice(&[0, 1]);
+        }
+
+        fn eval_jac_g(&mut self, _x: &[f64], _m: i32, values: &mut [f64]) -> bool {
+            values.fill(1.0);
+            true
+        }
+
+        fn get_hessian_sparsity(&mut self, _n: i32, _m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+            i_row.copy_from_slice(&[0, 1]);
+            j_col.copy_from_slice(&[0, 1]);
+        }
+
+        fn eval_h(
+            &mut self,
+            _x: &[f64],
+            obj_factor: f64,
+            _lambda: &[f64],
+            _m: i32,
+            values: &mut [f64],
+        ) -> bool {
+            values.fill(2.0 * obj_factor);
+            true
+        }
+    }
+
+    pub(crate) fn optimal_solution() -> Solution {
+        Solution {
+            x: vec![0.5, 0.5],
+            constraints: vec![1.0],
+            lambda: vec![-1.0],
+            z_l: vec![0.0, 0.0],
+            z_u: vec![0.0, 0.0],
+            objective: 0.5,
+        }
+    }
+
+    #[test]
+    fn solution_analysis_new_has_zero_residuals_at_optimum() {
+        let bounds = ProblemBounds::from_problem(&SumConstrained).unwrap();
+
+        let analysis = optimal_solution()
+            .analyze(&mut SumConstrained, &bounds)
+            .unwrap();
+
+        assert_eq!(KktSummary::default(), analysis.summary);
+    }
+
+    #[test]
+    fn solution_analysis_new_reports_violations() {
+        let bounds = ProblemBounds::from_problem(&SumConstrained).unwrap();
+        let solution = Solution {
+            x: vec![-0.5, 0.5],
+            lambda: vec![-1.0],
+            z_l: vec![1.0, 0.0],
+            ..optimal_solution()
+        };
+
+        let analysis = SolutionAnalysis::new(&mut SumConstrained, &bounds, &solution).unwrap();
+
+        assert_eq!(vec![1.0], analysis.constraint_violation);
+        assert_eq!(vec![0.5, 0.0], analysis.lower_bound_violation);
+        assert_eq!(vec![-3.0, 0.0], analysis.dual_infeasibility);
+        assert_eq!(vec![0.5, 0.0], analysis.lower_bound_complementarity);
+        assert_eq!(vec![1.0], analysis.constraint_complementarity);
+        assert_eq!(
+            vec![("demand".to_string(), 1.0)],
+            analysis.largest_constraint_violations(5)
+        );
+        assert_eq!(
+            ResidualNorms { max: 3.0, two: 3.0 },
+            analysis.summary.dual_infeasibility
+        );
+    }
+
+    #[test]
+    fn solution_analysis_new_rejects_mismatched_dimensions() {
+        let bounds = ProblemBounds::from_problem(&SumConstrained).unwrap();
+        let solution = Solution {
+            x: vec![0.5],
+            ..optimal_solution()
+        };
+
+        assert!(SolutionAnalysis::new(&mut SumConstrained, &bounds, &solution).is_err());
+    }
+}
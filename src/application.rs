@@ -5,30 +5,115 @@
 
 #[allow(clippy::wildcard_imports)]
 use crate::{
+    bounds::{BOUND_INFINITY, BoundInfinities},
     c_interface::*,
-    results::OptimizationResult,
-    tnlp::{IntermediateData, Tnlp, UserScaling},
+    cancellation::CancellationToken,
+    observer::{Observer, Observers},
+    output::{OutputDestination, OutputFile},
+    results::{OptimizationResult, StopReason},
+    statistics::FinalStatistics,
+    tnlp::{
+        InitialSolution, IntermediateData, OptionValue, ProblemBounds, ProblemNames, Tnlp,
+        UserScaling,
+    },
+};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    ffi::CString,
+    os::raw::c_void,
+    ptr, slice,
+    sync::Arc,
+    time::{Duration, Instant},
 };
-use std::{collections::HashMap, error::Error, ffi::CString, os::raw::c_void, ptr, slice};
 
 /// The main application type for making calls to Ipopt.
+///
+/// # Thread Safety
+///
+/// An `Application` only holds configuration, so it is `Send + Sync` and a single instance can be
+/// shared by optimizations running on many threads. Each call to `optimize_tnlp` creates its own
+/// Ipopt problem and only touches the `Tnlp` on the calling thread, so problems themselves do not
+/// need to be `Send`. Note that whether independent Ipopt solves can safely run concurrently also
+/// depends on the linear solver that Ipopt was built with.
+///
+/// # Serialization
+///
+/// With the `serde` feature, the options of an `Application` can be serialized. The cancellation
+/// token, deadline and observers are runtime state, so they are skipped and left unset when
+/// deserializing.
 #[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", allow(clippy::unsafe_derive_deserialize))]
 pub struct Application {
-    int_options: HashMap<String, i32>,
-    numeric_options: HashMap<String, f64>,
-    string_options: HashMap<String, String>,
+    int_options: BTreeMap<String, i32>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::map"))]
+    numeric_options: BTreeMap<String, f64>,
+    string_options: BTreeMap<String, String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cancellation_token: Option<CancellationToken>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    deadline: Option<Instant>,
+    timeout: Option<Duration>,
+    iteration_budget: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    observers: Observers,
+    output_destination: OutputDestination,
+    print_level: Option<i32>,
+}
+
+/// The conditions under which the Rust layer stops an optimization, checked at every iteration.
+#[derive(Debug, Default)]
+struct EarlyStopping<'a> {
+    pub cancellation_token: Option<&'a CancellationToken>,
+    pub deadline: Option<Instant>,
+    pub iteration_budget: Option<u32>,
+}
+
+impl EarlyStopping<'_> {
+    /// Gets the reason to stop at the given iteration, if there is one.
+    pub fn check(&self, iter_count: u32) -> Option<StopReason> {
+        if self
+            .cancellation_token
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            Some(StopReason::Cancelled)
+        } else if self.deadline.is_some_and(|d| Instant::now() >= d) {
+            Some(StopReason::DeadlineExceeded)
+        } else if self.iteration_budget.is_some_and(|b| iter_count >= b) {
+            Some(StopReason::IterationBudgetExhausted)
+        } else {
+            None
+        }
+    }
 }
 
 /// Helper type for passing Ipopt Bingen problem and results structures to Ipopt callbacks.
+///
+/// A pointer to this type is passed to Ipopt as the user data pointer. It lives on the stack of
+/// `optimize_tnlp_from` for the whole of `IpoptSolve`, and Ipopt only invokes the callbacks on the
+/// thread that called `IpoptSolve`, so the pointer is never shared between threads.
 #[derive(Debug)]
 struct IpoptBindgenUserData<'a, P: Tnlp> {
     pub problem: &'a mut P,
     pub results: &'a mut OptimizationResult,
+    pub early_stopping: EarlyStopping<'a>,
+    pub observers: &'a Observers,
 }
 
 impl<'a, P: Tnlp> IpoptBindgenUserData<'a, P> {
-    pub fn new(problem: &'a mut P, results: &'a mut OptimizationResult) -> Self {
-        Self { problem, results }
+    pub fn new(
+        problem: &'a mut P,
+        results: &'a mut OptimizationResult,
+        early_stopping: EarlyStopping<'a>,
+        observers: &'a Observers,
+    ) -> Self {
+        Self {
+            problem,
+            results,
+            early_stopping,
+            observers,
+        }
     }
 
     pub fn reify_from_void_ptr(value: *mut c_void) -> &'a mut Self {
@@ -37,6 +122,24 @@ impl<'a, P: Tnlp> IpoptBindgenUserData<'a, P> {
     }
 }
 
+/// Owns an Ipopt problem, and frees it when dropped, so that it is also freed on early returns.
+#[derive(Debug)]
+struct IpoptProblemHandle(IpoptProblem);
+
+impl Drop for IpoptProblemHandle {
+    fn drop(&mut self) {
+        unsafe {
+            FreeIpoptProblem(self.0);
+        }
+    }
+}
+
+// `MultiStart` shares an `Application` between worker threads.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Application>();
+};
+
 impl Application {
     /// Creates a new instance of an `Application`.
     ///
@@ -129,6 +232,217 @@ impl Application {
         self
     }
 
+    /// Sets a token that can be used to stop optimizations from another thread.
+    ///
+    /// The token is checked at every iteration. Once it has been cancelled, the optimization
+    /// terminates with the `User_Requested_Stop` status and the most recent iterate, without
+    /// requiring any changes to the problem's `Tnlp::intermediate` implementation.
+    ///
+    /// # Parameters
+    ///
+    /// - `token` - The cancellation token. Keep a clone to be able to cancel the optimization.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ipopt_bindgen::{Application, CancellationToken};
+    ///
+    /// let token = CancellationToken::new();
+    /// let mut application = Application::new();
+    ///
+    /// application.set_cancellation_token(token.clone());
+    ///
+    /// // Later, possibly from another thread.
+    /// token.cancel();
+    /// ```
+    pub fn set_cancellation_token(&mut self, token: CancellationToken) -> &mut Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Gets the token used to stop optimizations, if one has been set.
+    #[must_use]
+    pub fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation_token.as_ref()
+    }
+
+    /// Sets a wall-clock deadline for optimizations.
+    ///
+    /// Unlike the Ipopt `max_cpu_time` option, this is measured in wall-clock time. The deadline is
+    /// checked at every iteration. Once it has passed, the optimization terminates with the
+    /// `User_Requested_Stop` status and the most recent iterate, and the result's `stop_reason` is
+    /// `StopReason::DeadlineExceeded`.
+    ///
+    /// If a timeout is also set, whichever expires first is used.
+    ///
+    /// # Parameters
+    ///
+    /// - `deadline` - The instant after which optimizations are stopped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ipopt_bindgen::Application;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let mut application = Application::new();
+    ///
+    /// application.with_deadline(Instant::now() + Duration::from_secs(5));
+    /// ```
+    pub fn with_deadline(&mut self, deadline: Instant) -> &mut Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets a wall-clock time limit for each optimization, measured from the start of the solve.
+    ///
+    /// This behaves like `with_deadline`, except that the deadline is recomputed for every call to
+    /// `optimize_tnlp`, so the same `Application` can be reused for many solves.
+    ///
+    /// # Parameters
+    ///
+    /// - `timeout` - The maximum wall-clock duration of a solve.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ipopt_bindgen::Application;
+    /// use std::time::Duration;
+    ///
+    /// let mut application = Application::new();
+    ///
+    /// application.with_timeout(Duration::from_millis(250));
+    /// ```
+    pub fn with_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum number of iterations for each optimization, enforced by the Rust layer.
+    ///
+    /// In contrast to the Ipopt `max_iter` option, exhausting the budget terminates the
+    /// optimization with the `User_Requested_Stop` status, and the result's `stop_reason` is
+    /// `StopReason::IterationBudgetExhausted`.
+    ///
+    /// # Parameters
+    ///
+    /// - `iterations` - The number of iterations after which optimizations are stopped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ipopt_bindgen::Application;
+    ///
+    /// let mut application = Application::new();
+    ///
+    /// application.with_iteration_budget(100);
+    /// ```
+    pub fn with_iteration_budget(&mut self, iterations: u32) -> &mut Self {
+        self.iteration_budget = Some(iterations);
+        self
+    }
+
+    /// Adds an observer that is notified of the progress of optimizations.
+    ///
+    /// Observers are notified at every iteration, before the problem's own `Tnlp::intermediate`
+    /// callback, so monitoring and early stopping rules can be composed without changing the
+    /// problem. If any observer returns `false`, the optimization terminates with the
+    /// `User_Requested_Stop` status and the result's `stop_reason` is `StopReason::Observer`.
+    ///
+    /// # Parameters
+    ///
+    /// - `observer` - The observer to add.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ipopt_bindgen::{Application, IntermediateData};
+    /// use std::sync::mpsc;
+    ///
+    /// let (sender, receiver) = mpsc::channel();
+    /// let mut application = Application::new();
+    ///
+    /// application
+    ///     .add_observer(sender)
+    ///     .add_observer(|data: &IntermediateData| data.inf_pr < 1e10);
+    /// ```
+    pub fn add_observer(&mut self, observer: impl Observer + 'static) -> &mut Self {
+        self.observers.push(Arc::new(observer));
+        self
+    }
+
+    /// Sets where the console output of Ipopt is sent, and how verbose it is.
+    ///
+    /// When the output is captured, Ipopt writes it to a temporary file (via
+    /// `OpenIpoptOutputFile`) instead of `stdout`, and the text is returned in
+    /// `OptimizationResult::output` once the optimization has finished, along with the parsed
+    /// `OptimizationResult::final_statistics`. The Ipopt banner is also suppressed, unless the `sb`
+    /// option has been set explicitly.
+    ///
+    /// # Parameters
+    ///
+    /// - `destination` - Where the output is sent.
+    /// - `print_level` - The Ipopt print level, from 0 (no output) to 12 (most verbose). This
+    ///   overrides the `print_level` option.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ipopt_bindgen::{Application, OutputDestination};
+    ///
+    /// let mut application = Application::new();
+    ///
+    /// application.set_output(OutputDestination::Capture, 5);
+    /// ```
+    pub fn set_output(&mut self, destination: OutputDestination, print_level: i32) -> &mut Self {
+        self.output_destination = destination;
+        self.print_level = Some(print_level);
+        self
+    }
+
+    /// Gets the deadline for an optimization starting at the given instant.
+    fn effective_deadline(&self, start: Instant) -> Option<Instant> {
+        let timeout_deadline = self.timeout.and_then(|t| start.checked_add(t));
+        match (self.deadline, timeout_deadline) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Gets the Ipopt options set on the application, with `set_integer_option`,
+    /// `set_numeric_option` and `set_string_option`.
+    ///
+    /// These take precedence over the `Tnlp::default_options` of the problem.
+    #[must_use]
+    pub fn options(&self) -> Vec<(String, OptionValue)> {
+        let integers =
+            (self.int_options.iter()).map(|(key, &value)| (key, OptionValue::Integer(value)));
+        let numerics =
+            (self.numeric_options.iter()).map(|(key, &value)| (key, OptionValue::Numeric(value)));
+        let strings = (self.string_options.iter())
+            .map(|(key, value)| (key, OptionValue::String(value.clone())));
+        integers
+            .chain(numerics)
+            .chain(strings)
+            .map(|(key, value)| (key.clone(), value))
+            .collect()
+    }
+
+    /// Gets the values that Ipopt treats as infinite lower and upper bounds, i.e. the values of the
+    /// `nlp_lower_bound_inf` and `nlp_upper_bound_inf` options.
+    ///
+    /// Pass these to the active set, analysis and report of a solution, so that they classify
+    /// bounds the same way as Ipopt.
+    #[must_use]
+    pub fn bound_infinities(&self) -> BoundInfinities {
+        let option =
+            |key: &str, default: f64| self.numeric_options.get(key).copied().unwrap_or(default);
+        BoundInfinities {
+            lower: option("nlp_lower_bound_inf", -BOUND_INFINITY),
+            upper: option("nlp_upper_bound_inf", BOUND_INFINITY),
+        }
+    }
+
     extern "C" fn objective_callback<P: Tnlp>(
         n: ipindex,
         x: *mut ipnumber,
@@ -317,7 +631,15 @@ impl Application {
         let user_data: &mut IpoptBindgenUserData<'_, P> =
             IpoptBindgenUserData::reify_from_void_ptr(user_data_ptr);
 
-        user_data.problem.intermediate(IntermediateData {
+        let iterations = u32::try_from(iter_count).unwrap_or_default();
+        user_data.results.performance.number_of_iterations = iterations;
+
+        if let Some(reason) = user_data.early_stopping.check(iterations) {
+            user_data.results.stop_reason = Some(reason);
+            return false;
+        }
+
+        let data = IntermediateData {
             alg_mod,
             iter_count,
             obj_value,
@@ -329,7 +651,101 @@ impl Application {
             alpha_du,
             alpha_pr,
             ls_trials,
-        })
+        };
+
+        let observers_continue = user_data.observers.notify(&data);
+        let problem_continues = user_data.problem.intermediate(data);
+        if !observers_continue {
+            user_data.results.stop_reason = Some(StopReason::Observer);
+        } else if !problem_continues {
+            user_data.results.stop_reason = Some(StopReason::Problem);
+        }
+
+        observers_continue && problem_continues
+    }
+
+    /// Adds the default options of the problem and then the options of the application to the C
+    /// problem, so that the application options take precedence.
+    fn add_options(
+        &self,
+        ipopt_problem: IpoptProblem,
+        problem_options: Vec<(String, OptionValue)>,
+    ) -> Result<(), Box<dyn Error>> {
+        for (option, value) in problem_options {
+            let name = CString::new(option.as_bytes())?;
+            match value {
+                OptionValue::Integer(value) => unsafe {
+                    AddIpoptIntOption(ipopt_problem, name.into_raw(), value);
+                },
+                OptionValue::Numeric(value) => unsafe {
+                    AddIpoptNumOption(ipopt_problem, name.into_raw(), value);
+                },
+                OptionValue::String(value) => {
+                    let string = CString::new(value.as_bytes())?;
+                    unsafe {
+                        AddIpoptStrOption(ipopt_problem, name.into_raw(), string.into_raw());
+                    }
+                }
+            }
+        }
+
+        // Todo - see if we can avoid copying the strings.
+        for (option, value) in &self.int_options {
+            let name = CString::new(option.as_bytes())?;
+            unsafe {
+                AddIpoptIntOption(ipopt_problem, name.into_raw(), *value);
+            }
+        }
+        for (option, value) in &self.string_options {
+            let name = CString::new(option.as_bytes())?;
+            let string = CString::new(value.as_bytes())?;
+            unsafe {
+                AddIpoptStrOption(ipopt_problem, name.into_raw(), string.into_raw());
+            }
+        }
+        for (option, value) in &self.numeric_options {
+            let option_name = CString::new(option.as_bytes())?;
+            unsafe {
+                AddIpoptNumOption(ipopt_problem, option_name.into_raw(), *value);
+            }
+        }
+
+        // When the output is captured, the print level applies to the output file instead, and
+        // nothing should be printed to the console.
+        let console_print_level = if self.output_destination.is_captured() {
+            Some(0)
+        } else {
+            self.print_level
+        };
+        if let Some(print_level) = console_print_level {
+            let name = CString::new("print_level")?;
+            unsafe {
+                AddIpoptIntOption(ipopt_problem, name.into_raw(), print_level);
+            }
+        }
+        if self.output_destination.is_captured() && !self.string_options.contains_key("sb") {
+            let name = CString::new("sb")?;
+            let string = CString::new("yes")?;
+            unsafe {
+                AddIpoptStrOption(ipopt_problem, name.into_raw(), string.into_raw());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the user scaling on the C problem, if it is not the default.
+    fn set_scaling(ipopt_problem: IpoptProblem, mut scaling: UserScaling) {
+        if scaling != UserScaling::default() {
+            unsafe {
+                SetIpoptProblemScaling(
+                    ipopt_problem,
+                    scaling.objective.unwrap_or(1.0),
+                    scaling.x.as_mut().map_or(ptr::null_mut(), Vec::as_mut_ptr),
+                    scaling.g.as_mut().map_or(ptr::null_mut(), Vec::as_mut_ptr),
+                );
+            }
+        }
     }
 
     /// Optimizes the problem.
@@ -341,47 +757,86 @@ impl Application {
     /// # Errors
     ///
     /// - The problem dimensions are not representable as a usize.
+    /// - The problem returns a different number of names than it has variables or constraints.
+    /// - A lower bound is greater than its upper bound.
+    /// - A C compatible string cannot be created for an option.
+    /// - The output destination requires a crate feature that is not enabled.
+    /// - The temporary file for capturing the output cannot be created.
+    ///
+    /// # Returns
+    ///
+    /// A result containing the optimization results.
+    pub fn optimize_tnlp<P: Tnlp>(&self, problem: P) -> Result<OptimizationResult, Box<dyn Error>> {
+        let starting_point = problem.get_starting_point();
+        self.optimize_tnlp_from(problem, starting_point)
+    }
+
+    /// Optimizes the problem from the given starting point, instead of the point returned by
+    /// `Tnlp::get_starting_point`.
+    ///
+    /// # Parameters
+    ///
+    /// - `problem` - The problem to optimize.
+    /// - `starting_point` - The initial point for the optimization.
+    ///
+    /// # Errors
+    ///
+    /// - The problem dimensions are not representable as a usize.
+    /// - The starting point dimensions do not match the problem dimensions.
+    /// - The problem returns a different number of names than it has variables or constraints.
+    /// - A lower bound is greater than its upper bound.
     /// - A C compatible string cannot be created for an option.
+    /// - The output destination requires a crate feature that is not enabled.
+    /// - The temporary file for capturing the output cannot be created.
     ///
     /// # Returns
     ///
     /// A result containing the optimization results.
-    pub fn optimize_tnlp<P: Tnlp>(
+    pub fn optimize_tnlp_from<P: Tnlp>(
         &self,
         mut problem: P,
+        starting_point: InitialSolution,
     ) -> Result<OptimizationResult, Box<dyn Error>> {
         const C_STYLE_INDEXING: i32 = 0;
+        const DEFAULT_PRINT_LEVEL: i32 = 5;
+
+        let early_stopping = EarlyStopping {
+            cancellation_token: self.cancellation_token.as_ref(),
+            deadline: self.effective_deadline(Instant::now()),
+            iteration_budget: self.iteration_budget,
+        };
+
+        self.output_destination.validate()?;
 
         let problem_size = problem.get_nlp_info();
         let n = usize::try_from(problem_size.n)?;
         let m = usize::try_from(problem_size.m)?;
         usize::try_from(problem_size.nnz_jac)?;
         usize::try_from(problem_size.nnz_hess)?;
+        starting_point.validate(n, m)?;
 
-        let mut x_l = vec![0.0; n];
-        let mut x_u = vec![0.0; n];
-        let mut g_l = vec![0.0; m];
-        let mut g_u = vec![0.0; m];
-        problem.get_bounds_info(
-            x_l.as_mut_slice(),
-            x_u.as_mut_slice(),
-            g_l.as_mut_slice(),
-            g_u.as_mut_slice(),
-        );
+        let mut bounds = ProblemBounds::from_problem(&problem)?;
+        let names = ProblemNames::from_problem(&problem)?;
+        bounds.validate(&names)?;
+        let infinities = self.bound_infinities();
+        bounds.replace_infinities(infinities.lower, infinities.upper);
 
-        let initial_solution = problem.get_starting_point();
-        let mut results = OptimizationResult::default();
-        let mut user_data = IpoptBindgenUserData::new(&mut problem, &mut results);
+        let mut results = OptimizationResult {
+            names,
+            ..Default::default()
+        };
+        let mut user_data =
+            IpoptBindgenUserData::new(&mut problem, &mut results, early_stopping, &self.observers);
         let user_data_ptr = &raw mut user_data as UserDataPtr;
 
-        let ipopt_problem = unsafe {
+        let handle = IpoptProblemHandle(unsafe {
             CreateIpoptProblem(
                 problem_size.n,
-                x_l.as_mut_ptr(),
-                x_u.as_mut_ptr(),
+                bounds.x_l.as_mut_ptr(),
+                bounds.x_u.as_mut_ptr(),
                 problem_size.m,
-                g_l.as_mut_ptr(),
-                g_u.as_mut_ptr(),
+                bounds.g_l.as_mut_ptr(),
+                bounds.g_u.as_mut_ptr(),
                 problem_size.nnz_jac,
                 problem_size.nnz_hess,
                 C_STYLE_INDEXING,
@@ -391,50 +846,33 @@ impl Application {
                 Some(Self::jacobian_callback::<P>),
                 Some(Self::hessian_callback::<P>),
             )
-        };
+        });
+        let ipopt_problem = handle.0;
 
         unsafe {
             SetIntermediateCallback(ipopt_problem, Some(Self::intermediate_callback::<P>));
         }
 
-        // Add the options to the C problem. Todo - see if we can avoid copying the strings.
-        for (option, value) in &self.int_options {
-            let name = CString::new(option.as_bytes())?;
-            unsafe {
-                AddIpoptIntOption(ipopt_problem, name.into_raw(), *value);
-            }
-        }
-        for (option, value) in &self.string_options {
-            let name = CString::new(option.as_bytes())?;
-            let string = CString::new(value.as_bytes())?;
-            unsafe {
-                AddIpoptStrOption(ipopt_problem, name.into_raw(), string.into_raw());
-            }
-        }
-        for (option, value) in &self.numeric_options {
-            let option_name = CString::new(option.as_bytes())?;
-            unsafe {
-                AddIpoptNumOption(ipopt_problem, option_name.into_raw(), *value);
-            }
-        }
+        self.add_options(ipopt_problem, problem.default_options())?;
 
-        let scaling = problem.get_scaling();
-        if scaling != UserScaling::default() {
-            unsafe {
-                SetIpoptProblemScaling(
-                    ipopt_problem,
-                    scaling.objective.unwrap_or(1.0),
-                    scaling.x.map_or(ptr::null_mut(), |mut v| v.as_mut_ptr()),
-                    scaling.g.map_or(ptr::null_mut(), |mut v| v.as_mut_ptr()),
-                );
-            }
-        }
+        let output_file = if self.output_destination.is_captured() {
+            let file = OutputFile::new()?;
+            file.open(
+                ipopt_problem,
+                self.print_level.unwrap_or(DEFAULT_PRINT_LEVEL),
+            )?;
+            Some(file)
+        } else {
+            None
+        };
 
-        let mut variables = initial_solution.x;
-        let mut z_l = initial_solution.z_l.unwrap_or(vec![1.0; n]);
-        let mut z_u = initial_solution.z_u.unwrap_or(vec![1.0; n]);
+        Self::set_scaling(ipopt_problem, problem.get_scaling());
+
+        let mut variables = starting_point.x;
+        let mut z_l = starting_point.z_l.unwrap_or(vec![1.0; n]);
+        let mut z_u = starting_point.z_u.unwrap_or(vec![1.0; n]);
         let mut g = vec![0.0; m];
-        let mut lambda = initial_solution.lambda.unwrap_or(vec![1.0; m]);
+        let mut lambda = starting_point.lambda.unwrap_or(vec![1.0; m]);
 
         results.status = unsafe {
             IpoptSolve(
@@ -455,10 +893,101 @@ impl Application {
         results.solution.z_l = z_l;
         results.solution.z_u = z_u;
 
-        unsafe {
-            FreeIpoptProblem(ipopt_problem);
+        // Ipopt closes the output file when the problem is freed.
+        drop(handle);
+
+        if let Some(file) = output_file {
+            let output = file.read()?;
+            self.output_destination.forward(&output);
+            results.final_statistics = FinalStatistics::parse(&output);
+            results.output = Some(output);
         }
 
         Ok(results)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn effective_deadline_uses_earliest_of_deadline_and_timeout() {
+        let start = Instant::now();
+        let mut application = Application::new();
+        application
+            .with_deadline(start + Duration::from_secs(10))
+            .with_timeout(Duration::from_secs(1));
+
+        let deadline = application.effective_deadline(start);
+
+        assert_eq!(Some(start + Duration::from_secs(1)), deadline);
+    }
+
+    #[test]
+    fn effective_deadline_is_none_without_limits() {
+        let application = Application::new();
+
+        assert_eq!(None, application.effective_deadline(Instant::now()));
+    }
+
+    #[test]
+    fn options_lists_every_option_type() {
+        let mut application = Application::new();
+        application
+            .set_string_option("mu_strategy", "adaptive")
+            .set_integer_option("max_iter", 50)
+            .set_numeric_option("tol", 1e-10);
+
+        assert_eq!(
+            vec![
+                ("max_iter".to_string(), OptionValue::Integer(50)),
+                ("tol".to_string(), OptionValue::Numeric(1e-10)),
+                (
+                    "mu_strategy".to_string(),
+                    OptionValue::String("adaptive".to_string())
+                ),
+            ],
+            application.options()
+        );
+    }
+
+    #[test]
+    fn bound_infinities_follow_options() {
+        let mut application = Application::new();
+
+        assert_eq!(BoundInfinities::default(), application.bound_infinities());
+
+        application.set_numeric_option("nlp_upper_bound_inf", 1e10);
+
+        assert_eq!(
+            BoundInfinities {
+                lower: -1e19,
+                upper: 1e10
+            },
+            application.bound_infinities()
+        );
+    }
+
+    #[test]
+    fn early_stopping_check_returns_expected_reasons() {
+        let token = CancellationToken::new();
+        let mut early_stopping = EarlyStopping {
+            cancellation_token: Some(&token),
+            deadline: None,
+            iteration_budget: Some(10),
+        };
+
+        assert_eq!(None, early_stopping.check(9));
+        assert_eq!(
+            Some(StopReason::IterationBudgetExhausted),
+            early_stopping.check(10)
+        );
+
+        early_stopping.deadline = Some(Instant::now());
+        assert_eq!(Some(StopReason::DeadlineExceeded), early_stopping.check(0));
+
+        token.cancel();
+        assert_eq!(Some(StopReason::Cancelled), early_stopping.check(0));
+    }
+}
@@ -0,0 +1,238 @@
+//! # Ipopt Bindgen Async Solve
+//!
+//! Defines a runtime-agnostic asynchronous wrapper around `Application::optimize_tnlp`, which runs
+//! Ipopt on a dedicated thread so that async code is not blocked for the duration of the solve.
+//!
+//! Only `std` primitives are used, so the returned future can be awaited on any executor.
+
+use crate::{
+    application::Application,
+    cancellation::CancellationToken,
+    results::OptimizationResult,
+    tnlp::{IntermediateData, Tnlp},
+};
+use std::{
+    error::Error,
+    future::Future,
+    pin::Pin,
+    sync::{
+        Arc, Mutex, PoisonError,
+        mpsc::{self, Receiver},
+    },
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+/// The output of a `SolveFuture`.
+pub type SolveOutput = Result<OptimizationResult, Box<dyn Error + Send + Sync>>;
+
+/// State shared between a `SolveFuture` and the thread running the optimization.
+#[derive(Debug, Default)]
+struct SharedState {
+    output: Option<SolveOutput>,
+    waker: Option<Waker>,
+}
+
+impl SharedState {
+    fn complete(shared: &Mutex<Self>, output: SolveOutput) {
+        let mut state = shared.lock().unwrap_or_else(PoisonError::into_inner);
+        state.output = Some(output);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Completes the future with an error if the optimization thread panics before producing a result.
+struct CompletionGuard {
+    shared: Arc<Mutex<SharedState>>,
+    completed: bool,
+}
+
+impl CompletionGuard {
+    fn complete(mut self, output: SolveOutput) {
+        SharedState::complete(&self.shared, output);
+        self.completed = true;
+    }
+}
+
+impl Drop for CompletionGuard {
+    fn drop(&mut self) {
+        if !self.completed {
+            SharedState::complete(
+                &self.shared,
+                Err("The optimization thread panicked.".into()),
+            );
+        }
+    }
+}
+
+/// A future that resolves to the result of an optimization running on a dedicated thread.
+///
+/// Dropping the future before it resolves requests that the optimization stops at the next
+/// iteration, after which the thread exits and the result is discarded.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled, and dropping this future cancels the optimization"]
+pub struct SolveFuture {
+    shared: Arc<Mutex<SharedState>>,
+    cancellation_token: CancellationToken,
+}
+
+impl SolveFuture {
+    /// Requests that the optimization stops at the next iteration.
+    ///
+    /// The future still resolves, with the `User_Requested_Stop` status and the most recent
+    /// iterate.
+    pub fn cancel(&self) {
+        self.cancellation_token.cancel();
+    }
+}
+
+impl Future for SolveFuture {
+    type Output = SolveOutput;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.shared.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(output) = state.output.take() {
+            Poll::Ready(output)
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for SolveFuture {
+    fn drop(&mut self) {
+        self.cancellation_token.cancel();
+    }
+}
+
+impl Application {
+    /// Optimizes the problem on a dedicated thread.
+    ///
+    /// The application is cloned, so changes made to it after this call do not affect the running
+    /// optimization. If the application has a cancellation token, cancelling it also cancels this
+    /// optimization.
+    ///
+    /// # Parameters
+    ///
+    /// - `problem` - The problem to optimize. This is moved to the optimization thread.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to the optimization results, and a channel on which the intermediate
+    /// data of every iteration is sent. The channel is closed when the optimization finishes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ipopt_bindgen::{Application, OptimizationResult, Tnlp};
+    /// use std::error::Error;
+    ///
+    /// async fn solve<P: Tnlp + Send + 'static>(
+    ///     problem: P,
+    /// ) -> Result<OptimizationResult, Box<dyn Error + Send + Sync>> {
+    ///     let application = Application::new();
+    ///     let (solve, progress) = application.optimize_tnlp_async(problem);
+    ///
+    ///     std::thread::spawn(move || {
+    ///         for data in progress {
+    ///             println!("Iteration {}: {}", data.iter_count, data.obj_value);
+    ///         }
+    ///     });
+    ///
+    ///     solve.await
+    /// }
+    /// ```
+    pub fn optimize_tnlp_async<P: Tnlp + Send + 'static>(
+        &self,
+        problem: P,
+    ) -> (SolveFuture, Receiver<IntermediateData>) {
+        let shared = Arc::new(Mutex::new(SharedState::default()));
+        let cancellation_token = self
+            .cancellation_token()
+            .map_or_else(CancellationToken::new, CancellationToken::child_token);
+        let (progress, receiver) = mpsc::channel();
+
+        let mut application = self.clone();
+        application
+            .set_cancellation_token(cancellation_token.clone())
+            .add_observer(progress);
+        let guard = CompletionGuard {
+            shared: Arc::clone(&shared),
+            completed: false,
+        };
+
+        let spawned = thread::Builder::new()
+            .name("ipopt-solve".into())
+            .spawn(move || {
+                let output = application
+                    .optimize_tnlp(problem)
+                    .map_err(|e| e.to_string().into());
+                guard.complete(output);
+            });
+        if let Err(e) = spawned {
+            SharedState::complete(&shared, Err(Box::new(e)));
+        }
+
+        let future = SolveFuture {
+            shared,
+            cancellation_token,
+        };
+        (future, receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn future() -> SolveFuture {
+        SolveFuture {
+            shared: Arc::new(Mutex::new(SharedState::default())),
+            cancellation_token: CancellationToken::new(),
+        }
+    }
+
+    #[test]
+    fn solve_future_resolves_once_completed() {
+        let mut future = future();
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert!(Pin::new(&mut future).poll(&mut cx).is_pending());
+
+        SharedState::complete(&future.shared, Ok(OptimizationResult::default()));
+
+        match Pin::new(&mut future).poll(&mut cx) {
+            Poll::Ready(Ok(result)) => assert_eq!(OptimizationResult::default(), result),
+            _ => panic!("Expected the future to resolve successfully."),
+        }
+    }
+
+    #[test]
+    fn solve_future_resolves_to_error_if_guard_dropped() {
+        let mut future = future();
+        let mut cx = Context::from_waker(Waker::noop());
+
+        drop(CompletionGuard {
+            shared: Arc::clone(&future.shared),
+            completed: false,
+        });
+
+        assert!(matches!(
+            Pin::new(&mut future).poll(&mut cx),
+            Poll::Ready(Err(_))
+        ));
+    }
+
+    #[test]
+    fn solve_future_drop_cancels_optimization() {
+        let future = future();
+        let token = future.cancellation_token.clone();
+
+        drop(future);
+
+        assert!(token.is_cancelled());
+    }
+}
@@ -0,0 +1,703 @@
+//! # Ipopt Bindgen Benchmark
+//!
+//! Defines a harness that optimizes a suite of problems under several `Application`
+//! configurations, e.g. different linear solvers, `mu_strategy` settings or scaling methods, and
+//! compares them with a summary table and Dolan-Moré performance profiles.
+//!
+//! A performance profile compares the configurations on a metric such as the number of iterations.
+//! For each problem, the ratio of a configuration is its metric divided by the best metric of any
+//! configuration on that problem, and is infinite if the configuration failed to solve it. The
+//! profile of a configuration is the fraction of problems whose ratio is at most `τ`, as a
+//! function of `τ`, so its value at `τ = 1` is the fraction of problems on which it was the best,
+//! and its limit is the fraction of problems it solved. Measures are raised to a floor of one for
+//! counts and one microsecond for the wall time before dividing, so that a best measure of zero
+//! does not make the ratios of the other configurations infinite.
+
+use crate::{application::Application, report::csv_field, results::OptimizationResult, tnlp::Tnlp};
+use std::{
+    fmt,
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+/// A metric that configurations are compared on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchmarkMetric {
+    /// The number of iterations.
+    Iterations,
+
+    /// The wall-clock time of the optimization.
+    WallTime,
+
+    /// The number of evaluations of the objective function.
+    ObjectiveEvaluations,
+
+    /// The total number of evaluations of the objective, constraints and their derivatives.
+    TotalEvaluations,
+}
+
+/// The outcome of optimizing one problem with one configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkRun {
+    /// The index of the problem in `BenchmarkResults::problems`.
+    pub problem: usize,
+
+    /// The index of the configuration in `BenchmarkResults::configurations`.
+    pub configuration: usize,
+
+    /// The optimization results, or a description of the error if the optimization failed to run.
+    pub result: Result<OptimizationResult, String>,
+
+    /// The wall-clock time of the optimization, excluding the creation of the problem.
+    pub wall_time: Duration,
+}
+
+impl BenchmarkRun {
+    /// Checks whether the optimization ran and found a feasible solution.
+    #[must_use]
+    pub fn succeeded(&self) -> bool {
+        self.result
+            .as_ref()
+            .is_ok_and(OptimizationResult::succeeded)
+    }
+
+    /// Gets the value of a metric for this run.
+    ///
+    /// # Parameters
+    ///
+    /// - `metric` - The metric to get.
+    ///
+    /// # Returns
+    ///
+    /// The value of the metric, or `None` if the run did not succeed.
+    #[must_use]
+    pub fn measure(&self, metric: BenchmarkMetric) -> Option<f64> {
+        let result = self
+            .result
+            .as_ref()
+            .ok()
+            .filter(|result| result.succeeded())?;
+        let performance = &result.performance;
+        let value = match metric {
+            BenchmarkMetric::Iterations => f64::from(performance.number_of_iterations),
+            BenchmarkMetric::WallTime => self.wall_time.as_secs_f64(),
+            BenchmarkMetric::ObjectiveEvaluations => {
+                f64::from(performance.number_of_objective_evaluations)
+            }
+            #[allow(clippy::cast_precision_loss)]
+            BenchmarkMetric::TotalEvaluations => total_evaluations(result) as f64,
+        };
+        Some(value)
+    }
+}
+
+/// Gets the total number of evaluations of the objective, constraints and their derivatives.
+fn total_evaluations(result: &OptimizationResult) -> u64 {
+    let performance = &result.performance;
+    [
+        performance.number_of_objective_evaluations,
+        performance.number_of_objective_gradient_evaluations,
+        performance.number_of_constraint_evaluations,
+        performance.number_of_jacobian_evaluations,
+        performance.number_of_hessian_evaluations,
+    ]
+    .into_iter()
+    .map(u64::from)
+    .sum()
+}
+
+/// The totals of a configuration over every problem of a benchmark.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigurationSummary {
+    /// The name of the configuration.
+    pub configuration: String,
+
+    /// The number of problems for which a feasible solution was found.
+    pub number_solved: usize,
+
+    /// The number of problems for which no feasible solution was found, or the optimization failed
+    /// to run.
+    pub number_failed: usize,
+
+    /// The total number of iterations.
+    pub number_of_iterations: u64,
+
+    /// The total number of evaluations of the objective, constraints and their derivatives.
+    pub number_of_evaluations: u64,
+
+    /// The total wall-clock time.
+    pub wall_time: Duration,
+}
+
+/// The outcome of a benchmark.
+///
+/// The `Display` implementation writes a summary table with one row per configuration.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BenchmarkResults {
+    /// The names of the problems.
+    pub problems: Vec<String>,
+
+    /// The names of the configurations.
+    pub configurations: Vec<String>,
+
+    /// The run of every problem with every configuration, ordered by problem and then by
+    /// configuration.
+    pub runs: Vec<BenchmarkRun>,
+}
+
+impl BenchmarkResults {
+    /// Gets the run of a problem with a configuration.
+    ///
+    /// # Parameters
+    ///
+    /// - `problem` - The index of the problem.
+    /// - `configuration` - The index of the configuration.
+    ///
+    /// # Returns
+    ///
+    /// The run, or `None` if there is no such problem or configuration.
+    #[must_use]
+    pub fn run(&self, problem: usize, configuration: usize) -> Option<&BenchmarkRun> {
+        self.runs
+            .iter()
+            .find(|run| run.problem == problem && run.configuration == configuration)
+    }
+
+    /// Gets the totals of every configuration over every problem.
+    #[must_use]
+    pub fn summaries(&self) -> Vec<ConfigurationSummary> {
+        self.configurations
+            .iter()
+            .enumerate()
+            .map(|(configuration, name)| {
+                let runs: Vec<_> = self
+                    .runs
+                    .iter()
+                    .filter(|run| run.configuration == configuration)
+                    .collect();
+                let results = runs.iter().filter_map(|run| run.result.as_ref().ok());
+                let number_solved = runs.iter().filter(|run| run.succeeded()).count();
+
+                ConfigurationSummary {
+                    configuration: name.clone(),
+                    number_solved,
+                    number_failed: runs.len() - number_solved,
+                    number_of_iterations: results
+                        .clone()
+                        .map(|result| u64::from(result.performance.number_of_iterations))
+                        .sum(),
+                    number_of_evaluations: results.map(total_evaluations).sum(),
+                    wall_time: runs.iter().map(|run| run.wall_time).sum(),
+                }
+            })
+            .collect()
+    }
+
+    /// Computes the Dolan-Moré performance profile of the configurations on a metric.
+    ///
+    /// # Parameters
+    ///
+    /// - `metric` - The metric to compare the configurations on.
+    #[must_use]
+    pub fn performance_profile(&self, metric: BenchmarkMetric) -> PerformanceProfile {
+        let ratios = (0..self.problems.len())
+            .map(|problem| {
+                let measures: Vec<_> = (0..self.configurations.len())
+                    .map(|configuration| {
+                        self.run(problem, configuration)
+                            .and_then(|run| run.measure(metric))
+                            .unwrap_or(f64::INFINITY)
+                    })
+                    .collect();
+                let best = measures.iter().copied().fold(f64::INFINITY, f64::min);
+                measures
+                    .into_iter()
+                    .map(|measure| performance_ratio(measure, best, metric))
+                    .collect()
+            })
+            .collect();
+
+        PerformanceProfile {
+            metric,
+            configurations: self.configurations.clone(),
+            ratios,
+        }
+    }
+
+    /// Writes every run as CSV.
+    ///
+    /// The columns are
+    /// `problem,configuration,status,iterations,objective,objective_evaluations,evaluations,wall_time`,
+    /// with the wall time in seconds. Runs that failed to run have the status `error` and empty
+    /// cells for the other columns except the wall time.
+    ///
+    /// # Parameters
+    ///
+    /// - `writer` - The writer to write the CSV to.
+    ///
+    /// # Errors
+    ///
+    /// Writing to the writer fails.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(
+            writer,
+            "problem,configuration,status,iterations,objective,objective_evaluations,evaluations,wall_time"
+        )?;
+        for run in &self.runs {
+            write!(
+                writer,
+                "{0},{1},",
+                csv_field(&self.problems[run.problem]),
+                csv_field(&self.configurations[run.configuration])
+            )?;
+            match &run.result {
+                Ok(result) => write!(
+                    writer,
+                    "{0},{1},{2},{3},{4},",
+                    result.status_name(),
+                    result.performance.number_of_iterations,
+                    result.solution.objective,
+                    result.performance.number_of_objective_evaluations,
+                    total_evaluations(result)
+                )?,
+                Err(_) => write!(writer, "error,,,,,")?,
+            }
+            writeln!(writer, "{0}", run.wall_time.as_secs_f64())?;
+        }
+
+        writer.flush()
+    }
+}
+
+impl fmt::Display for BenchmarkResults {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let summaries = self.summaries();
+        let width = summaries
+            .iter()
+            .map(|summary| summary.configuration.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Configuration".len());
+
+        writeln!(
+            f,
+            "{0:<width$} {1:>8} {2:>8} {3:>12} {4:>12} {5:>12}",
+            "Configuration", "Solved", "Failed", "Iterations", "Evaluations", "Time (s)"
+        )?;
+        for summary in &summaries {
+            writeln!(
+                f,
+                "{0:<width$} {1:>8} {2:>8} {3:>12} {4:>12} {5:>12.3}",
+                summary.configuration,
+                summary.number_solved,
+                summary.number_failed,
+                summary.number_of_iterations,
+                summary.number_of_evaluations,
+                summary.wall_time.as_secs_f64()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The floor that wall times are raised to when computing performance ratios, since times this
+/// short are dominated by the resolution of the timer.
+const WALL_TIME_FLOOR: Duration = Duration::from_micros(1);
+
+/// Gets the performance ratio of a measure, relative to the best measure on the same problem.
+///
+/// The best configuration always has a ratio of one, even if its measure is zero. Both measures
+/// are raised to a floor of one for counts and `WALL_TIME_FLOOR` for the wall time, so that a
+/// best measure of zero, e.g. a problem solved at the starting point, gives finite ratios.
+fn performance_ratio(measure: f64, best: f64, metric: BenchmarkMetric) -> f64 {
+    let floor = match metric {
+        BenchmarkMetric::WallTime => WALL_TIME_FLOOR.as_secs_f64(),
+        BenchmarkMetric::Iterations
+        | BenchmarkMetric::ObjectiveEvaluations
+        | BenchmarkMetric::TotalEvaluations => 1.0,
+    };
+    if measure.is_infinite() {
+        f64::INFINITY
+    } else if measure <= best {
+        1.0
+    } else {
+        measure.max(floor) / best.max(floor)
+    }
+}
+
+/// The Dolan-Moré performance profile of a set of configurations on a metric.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerformanceProfile {
+    /// The metric that the configurations were compared on.
+    pub metric: BenchmarkMetric,
+
+    /// The names of the configurations.
+    pub configurations: Vec<String>,
+
+    /// The performance ratio of every configuration on every problem, indexed by problem and then
+    /// by configuration.
+    ///
+    /// Ratios are infinite if the configuration did not solve the problem.
+    pub ratios: Vec<Vec<f64>>,
+}
+
+impl PerformanceProfile {
+    /// Gets the fraction of problems on which the ratio of a configuration is at most `tau`.
+    ///
+    /// # Parameters
+    ///
+    /// - `configuration` - The index of the configuration.
+    /// - `tau` - The performance ratio, which is at least one.
+    #[must_use]
+    pub fn fraction_within(&self, configuration: usize, tau: f64) -> f64 {
+        if self.ratios.is_empty() {
+            return 0.0;
+        }
+
+        let number_within = self
+            .ratios
+            .iter()
+            .filter(|ratios| ratios[configuration] <= tau)
+            .count();
+        #[allow(clippy::cast_precision_loss)]
+        let fraction = number_within as f64 / self.ratios.len() as f64;
+        fraction
+    }
+
+    /// Gets the points at which the profile of a configuration steps up.
+    ///
+    /// # Parameters
+    ///
+    /// - `configuration` - The index of the configuration.
+    ///
+    /// # Returns
+    ///
+    /// The pairs of a ratio and the fraction of problems within that ratio, in increasing order of
+    /// the ratio.
+    #[must_use]
+    pub fn steps(&self, configuration: usize) -> Vec<(f64, f64)> {
+        self.finite_ratios(Some(configuration))
+            .into_iter()
+            .map(|tau| (tau, self.fraction_within(configuration, tau)))
+            .collect()
+    }
+
+    /// Writes the profiles of every configuration as CSV, for plotting.
+    ///
+    /// The columns are `tau` followed by the names of the configurations, with one row for every
+    /// distinct finite ratio in increasing order.
+    ///
+    /// # Parameters
+    ///
+    /// - `writer` - The writer to write the CSV to.
+    ///
+    /// # Errors
+    ///
+    /// Writing to the writer fails.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "tau")?;
+        for name in &self.configurations {
+            write!(writer, ",{0}", csv_field(name))?;
+        }
+        writeln!(writer)?;
+
+        for tau in self.finite_ratios(None) {
+            write!(writer, "{tau}")?;
+            for configuration in 0..self.configurations.len() {
+                write!(writer, ",{0}", self.fraction_within(configuration, tau))?;
+            }
+            writeln!(writer)?;
+        }
+
+        writer.flush()
+    }
+
+    /// Gets the distinct finite ratios of a configuration, or of every configuration, in
+    /// increasing order.
+    fn finite_ratios(&self, configuration: Option<usize>) -> Vec<f64> {
+        let mut ratios: Vec<f64> = self
+            .ratios
+            .iter()
+            .flat_map(|ratios| match configuration {
+                Some(configuration) => &ratios[configuration..=configuration],
+                None => ratios.as_slice(),
+            })
+            .copied()
+            .filter(|ratio| ratio.is_finite())
+            .collect();
+        ratios.sort_by(f64::total_cmp);
+        ratios.dedup();
+        ratios
+    }
+}
+
+/// A problem of a benchmark, with the factory that creates a new instance for each run.
+struct BenchmarkProblem {
+    name: String,
+    factory: Box<dyn Fn() -> Box<dyn Tnlp>>,
+}
+
+/// Optimizes a suite of problems under several `Application` configurations.
+///
+/// A new instance of a problem is created by its factory for every configuration. The
+/// optimizations run one after the other on the current thread, so that their wall-clock times
+/// are comparable.
+///
+/// # Example
+///
+/// ```no_run
+/// use ipopt_bindgen::{Application, Benchmark, BenchmarkMetric};
+/// # use ipopt_bindgen::{InitialSolution, ProblemSize, Tnlp};
+/// # struct Problem;
+/// # impl Tnlp for Problem {
+/// #     fn get_nlp_info(&self) -> ProblemSize { ProblemSize::default() }
+/// #     fn get_bounds_info(&self, _: &mut [f64], _: &mut [f64], _: &mut [f64], _: &mut [f64]) {}
+/// #     fn get_starting_point(&self) -> InitialSolution { InitialSolution::default() }
+/// #     fn eval_f(&mut self, _: &[f64], _: &mut f64) -> bool { true }
+/// #     fn eval_grad_f(&mut self, _: &[f64], _: &mut [f64]) -> bool { true }
+/// #     fn eval_g(&mut self, _: &[f64], _: &mut [f64]) -> bool { true }
+/// #     fn get_jacobian_sparsity(&mut self, _: i32, _: i32, _: &mut [i32], _: &mut [i32]) {}
+/// #     fn eval_jac_g(&mut self, _: &[f64], _: i32, _: &mut [f64]) -> bool { true }
+/// #     fn get_hessian_sparsity(&mut self, _: i32, _: i32, _: &mut [i32], _: &mut [i32]) {}
+/// #     fn eval_h(&mut self, _: &[f64], _: f64, _: &[f64], _: i32, _: &mut [f64]) -> bool { true }
+/// # }
+///
+/// let mut adaptive = Application::new();
+/// adaptive.set_string_option("mu_strategy", "adaptive");
+///
+/// let mut benchmark = Benchmark::new();
+/// benchmark
+///     .add_problem("problem", || Problem)
+///     .add_configuration("monotone", Application::new())
+///     .add_configuration("adaptive", adaptive);
+///
+/// let results = benchmark.run();
+/// println!("{results}");
+///
+/// let profile = results.performance_profile(BenchmarkMetric::Iterations);
+/// profile.write_csv(std::fs::File::create("profile.csv")?)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Default)]
+pub struct Benchmark {
+    problems: Vec<BenchmarkProblem>,
+    configurations: Vec<(String, Application)>,
+}
+
+impl Benchmark {
+    /// Creates a new `Benchmark` without problems or configurations.
+    #[must_use]
+    pub fn new() -> Self {
+        Benchmark::default()
+    }
+
+    /// Adds a problem to the suite.
+    ///
+    /// # Parameters
+    ///
+    /// - `name` - The name of the problem.
+    /// - `factory` - Creates a new instance of the problem for each configuration.
+    pub fn add_problem<P, F>(&mut self, name: impl Into<String>, factory: F) -> &mut Self
+    where
+        P: Tnlp + 'static,
+        F: Fn() -> P + 'static,
+    {
+        self.problems.push(BenchmarkProblem {
+            name: name.into(),
+            factory: Box::new(move || Box::new(factory())),
+        });
+        self
+    }
+
+    /// Adds a configuration to compare.
+    ///
+    /// # Parameters
+    ///
+    /// - `name` - The name of the configuration.
+    /// - `application` - The application, with the options of the configuration.
+    pub fn add_configuration(
+        &mut self,
+        name: impl Into<String>,
+        application: Application,
+    ) -> &mut Self {
+        self.configurations.push((name.into(), application));
+        self
+    }
+
+    /// Optimizes every problem with every configuration.
+    ///
+    /// # Returns
+    ///
+    /// The runs of every problem with every configuration.
+    #[must_use]
+    pub fn run(&self) -> BenchmarkResults {
+        let mut runs = Vec::with_capacity(self.problems.len() * self.configurations.len());
+        for (problem_index, problem) in self.problems.iter().enumerate() {
+            for (configuration_index, (_, application)) in self.configurations.iter().enumerate() {
+                let mut instance = (problem.factory)();
+                let start = Instant::now();
+                let result = application
+                    .optimize_tnlp(instance.as_mut())
+                    .map_err(|e| e.to_string());
+                runs.push(BenchmarkRun {
+                    problem: problem_index,
+                    configuration: configuration_index,
+                    result,
+                    wall_time: start.elapsed(),
+                });
+            }
+        }
+
+        BenchmarkResults {
+            problems: self
+                .problems
+                .iter()
+                .map(|problem| problem.name.clone())
+                .collect(),
+            configurations: self
+                .configurations
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect(),
+            runs,
+        }
+    }
+}
+
+impl fmt::Debug for Benchmark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Benchmark")
+            .field(
+                "problems",
+                &self
+                    .problems
+                    .iter()
+                    .map(|problem| &problem.name)
+                    .collect::<Vec<_>>(),
+            )
+            .field("configurations", &self.configurations)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(problem: usize, configuration: usize, status: i32, iterations: u32) -> BenchmarkRun {
+        let mut result = OptimizationResult {
+            status,
+            ..Default::default()
+        };
+        result.performance.number_of_iterations = iterations;
+        result.performance.number_of_objective_evaluations = iterations + 1;
+        BenchmarkRun {
+            problem,
+            configuration,
+            result: Ok(result),
+            wall_time: Duration::from_millis(u64::from(iterations)),
+        }
+    }
+
+    fn results() -> BenchmarkResults {
+        BenchmarkResults {
+            problems: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            configurations: vec!["monotone".to_string(), "adaptive, ma27".to_string()],
+            runs: vec![
+                run(0, 0, 0, 10),
+                run(0, 1, 0, 20),
+                run(1, 0, 0, 30),
+                run(1, 1, 1, 15),
+                run(2, 0, -1, 100),
+                BenchmarkRun {
+                    problem: 2,
+                    configuration: 1,
+                    result: Err("error".into()),
+                    wall_time: Duration::ZERO,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn benchmark_results_performance_profile_computes_ratios() {
+        let profile = results().performance_profile(BenchmarkMetric::Iterations);
+
+        assert_eq!(
+            vec![
+                vec![1.0, 2.0],
+                vec![2.0, 1.0],
+                vec![f64::INFINITY, f64::INFINITY]
+            ],
+            profile.ratios
+        );
+        assert_eq!(
+            vec![1.0 / 3.0, 2.0 / 3.0],
+            vec![
+                profile.fraction_within(0, 1.0),
+                profile.fraction_within(0, 2.0)
+            ]
+        );
+        assert_eq!(vec![(1.0, 1.0 / 3.0), (2.0, 2.0 / 3.0)], profile.steps(1));
+    }
+
+    #[test]
+    fn benchmark_results_performance_profile_floors_zero_measures() {
+        let results = BenchmarkResults {
+            problems: vec!["a".to_string()],
+            configurations: vec![
+                "first".to_string(),
+                "second".to_string(),
+                "third".to_string(),
+            ],
+            runs: vec![run(0, 0, 0, 0), run(0, 1, 0, 0), run(0, 2, 0, 4)],
+        };
+
+        let profile = results.performance_profile(BenchmarkMetric::Iterations);
+        assert_eq!(vec![vec![1.0, 1.0, 4.0]], profile.ratios);
+
+        let profile = results.performance_profile(BenchmarkMetric::WallTime);
+        assert!(profile.ratios[0].iter().all(|ratio| ratio.is_finite()));
+    }
+
+    #[test]
+    fn performance_profile_write_csv_writes_fractions() {
+        let profile = results().performance_profile(BenchmarkMetric::ObjectiveEvaluations);
+        let mut csv = Vec::new();
+
+        profile.write_csv(&mut csv).unwrap();
+
+        assert_eq!(
+            "tau,monotone,\"adaptive, ma27\"\n\
+             1,0.3333333333333333,0.3333333333333333\n\
+             1.9090909090909092,0.3333333333333333,0.6666666666666666\n\
+             1.9375,0.6666666666666666,0.6666666666666666\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
+
+    #[test]
+    fn benchmark_results_summaries_count_solved_problems() {
+        let summaries = results().summaries();
+
+        assert_eq!(2, summaries[0].number_solved);
+        assert_eq!(1, summaries[0].number_failed);
+        assert_eq!(140, summaries[0].number_of_iterations);
+        assert_eq!(Duration::from_millis(140), summaries[0].wall_time);
+        assert_eq!(2, summaries[1].number_solved);
+        assert_eq!(35, summaries[1].number_of_iterations);
+        assert!(results().to_string().contains("\nadaptive, ma27        2 "));
+    }
+
+    #[test]
+    fn benchmark_results_write_csv_writes_runs() {
+        let mut csv = Vec::new();
+
+        results().write_csv(&mut csv).unwrap();
+
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.contains("\na,monotone,Solve_Succeeded,10,0,11,11,0.01\n"));
+        assert!(csv.ends_with("\nc,\"adaptive, ma27\",error,,,,,0\n"));
+    }
+}
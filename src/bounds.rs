@@ -0,0 +1,284 @@
+//! # Ipopt Bindgen Bounds
+//!
+//! Defines helpers for setting the bounds of variables and constraints in `Tnlp::get_bounds_info`.
+//!
+//! Ipopt treats any bound at or beyond the `nlp_lower_bound_inf` and `nlp_upper_bound_inf` options
+//! (`-1e19` and `1e19` by default) as infinite. Infinite bounds can be given as `f64::INFINITY` and
+//! `f64::NEG_INFINITY`, which the `Application` replaces with the values of those options before
+//! passing them to Ipopt, so they stay consistent if the options are overridden.
+//!
+//! When a solution is analyzed or reported, bounds are classified as infinite with
+//! `BoundInfinities`, which should match the options of the `Application` that optimized the
+//! problem, see `Application::bound_infinities`.
+
+/// The magnitude at or beyond which a bound is treated as infinite, matching the defaults of the
+/// Ipopt `nlp_lower_bound_inf` and `nlp_upper_bound_inf` options.
+pub const BOUND_INFINITY: f64 = 1e19;
+
+/// The values at or beyond which lower and upper bounds are treated as infinite, i.e. the values
+/// of the Ipopt `nlp_lower_bound_inf` and `nlp_upper_bound_inf` options.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundInfinities {
+    /// Lower bounds at or below this value are infinite.
+    pub lower: f64,
+
+    /// Upper bounds at or above this value are infinite.
+    pub upper: f64,
+}
+
+impl Default for BoundInfinities {
+    fn default() -> Self {
+        BoundInfinities {
+            lower: -BOUND_INFINITY,
+            upper: BOUND_INFINITY,
+        }
+    }
+}
+
+impl BoundInfinities {
+    /// Classifies a raw lower bound value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ipopt_bindgen::{Bound, BoundInfinities};
+    ///
+    /// let infinities = BoundInfinities {
+    ///     lower: -1e10,
+    ///     upper: 1e10,
+    /// };
+    ///
+    /// assert_eq!(Bound::Infinite, infinities.lower_bound(-1e10));
+    /// assert_eq!(Bound::Finite(-1e9), infinities.lower_bound(-1e9));
+    /// ```
+    #[must_use]
+    pub fn lower_bound(self, value: f64) -> Bound {
+        if value <= self.lower {
+            Bound::Infinite
+        } else {
+            Bound::Finite(value)
+        }
+    }
+
+    /// Classifies a raw upper bound value.
+    #[must_use]
+    pub fn upper_bound(self, value: f64) -> Bound {
+        if value >= self.upper {
+            Bound::Infinite
+        } else {
+            Bound::Finite(value)
+        }
+    }
+}
+
+/// A single lower or upper bound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Bound {
+    /// A finite bound.
+    Finite(#[cfg_attr(feature = "serde", serde(with = "crate::float_serde::value"))] f64),
+
+    /// No bound, i.e. negative infinity for a lower bound and positive infinity for an upper
+    /// bound.
+    Infinite,
+}
+
+impl Bound {
+    /// Creates a `Bound` from a raw bound value, treating any value with a magnitude of at least
+    /// `BOUND_INFINITY` as infinite.
+    ///
+    /// This matches the default Ipopt options. If they may have been overridden, use
+    /// `BoundInfinities` instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ipopt_bindgen::Bound;
+    ///
+    /// assert_eq!(Bound::Finite(1.0), Bound::from_value(1.0));
+    /// assert_eq!(Bound::Infinite, Bound::from_value(2e19));
+    /// assert_eq!(Bound::Infinite, Bound::from_value(f64::NEG_INFINITY));
+    /// ```
+    #[must_use]
+    pub fn from_value(value: f64) -> Self {
+        if value.abs() >= BOUND_INFINITY {
+            Bound::Infinite
+        } else {
+            Bound::Finite(value)
+        }
+    }
+
+    /// Checks whether the bound is infinite.
+    #[must_use]
+    pub fn is_infinite(self) -> bool {
+        self == Bound::Infinite
+    }
+
+    /// Gets the value of the bound when used as a lower bound.
+    #[must_use]
+    pub fn lower_value(self) -> f64 {
+        match self {
+            Bound::Finite(value) => value,
+            Bound::Infinite => f64::NEG_INFINITY,
+        }
+    }
+
+    /// Gets the value of the bound when used as an upper bound.
+    #[must_use]
+    pub fn upper_value(self) -> f64 {
+        match self {
+            Bound::Finite(value) => value,
+            Bound::Infinite => f64::INFINITY,
+        }
+    }
+}
+
+/// The lower and upper bounds of a variable or constraint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bounds {
+    /// The lower bound.
+    pub lower: Bound,
+
+    /// The upper bound.
+    pub upper: Bound,
+}
+
+impl Bounds {
+    /// Creates bounds with neither a lower nor an upper bound.
+    #[must_use]
+    pub fn free() -> Self {
+        Bounds {
+            lower: Bound::Infinite,
+            upper: Bound::Infinite,
+        }
+    }
+
+    /// Creates bounds with only a lower bound.
+    ///
+    /// # Parameters
+    ///
+    /// - `value` - The lower bound.
+    #[must_use]
+    pub fn lower(value: f64) -> Self {
+        Bounds {
+            lower: Bound::Finite(value),
+            upper: Bound::Infinite,
+        }
+    }
+
+    /// Creates bounds with only an upper bound.
+    ///
+    /// # Parameters
+    ///
+    /// - `value` - The upper bound.
+    #[must_use]
+    pub fn upper(value: f64) -> Self {
+        Bounds {
+            lower: Bound::Infinite,
+            upper: Bound::Finite(value),
+        }
+    }
+
+    /// Creates bounds with equal lower and upper bounds, i.e. a fixed variable or an equality
+    /// constraint.
+    ///
+    /// # Parameters
+    ///
+    /// - `value` - The value of both bounds.
+    #[must_use]
+    pub fn equal(value: f64) -> Self {
+        Bounds::range(value, value)
+    }
+
+    /// Creates bounds with both a lower and an upper bound.
+    ///
+    /// # Parameters
+    ///
+    /// - `lower` - The lower bound.
+    /// - `upper` - The upper bound.
+    #[must_use]
+    pub fn range(lower: f64, upper: f64) -> Self {
+        Bounds {
+            lower: Bound::Finite(lower),
+            upper: Bound::Finite(upper),
+        }
+    }
+
+    /// Writes the bounds to the lower and upper bound values of a variable or constraint.
+    ///
+    /// # Parameters
+    ///
+    /// - `lower` - The lower bound value to write to.
+    /// - `upper` - The upper bound value to write to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ipopt_bindgen::Bounds;
+    ///
+    /// let mut g_l = [0.0; 2];
+    /// let mut g_u = [0.0; 2];
+    ///
+    /// Bounds::lower(25.0).write_to(&mut g_l[0], &mut g_u[0]);
+    /// Bounds::equal(40.0).write_to(&mut g_l[1], &mut g_u[1]);
+    ///
+    /// assert_eq!([25.0, 40.0], g_l);
+    /// assert_eq!([f64::INFINITY, 40.0], g_u);
+    /// ```
+    pub fn write_to(self, lower: &mut f64, upper: &mut f64) {
+        *lower = self.lower.lower_value();
+        *upper = self.upper.upper_value();
+    }
+
+    /// Writes the bounds to every element of the lower and upper bound slices.
+    ///
+    /// # Parameters
+    ///
+    /// - `lower` - The lower bound values to write to.
+    /// - `upper` - The upper bound values to write to.
+    pub fn fill(self, lower: &mut [f64], upper: &mut [f64]) {
+        lower.fill(self.lower.lower_value());
+        upper.fill(self.upper.upper_value());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_constructors_produce_expected_values() {
+        let values = |bounds: Bounds| (bounds.lower.lower_value(), bounds.upper.upper_value());
+
+        assert_eq!((f64::NEG_INFINITY, f64::INFINITY), values(Bounds::free()));
+        assert_eq!((1.0, f64::INFINITY), values(Bounds::lower(1.0)));
+        assert_eq!((f64::NEG_INFINITY, 2.0), values(Bounds::upper(2.0)));
+        assert_eq!((3.0, 3.0), values(Bounds::equal(3.0)));
+        assert_eq!((1.0, 5.0), values(Bounds::range(1.0, 5.0)));
+    }
+
+    #[test]
+    fn bound_infinities_classify_bounds_at_thresholds() {
+        let infinities = BoundInfinities {
+            lower: -1e10,
+            upper: 1e10,
+        };
+
+        assert_eq!(Bound::Infinite, infinities.upper_bound(1e10));
+        assert_eq!(Bound::Finite(1e9), infinities.upper_bound(1e9));
+        assert_eq!(Bound::Finite(1e10), infinities.lower_bound(1e10));
+        assert_eq!(Bound::Infinite, infinities.lower_bound(f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn bounds_fill_writes_every_element() {
+        let mut x_l = vec![0.0; 3];
+        let mut x_u = vec![0.0; 3];
+
+        Bounds::range(1.0, 5.0).fill(&mut x_l, &mut x_u);
+
+        assert_eq!(vec![1.0; 3], x_l);
+        assert_eq!(vec![5.0; 3], x_u);
+    }
+}
@@ -0,0 +1,159 @@
+//! # Ipopt Bindgen Cancellation
+//!
+//! Defines a token that can be used to cooperatively stop a running optimization from another
+//! thread.
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+/// A cloneable handle that can be used to request that a running optimization stops early.
+///
+/// All clones of a token share the same state, so cancelling any one of them cancels them all.
+/// Child tokens can also be created, which are cancelled along with their parent but can be
+/// cancelled without affecting it.
+///
+/// The token is checked by the `Application` at the end of every Ipopt iteration. A cancelled
+/// solve terminates with the `User_Requested_Stop` status and returns the most recent iterate.
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    parent: Option<Box<CancellationToken>>,
+}
+
+impl CancellationToken {
+    /// Creates a new `CancellationToken` that has not been cancelled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ipopt_bindgen::CancellationToken;
+    ///
+    /// let token = CancellationToken::new();
+    ///
+    /// assert!(!token.is_cancelled());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        CancellationToken {
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new token that is cancelled when either it or this token is cancelled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ipopt_bindgen::CancellationToken;
+    ///
+    /// let parent = CancellationToken::new();
+    /// let child = parent.child_token();
+    ///
+    /// child.cancel();
+    /// assert!(!parent.is_cancelled());
+    ///
+    /// let child = parent.child_token();
+    ///
+    /// parent.cancel();
+    /// assert!(child.is_cancelled());
+    /// ```
+    #[must_use]
+    pub fn child_token(&self) -> Self {
+        CancellationToken {
+            cancelled: Arc::default(),
+            parent: Some(Box::new(self.clone())),
+        }
+    }
+
+    /// Requests that any optimization using this token (or a clone of it) stops.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ipopt_bindgen::CancellationToken;
+    ///
+    /// let token = CancellationToken::new();
+    /// let clone = token.clone();
+    ///
+    /// clone.cancel();
+    ///
+    /// assert!(token.is_cancelled());
+    /// ```
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Clears a previous cancellation request so that the token can be reused.
+    ///
+    /// This does not affect the parent of a child token.
+    pub fn reset(&self) {
+        self.cancelled.store(false, Ordering::Release);
+    }
+
+    /// Checks whether cancellation has been requested.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+            || self.parent.as_ref().is_some_and(|p| p.is_cancelled())
+    }
+}
+
+/// Tokens are equal if they share the same underlying state (i.e. one is a clone of the other).
+impl PartialEq for CancellationToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.cancelled, &other.cancelled)
+    }
+}
+
+impl Eq for CancellationToken {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancellation_token_cancel_is_shared_between_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+
+        clone.cancel();
+
+        assert!(token.is_cancelled());
+        assert!(clone.is_cancelled());
+    }
+
+    #[test]
+    fn cancellation_token_reset_clears_cancellation() {
+        let token = CancellationToken::new();
+        token.cancel();
+
+        token.reset();
+
+        assert!(!token.is_cancelled());
+    }
+
+    #[test]
+    fn cancellation_token_eq_only_for_clones() {
+        let token = CancellationToken::new();
+
+        assert_eq!(token, token.clone());
+        assert_ne!(token, CancellationToken::new());
+        assert_ne!(token, token.child_token());
+    }
+
+    #[test]
+    fn cancellation_token_child_is_cancelled_with_parent_only() {
+        let parent = CancellationToken::new();
+        let child = parent.child_token();
+        let grandchild = child.child_token();
+
+        grandchild.cancel();
+        assert!(!child.is_cancelled());
+
+        parent.cancel();
+        assert!(child.is_cancelled());
+        assert!(grandchild.is_cancelled());
+    }
+}
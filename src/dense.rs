@@ -0,0 +1,379 @@
+//! # Ipopt Bindgen Dense
+//!
+//! Defines a trait for small nonlinear problems with dense derivatives, and an adapter that
+//! implements `Tnlp` for them by generating the full sparsity patterns automatically.
+
+use crate::tnlp::{InitialSolution, IntermediateData, OptionValue, ProblemSize, Tnlp, UserScaling};
+
+/// A trait for NLPs with dense derivative matrices.
+///
+/// The Jacobian of the constraints is a dense, row-major, `m x n` matrix, and the Hessian of the
+/// Lagrangian is a dense, row-major, `n x n` matrix of which only the lower triangle (including
+/// the diagonal) is read. Use `DenseAdapter` to optimize a `DenseTnlp` with an `Application`.
+///
+/// Methods that are shared with `Tnlp` have the same semantics.
+pub trait DenseTnlp {
+    /// Gets the number of primal variables in the problem.
+    fn number_of_variables(&self) -> usize;
+
+    /// Gets the number of constraint functions in the problem.
+    fn number_of_constraints(&self) -> usize;
+
+    /// Gets the bounds on the variables and constraints.
+    ///
+    /// # Parameters
+    ///
+    /// - `x_l` - The variable lower bounds.
+    /// - `x_u` - The variable upper bounds.
+    /// - `g_l` - The constraint lower bounds.
+    /// - `g_u` - The constraint upper bounds.
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]);
+
+    /// Gets the problem scaling parameters.
+    ///
+    /// The default implementation uses the Ipopt default scaling factors.
+    fn get_scaling(&self) -> UserScaling {
+        UserScaling::default()
+    }
+
+    /// Gets the starting point for the optimization.
+    fn get_starting_point(&self) -> InitialSolution;
+
+    /// Evaluates the objective function.
+    ///
+    /// # Parameters
+    /// - `x` - The current variable values.
+    /// - `obj_value` - The objective function value.
+    ///
+    /// # Returns
+    /// `true` if the objective function was successfully evaluated, `false` otherwise.
+    fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool;
+
+    /// Evaluates the gradient of the objective function.
+    ///
+    /// # Parameters
+    /// - `x` - The current variable values.
+    /// - `grad_f` - The gradient of the objective function.
+    ///
+    /// # Returns
+    /// `true` if the gradient was successfully evaluated, `false` otherwise.
+    fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool;
+
+    /// Evaluates the constraint functions.
+    ///
+    /// # Parameters
+    /// - `x` - The current variable values.
+    /// - `g` - The constraint function values.
+    ///
+    /// # Returns
+    /// `true` if the constraints were successfully evaluated, `false` otherwise.
+    fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool;
+
+    /// Evaluates the dense Jacobian of the constraints.
+    ///
+    /// # Parameters
+    /// - `x` - The current variable values.
+    /// - `jacobian` - The row-major `m x n` Jacobian, where element `i * n + j` is the derivative
+    ///   of constraint `i` with respect to variable `j`.
+    ///
+    /// # Returns
+    /// `true` if the Jacobian was successfully evaluated, `false` otherwise.
+    fn eval_jac_g(&mut self, x: &[f64], jacobian: &mut [f64]) -> bool;
+
+    /// Evaluates the dense Hessian of the Lagrangian.
+    ///
+    /// # Parameters
+    /// - `x` - The current variable values.
+    /// - `obj_factor` - The factor of the objective function in the Lagrangian.
+    /// - `lambda` - The constraint multipliers.
+    /// - `hessian` - The row-major `n x n` Hessian. Only the elements `i * n + j` with `j <= i`
+    ///   are read, so the upper triangle does not need to be filled. It is zeroed before each call.
+    ///
+    /// # Returns
+    /// `true` if the Hessian was successfully evaluated, `false` otherwise.
+    fn eval_h(&mut self, x: &[f64], obj_factor: f64, lambda: &[f64], hessian: &mut [f64]) -> bool;
+
+    /// User code that runs at each iteration.
+    ///
+    /// # Parameters
+    /// - `data` - The intermediate data at the current iteration.
+    ///
+    /// Return `false` to terminate the optimization.
+    fn intermediate(&mut self, _data: IntermediateData) -> bool {
+        true
+    }
+
+    /// Gets the names of the variables, which are used in diagnostics and reports.
+    fn variable_names(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Gets the names of the constraints, which are used in diagnostics and reports.
+    fn constraint_names(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Gets the Ipopt options that suit the structure of the problem.
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        Vec::new()
+    }
+}
+
+/// Adapts a `DenseTnlp` into a `Tnlp`.
+///
+/// The Jacobian pattern contains every element in row-major order, so the dense Jacobian is passed
+/// straight through. The Hessian pattern contains the lower triangle in row-major order, and the
+/// dense Hessian is copied into it after each evaluation.
+#[derive(Debug, Default, Clone)]
+pub struct DenseAdapter<P> {
+    problem: P,
+    hessian: Vec<f64>,
+}
+
+impl<P: DenseTnlp> DenseAdapter<P> {
+    /// Creates a new `DenseAdapter`.
+    ///
+    /// # Parameters
+    ///
+    /// - `problem` - The dense problem to adapt.
+    #[must_use]
+    pub fn new(problem: P) -> Self {
+        DenseAdapter {
+            problem,
+            hessian: Vec::new(),
+        }
+    }
+
+    /// Gets the dense problem.
+    #[must_use]
+    pub fn problem(&self) -> &P {
+        &self.problem
+    }
+
+    /// Gets the dense problem mutably.
+    #[must_use]
+    pub fn problem_mut(&mut self) -> &mut P {
+        &mut self.problem
+    }
+
+    /// Unwraps the dense problem.
+    #[must_use]
+    pub fn into_inner(self) -> P {
+        self.problem
+    }
+}
+
+impl<P: DenseTnlp> Tnlp for DenseAdapter<P> {
+    /// # Panics
+    ///
+    /// The number of elements in the dense Jacobian or in the lower triangle of the dense Hessian
+    /// is not representable as an `i32`.
+    fn get_nlp_info(&self) -> ProblemSize {
+        let n = self.problem.number_of_variables();
+        let m = self.problem.number_of_constraints();
+        let to_i32 = |value: Option<usize>| {
+            value
+                .and_then(|value| i32::try_from(value).ok())
+                .expect("The dense problem is too large for Ipopt.")
+        };
+
+        ProblemSize {
+            n: to_i32(Some(n)),
+            m: to_i32(Some(m)),
+            nnz_jac: to_i32(m.checked_mul(n)),
+            nnz_hess: to_i32(n.checked_mul(n + 1).map(|value| value / 2)),
+        }
+    }
+
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
+        self.problem.get_bounds_info(x_l, x_u, g_l, g_u);
+    }
+
+    fn get_scaling(&self) -> UserScaling {
+        self.problem.get_scaling()
+    }
+
+    fn get_starting_point(&self) -> InitialSolution {
+        self.problem.get_starting_point()
+    }
+
+    fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+        self.problem.eval_f(x, obj_value)
+    }
+
+    fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+        self.problem.eval_grad_f(x, grad_f)
+    }
+
+    fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+        self.problem.eval_g(x, g)
+    }
+
+    fn get_jacobian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        let cells = (0..m).flat_map(|row| (0..n).map(move |col| (row, col)));
+        for ((i, j), (row, col)) in i_row.iter_mut().zip(j_col.iter_mut()).zip(cells) {
+            *i = row;
+            *j = col;
+        }
+    }
+
+    fn eval_jac_g(&mut self, x: &[f64], _m: i32, values: &mut [f64]) -> bool {
+        self.problem.eval_jac_g(x, values)
+    }
+
+    fn get_hessian_sparsity(&mut self, n: i32, _m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        let cells = (0..n).flat_map(|row| (0..=row).map(move |col| (row, col)));
+        for ((i, j), (row, col)) in i_row.iter_mut().zip(j_col.iter_mut()).zip(cells) {
+            *i = row;
+            *j = col;
+        }
+    }
+
+    fn eval_h(
+        &mut self,
+        x: &[f64],
+        obj_factor: f64,
+        lambda: &[f64],
+        _m: i32,
+        values: &mut [f64],
+    ) -> bool {
+        let n = x.len();
+        self.hessian.clear();
+        self.hessian.resize(n * n, 0.0);
+
+        if !self
+            .problem
+            .eval_h(x, obj_factor, lambda, &mut self.hessian)
+        {
+            return false;
+        }
+
+        let lower_triangle = (0..n).flat_map(|row| &self.hessian[row * n..=row * n + row]);
+        for (value, &dense) in values.iter_mut().zip(lower_triangle) {
+            *value = dense;
+        }
+        true
+    }
+
+    fn intermediate(&mut self, data: IntermediateData) -> bool {
+        self.problem.intermediate(data)
+    }
+
+    fn variable_names(&self) -> Option<Vec<String>> {
+        self.problem.variable_names()
+    }
+
+    fn constraint_names(&self) -> Option<Vec<String>> {
+        self.problem.constraint_names()
+    }
+
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        self.problem.default_options()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// min x0 * x1 + x1^2 s.t. x0 + 2 x1 = 1.
+    struct Quadratic;
+
+    impl DenseTnlp for Quadratic {
+        fn number_of_variables(&self) -> usize {
+            2
+        }
+
+        fn number_of_constraints(&self) -> usize {
+            1
+        }
+
+        fn get_bounds_info(
+            &self,
+            _x_l: &mut [f64],
+            _x_u: &mut [f64],
+            _g_l: &mut [f64],
+            _g_u: &mut [f64],
+        ) {
+        }
+
+        fn get_starting_point(&self) -> InitialSolution {
+            InitialSolution::from_variables(vec![0.0, 0.0])
+        }
+
+        fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+            *obj_value = x[0] * x[1] + x[1] * x[1];
+            true
+        }
+
+        fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+            grad_f.copy_from_slice(&[x[1], x[0] + 2.0 * x[1]]);
+            true
+        }
+
+        fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+            g[0] = x[0] + 2.0 * x[1];
+            true
+        }
+
+        fn eval_jac_g(&mut self, _x: &[f64], jacobian: &mut [f64]) -> bool {
+            jacobian.copy_from_slice(&[1.0, 2.0]);
+            true
+        }
+
+        fn eval_h(
+            &mut self,
+            _x: &[f64],
+            obj_factor: f64,
+            _lambda: &[f64],
+            hessian: &mut [f64],
+        ) -> bool {
+            hessian[2] = obj_factor;
+            hessian[3] = 2.0 * obj_factor;
+            true
+        }
+    }
+
+    #[test]
+    fn dense_adapter_get_nlp_info_counts_dense_elements() {
+        let adapter = DenseAdapter::new(Quadratic);
+
+        assert_eq!(
+            ProblemSize {
+                n: 2,
+                m: 1,
+                nnz_jac: 2,
+                nnz_hess: 3,
+            },
+            adapter.get_nlp_info()
+        );
+    }
+
+    #[test]
+    fn dense_adapter_generates_full_patterns() {
+        let mut adapter = DenseAdapter::new(Quadratic);
+        let (mut i_row, mut j_col) = ([0; 3], [0; 3]);
+
+        adapter.get_hessian_sparsity(2, 1, &mut i_row, &mut j_col);
+
+        assert_eq!([0, 1, 1], i_row);
+        assert_eq!([0, 0, 1], j_col);
+
+        let (mut i_row, mut j_col) = ([0; 2], [0; 2]);
+
+        adapter.get_jacobian_sparsity(2, 1, &mut i_row, &mut j_col);
+
+        assert_eq!([0, 0], i_row);
+        assert_eq!([0, 1], j_col);
+    }
+
+    #[test]
+    fn dense_adapter_eval_h_copies_lower_triangle() {
+        let mut adapter = DenseAdapter::new(Quadratic);
+        let mut values = vec![-1.0; 3];
+
+        assert!(adapter.eval_h(&[0.0, 0.0], 2.0, &[1.0], 1, &mut values));
+
+        assert_eq!(vec![0.0, 2.0, 4.0], values);
+    }
+}
@@ -0,0 +1,274 @@
+//! # Ipopt Bindgen Float Serde
+//!
+//! Defines `serde(with = ...)` helpers for floating point values that may be non-finite, such as
+//! bounds and multipliers.
+//!
+//! JSON has no representation of infinity or NaN, so `serde_json` writes them as `null` and then
+//! fails to read them back. For human-readable formats, non-finite values are instead written as
+//! the strings `"inf"`, `"-inf"` and `"nan"`. Finite values are written unchanged, including the
+//! `±2e19` that Ipopt uses for infinite bounds, which are already JSON-safe. Binary formats are
+//! unaffected.
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, Visitor},
+};
+use std::{collections::BTreeMap, fmt};
+
+/// A float that is serialized as a string if it is not finite.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Float(f64);
+
+impl Serialize for Float {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = self.0;
+        if value.is_finite() || !serializer.is_human_readable() {
+            serializer.serialize_f64(value)
+        } else if value.is_nan() {
+            serializer.serialize_str("nan")
+        } else if value > 0.0 {
+            serializer.serialize_str("inf")
+        } else {
+            serializer.serialize_str("-inf")
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Float {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(FloatVisitor)
+        } else {
+            f64::deserialize(deserializer).map(Float)
+        }
+    }
+}
+
+/// Visits either a number or one of the non-finite strings.
+struct FloatVisitor;
+
+impl Visitor<'_> for FloatVisitor {
+    type Value = Float;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a number or one of \"inf\", \"-inf\" or \"nan\"")
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Float, E> {
+        Ok(Float(value))
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Float, E> {
+        Ok(Float(value as f64))
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Float, E> {
+        Ok(Float(value as f64))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Float, E> {
+        match value {
+            "inf" => Ok(Float(f64::INFINITY)),
+            "-inf" => Ok(Float(f64::NEG_INFINITY)),
+            "nan" => Ok(Float(f64::NAN)),
+            _ => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+        }
+    }
+}
+
+/// Serializes a single `f64`.
+pub(crate) mod value {
+    use super::{Deserialize, Deserializer, Float, Serialize, Serializer};
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    pub fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        Float(*value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        Float::deserialize(deserializer).map(|f| f.0)
+    }
+}
+
+/// Serializes a `Vec<f64>`.
+pub(crate) mod vec {
+    use super::{Deserialize, Deserializer, Float, Serializer};
+
+    #[allow(clippy::ptr_arg)]
+    pub fn serialize<S: Serializer>(values: &Vec<f64>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(|&v| Float(v)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<f64>, D::Error> {
+        let values = Vec::<Float>::deserialize(deserializer)?;
+        Ok(values.into_iter().map(|f| f.0).collect())
+    }
+}
+
+/// Serializes an `Option<f64>`.
+pub(crate) mod option {
+    use super::{Deserialize, Deserializer, Float, Serialize, Serializer};
+
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S: Serializer>(value: &Option<f64>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.map(Float).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<f64>, D::Error> {
+        Ok(Option::<Float>::deserialize(deserializer)?.map(|f| f.0))
+    }
+}
+
+/// Serializes a `BTreeMap<String, f64>`, such as the numeric options of an `Application`.
+pub(crate) mod map {
+    use super::{BTreeMap, Deserialize, Deserializer, Float, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        values: &BTreeMap<String, f64>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(values.iter().map(|(key, &v)| (key, Float(v))))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<String, f64>, D::Error> {
+        let values = BTreeMap::<String, Float>::deserialize(deserializer)?;
+        Ok(values.into_iter().map(|(key, f)| (key, f.0)).collect())
+    }
+}
+
+/// Serializes an `Option<Vec<f64>>`.
+pub(crate) mod option_vec {
+    use super::{Deserialize, Deserializer, Float, Serialize, Serializer};
+
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S: Serializer>(
+        values: &Option<Vec<f64>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        values
+            .as_ref()
+            .map(|values| values.iter().map(|&v| Float(v)).collect::<Vec<_>>())
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<f64>>, D::Error> {
+        let values = Option::<Vec<Float>>::deserialize(deserializer)?;
+        Ok(values.map(|values| values.into_iter().map(|f| f.0).collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application::Application,
+        results::{OptimizationResult, Solution},
+        statistics::{FinalStatistics, ScaledValue},
+        tnlp::{InitialSolution, IntermediateData, ProblemBounds, UserScaling},
+    };
+
+    #[test]
+    fn problem_bounds_serde_round_trips_infinite_values() {
+        let bounds = ProblemBounds {
+            x_l: vec![f64::NEG_INFINITY, -2e19],
+            x_u: vec![f64::INFINITY, 2e19],
+            g_l: vec![1.5],
+            g_u: vec![f64::INFINITY],
+        };
+
+        let json = serde_json::to_string(&bounds).unwrap();
+
+        assert_eq!(
+            r#"{"x_l":["-inf",-2e+19],"x_u":["inf",2e+19],"g_l":[1.5],"g_u":["inf"]}"#,
+            json
+        );
+        assert_eq!(bounds, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn optimization_result_serde_round_trips() {
+        let result = OptimizationResult {
+            solution: Solution {
+                x: vec![1.0, 2.0],
+                constraints: vec![3.0],
+                lambda: vec![-1.0],
+                z_l: vec![0.0, 0.5],
+                z_u: vec![0.0, 0.0],
+                objective: f64::INFINITY,
+            },
+            output: Some("EXIT: Optimal Solution Found.".to_string()),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_string(&result).unwrap();
+
+        assert_eq!(result, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn intermediate_data_serde_round_trips_non_finite_values() {
+        let data = IntermediateData {
+            mu: f64::INFINITY,
+            d_norm: f64::NEG_INFINITY,
+            regularization_size: f64::INFINITY,
+            alpha_du: f64::INFINITY,
+            alpha_pr: f64::NEG_INFINITY,
+            ..Default::default()
+        };
+
+        let json = serde_json::to_string(&data).unwrap();
+
+        assert_eq!(data, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn user_scaling_and_final_statistics_serde_round_trip_non_finite_values() {
+        let scaling = UserScaling {
+            objective: Some(f64::INFINITY),
+            x: Some(vec![f64::NEG_INFINITY]),
+            g: Some(vec![1.0, f64::INFINITY]),
+        };
+        let statistics = FinalStatistics {
+            objective: Some(ScaledValue {
+                scaled: f64::INFINITY,
+                unscaled: f64::NEG_INFINITY,
+            }),
+            total_seconds: Some(f64::INFINITY),
+            ..Default::default()
+        };
+
+        let scaling_json = serde_json::to_string(&scaling).unwrap();
+        let statistics_json = serde_json::to_string(&statistics).unwrap();
+
+        assert_eq!(scaling, serde_json::from_str(&scaling_json).unwrap());
+        assert_eq!(statistics, serde_json::from_str(&statistics_json).unwrap());
+    }
+
+    #[test]
+    fn application_serde_round_trips_non_finite_numeric_options() {
+        let mut application = Application::new();
+        application.set_numeric_option("nlp_upper_bound_inf", f64::INFINITY);
+
+        let json = serde_json::to_string(&application).unwrap();
+
+        assert!(json.contains(r#""nlp_upper_bound_inf":"inf""#));
+        assert_eq!(application, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn initial_solution_serde_reads_integers_and_missing_multipliers() {
+        let initial_solution: InitialSolution =
+            serde_json::from_str(r#"{"x":[1,"-inf"],"z_l":null,"lambda":[0.5]}"#).unwrap();
+
+        assert_eq!(vec![1.0, f64::NEG_INFINITY], initial_solution.x);
+        assert_eq!(None, initial_solution.z_l);
+        assert_eq!(Some(vec![0.5]), initial_solution.lambda);
+    }
+}
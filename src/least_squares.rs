@@ -0,0 +1,533 @@
+//! # Ipopt Bindgen Least Squares
+//!
+//! Defines a trait for nonlinear least-squares problems, e.g. parameter estimation, of the form
+//!
+//! ```text
+//! minimize    ½ ‖r(x)‖²
+//! subject to  g_l ≤ g(x) ≤ g_u
+//!             x_l ≤ x ≤ x_u
+//! ```
+//!
+//! and an adapter that implements `Tnlp` for them from the residuals and their Jacobian.
+
+use crate::{
+    bounds::Bounds,
+    sparsity::{Slot, SparsityPattern, TripletBuilder, dimension_to_i32},
+    tnlp::{InitialSolution, IntermediateData, OptionValue, ProblemSize, Tnlp, UserScaling},
+};
+use std::error::Error;
+
+/// A trait for nonlinear least-squares problems.
+///
+/// The objective is `½ ‖r(x)‖²` with gradient `Jᵀr`, where `J` is the Jacobian of the residuals.
+/// The Hessian of the Lagrangian is the Gauss–Newton approximation `JᵀJ`, unless the second-order
+/// term is provided through `second_order_pattern` and `eval_second_order`, in which case it is
+/// exact. Use `LeastSquaresAdapter` to optimize a `LeastSquaresProblem` with an `Application`.
+///
+/// Methods that are shared with `Tnlp` have the same semantics.
+pub trait LeastSquaresProblem {
+    /// Gets the sparsity pattern of the Jacobian of the residuals, which has one row per residual
+    /// and one column per variable.
+    fn residual_jacobian_pattern(&self) -> SparsityPattern;
+
+    /// Gets the sparsity pattern of the Jacobian of the constraints, which has one row per
+    /// constraint and one column per variable.
+    ///
+    /// The default implementation returns `None`, i.e. there are no constraints.
+    fn constraint_jacobian_pattern(&self) -> Option<SparsityPattern> {
+        None
+    }
+
+    /// Gets the lower triangular sparsity pattern of the second-order term of the Hessian of the
+    /// Lagrangian, as evaluated by `eval_second_order`.
+    ///
+    /// The default implementation returns `None`, so the Gauss–Newton approximation is used.
+    fn second_order_pattern(&self) -> Option<SparsityPattern> {
+        None
+    }
+
+    /// Gets the bounds on the variables and constraints.
+    ///
+    /// The default implementation leaves the variables and constraints unbounded.
+    ///
+    /// # Parameters
+    ///
+    /// - `x_l` - The variable lower bounds.
+    /// - `x_u` - The variable upper bounds.
+    /// - `g_l` - The constraint lower bounds.
+    /// - `g_u` - The constraint upper bounds.
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
+        Bounds::free().fill(x_l, x_u);
+        Bounds::free().fill(g_l, g_u);
+    }
+
+    /// Gets the problem scaling parameters.
+    ///
+    /// The default implementation uses the Ipopt default scaling factors.
+    fn get_scaling(&self) -> UserScaling {
+        UserScaling::default()
+    }
+
+    /// Gets the starting point for the optimization.
+    fn get_starting_point(&self) -> InitialSolution;
+
+    /// Evaluates the residuals.
+    ///
+    /// # Parameters
+    /// - `x` - The current variable values.
+    /// - `residuals` - The residual values.
+    ///
+    /// # Returns
+    /// `true` if the residuals were successfully evaluated, `false` otherwise.
+    fn eval_residuals(&mut self, x: &[f64], residuals: &mut [f64]) -> bool;
+
+    /// Evaluates the Jacobian of the residuals.
+    ///
+    /// # Parameters
+    /// - `x` - The current variable values.
+    /// - `values` - The values of the entries of `residual_jacobian_pattern`, in slot order.
+    ///
+    /// # Returns
+    /// `true` if the Jacobian was successfully evaluated, `false` otherwise.
+    fn eval_residual_jacobian(&mut self, x: &[f64], values: &mut [f64]) -> bool;
+
+    /// Evaluates the constraint functions.
+    ///
+    /// The default implementation has no constraints to evaluate.
+    ///
+    /// # Parameters
+    /// - `x` - The current variable values.
+    /// - `g` - The constraint function values.
+    ///
+    /// # Returns
+    /// `true` if the constraints were successfully evaluated, `false` otherwise.
+    fn eval_g(&mut self, _x: &[f64], _g: &mut [f64]) -> bool {
+        true
+    }
+
+    /// Evaluates the Jacobian of the constraints.
+    ///
+    /// The default implementation has no constraints to evaluate.
+    ///
+    /// # Parameters
+    /// - `x` - The current variable values.
+    /// - `values` - The values of the entries of `constraint_jacobian_pattern`, in slot order.
+    ///
+    /// # Returns
+    /// `true` if the Jacobian was successfully evaluated, `false` otherwise.
+    fn eval_jac_g(&mut self, _x: &[f64], _values: &mut [f64]) -> bool {
+        true
+    }
+
+    /// Evaluates the second-order term of the Hessian of the Lagrangian,
+    /// `Σₖ wₖ ∇²rₖ(x) + Σᵢ λᵢ ∇²gᵢ(x)`.
+    ///
+    /// Only called if `second_order_pattern` returns a pattern. The default implementation fails.
+    ///
+    /// # Parameters
+    /// - `x` - The current variable values.
+    /// - `residual_weights` - The weights `w` of the residual Hessians, which are the residuals
+    ///   scaled by the objective factor.
+    /// - `lambda` - The constraint multipliers.
+    /// - `values` - The values of the entries of `second_order_pattern`, in slot order. They are
+    ///   zeroed before each call.
+    ///
+    /// # Returns
+    /// `true` if the second-order term was successfully evaluated, `false` otherwise.
+    fn eval_second_order(
+        &mut self,
+        _x: &[f64],
+        _residual_weights: &[f64],
+        _lambda: &[f64],
+        _values: &mut [f64],
+    ) -> bool {
+        false
+    }
+
+    /// User code that runs at each iteration.
+    ///
+    /// # Parameters
+    /// - `data` - The intermediate data at the current iteration.
+    ///
+    /// Return `false` to terminate the optimization.
+    fn intermediate(&mut self, _data: IntermediateData) -> bool {
+        true
+    }
+
+    /// Gets the names of the variables, which are used in diagnostics and reports.
+    fn variable_names(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Gets the names of the constraints, which are used in diagnostics and reports.
+    fn constraint_names(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Gets the Ipopt options that suit the structure of the problem.
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        Vec::new()
+    }
+}
+
+/// Adapts a `LeastSquaresProblem` into a `Tnlp`.
+///
+/// The sparsity pattern of the Hessian is the lower triangle of the pattern of `JᵀJ`, merged with
+/// the second-order pattern if there is one, and is computed once when the adapter is created.
+/// With the Gauss–Newton approximation, the curvature of the constraints is ignored, which is
+/// exact for linear constraints.
+#[derive(Debug, Clone)]
+pub struct LeastSquaresAdapter<P> {
+    problem: P,
+    residual_jacobian: SparsityPattern,
+    constraint_jacobian: Option<SparsityPattern>,
+    hessian: SparsityPattern,
+    products: Vec<(usize, usize, Slot)>,
+    second_order_slots: Option<Vec<Slot>>,
+    residuals: Vec<f64>,
+    jacobian_values: Vec<f64>,
+    second_order_values: Vec<f64>,
+}
+
+impl<P: LeastSquaresProblem> LeastSquaresAdapter<P> {
+    /// Creates a new `LeastSquaresAdapter`.
+    ///
+    /// # Parameters
+    ///
+    /// - `problem` - The least-squares problem to adapt.
+    ///
+    /// # Errors
+    ///
+    /// - The residual Jacobian pattern is lower triangular.
+    /// - The constraint Jacobian pattern has a different number of columns to the residual
+    ///   Jacobian pattern.
+    /// - The second-order pattern is not a lower triangular `n x n` pattern.
+    /// - The Hessian pattern has more entries than are representable as an `i32`.
+    pub fn new(problem: P) -> Result<Self, Box<dyn Error>> {
+        let residual_jacobian = problem.residual_jacobian_pattern();
+        let constraint_jacobian = problem.constraint_jacobian_pattern();
+        let second_order = problem.second_order_pattern();
+        let n = residual_jacobian.columns();
+
+        if residual_jacobian.is_lower_triangular() {
+            return Err("The residual Jacobian pattern must not be lower triangular.".into());
+        }
+        if let Some(pattern) = &constraint_jacobian
+            && (pattern.columns() != n || pattern.is_lower_triangular())
+        {
+            return Err(format!(
+                "The constraint Jacobian pattern must be a general pattern with {n} columns."
+            )
+            .into());
+        }
+        if let Some(pattern) = &second_order
+            && (pattern.rows() != n || !pattern.is_lower_triangular())
+        {
+            return Err(format!(
+                "The second-order pattern must be a lower triangular {n} x {n} pattern."
+            )
+            .into());
+        }
+
+        let mut rows = vec![Vec::new(); residual_jacobian.rows()];
+        for (index, &(row, column)) in residual_jacobian.entries().iter().enumerate() {
+            rows[row].push((column, index));
+        }
+
+        let mut builder = TripletBuilder::lower_triangular(n);
+        let mut products = Vec::new();
+        for row in &rows {
+            for (i, &(column_i, index_i)) in row.iter().enumerate() {
+                for &(column_k, index_k) in &row[..=i] {
+                    let slot = builder.entry(column_i.max(column_k), column_i.min(column_k));
+                    products.push((index_i, index_k, slot));
+                }
+            }
+        }
+        let second_order_slots = second_order.as_ref().map(|pattern| {
+            pattern
+                .entries()
+                .iter()
+                .map(|&(row, column)| builder.entry(row, column))
+                .collect::<Vec<_>>()
+        });
+
+        Ok(LeastSquaresAdapter {
+            residuals: vec![0.0; residual_jacobian.rows()],
+            jacobian_values: vec![0.0; residual_jacobian.nnz()],
+            second_order_values: vec![0.0; second_order.map_or(0, |pattern| pattern.nnz())],
+            problem,
+            residual_jacobian,
+            constraint_jacobian,
+            hessian: builder.build()?,
+            products,
+            second_order_slots,
+        })
+    }
+
+    /// Gets the least-squares problem.
+    #[must_use]
+    pub fn problem(&self) -> &P {
+        &self.problem
+    }
+
+    /// Gets the least-squares problem mutably.
+    #[must_use]
+    pub fn problem_mut(&mut self) -> &mut P {
+        &mut self.problem
+    }
+
+    /// Unwraps the least-squares problem.
+    #[must_use]
+    pub fn into_inner(self) -> P {
+        self.problem
+    }
+
+    /// Gets the sparsity pattern of the Hessian of the Lagrangian.
+    #[must_use]
+    pub fn hessian_pattern(&self) -> &SparsityPattern {
+        &self.hessian
+    }
+
+    /// Gets the number of constraints.
+    fn number_of_constraints(&self) -> usize {
+        self.constraint_jacobian
+            .as_ref()
+            .map_or(0, SparsityPattern::rows)
+    }
+}
+
+impl<P: LeastSquaresProblem> Tnlp for LeastSquaresAdapter<P> {
+    fn get_nlp_info(&self) -> ProblemSize {
+        ProblemSize {
+            n: dimension_to_i32(self.residual_jacobian.columns()),
+            m: dimension_to_i32(self.number_of_constraints()),
+            nnz_jac: dimension_to_i32(
+                self.constraint_jacobian
+                    .as_ref()
+                    .map_or(0, SparsityPattern::nnz),
+            ),
+            nnz_hess: dimension_to_i32(self.hessian.nnz()),
+        }
+    }
+
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
+        self.problem.get_bounds_info(x_l, x_u, g_l, g_u);
+    }
+
+    fn get_scaling(&self) -> UserScaling {
+        self.problem.get_scaling()
+    }
+
+    fn get_starting_point(&self) -> InitialSolution {
+        self.problem.get_starting_point()
+    }
+
+    fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+        if !self.problem.eval_residuals(x, &mut self.residuals) {
+            return false;
+        }
+
+        *obj_value = 0.5 * self.residuals.iter().map(|r| r * r).sum::<f64>();
+        true
+    }
+
+    fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+        if !self.problem.eval_residuals(x, &mut self.residuals)
+            || !self
+                .problem
+                .eval_residual_jacobian(x, &mut self.jacobian_values)
+        {
+            return false;
+        }
+
+        grad_f.fill(0.0);
+        let entries = self.residual_jacobian.entries();
+        for (&(row, column), value) in entries.iter().zip(&self.jacobian_values) {
+            grad_f[column] += value * self.residuals[row];
+        }
+        true
+    }
+
+    fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+        self.problem.eval_g(x, g)
+    }
+
+    fn get_jacobian_sparsity(&mut self, _n: i32, _m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        if let Some(pattern) = &self.constraint_jacobian {
+            pattern.write_indices(i_row, j_col);
+        }
+    }
+
+    fn eval_jac_g(&mut self, x: &[f64], _m: i32, values: &mut [f64]) -> bool {
+        self.problem.eval_jac_g(x, values)
+    }
+
+    fn get_hessian_sparsity(&mut self, _n: i32, _m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.hessian.write_indices(i_row, j_col);
+    }
+
+    fn eval_h(
+        &mut self,
+        x: &[f64],
+        obj_factor: f64,
+        lambda: &[f64],
+        _m: i32,
+        values: &mut [f64],
+    ) -> bool {
+        if !self
+            .problem
+            .eval_residual_jacobian(x, &mut self.jacobian_values)
+        {
+            return false;
+        }
+
+        let mut hessian = self.hessian.values(values);
+        hessian.clear();
+        for &(i, k, slot) in &self.products {
+            hessian.add(
+                slot,
+                obj_factor * self.jacobian_values[i] * self.jacobian_values[k],
+            );
+        }
+
+        if let Some(slots) = &self.second_order_slots {
+            if !self.problem.eval_residuals(x, &mut self.residuals) {
+                return false;
+            }
+            let weights: Vec<f64> = self.residuals.iter().map(|r| obj_factor * r).collect();
+            self.second_order_values.fill(0.0);
+            if !self
+                .problem
+                .eval_second_order(x, &weights, lambda, &mut self.second_order_values)
+            {
+                return false;
+            }
+            for (&slot, &value) in slots.iter().zip(&self.second_order_values) {
+                hessian.add(slot, value);
+            }
+        }
+        true
+    }
+
+    fn intermediate(&mut self, data: IntermediateData) -> bool {
+        self.problem.intermediate(data)
+    }
+
+    fn variable_names(&self) -> Option<Vec<String>> {
+        self.problem.variable_names()
+    }
+
+    fn constraint_names(&self) -> Option<Vec<String>> {
+        self.problem.constraint_names()
+    }
+
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        self.problem.default_options()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fits y = a * exp(b * t) to the points (0, 2) and (1, 4), i.e. the residuals are
+    /// `a * exp(b * t) - y`.
+    struct ExponentialFit {
+        exact: bool,
+    }
+
+    const POINTS: [(f64, f64); 2] = [(0.0, 2.0), (1.0, 4.0)];
+
+    impl LeastSquaresProblem for ExponentialFit {
+        fn residual_jacobian_pattern(&self) -> SparsityPattern {
+            let mut builder = TripletBuilder::new(2, 2);
+            for row in 0..2 {
+                builder.entry(row, 0);
+                builder.entry(row, 1);
+            }
+            builder.build().unwrap()
+        }
+
+        fn second_order_pattern(&self) -> Option<SparsityPattern> {
+            self.exact.then(|| {
+                let mut builder = TripletBuilder::lower_triangular(2);
+                builder.entry(1, 0);
+                builder.entry(1, 1);
+                builder.build().unwrap()
+            })
+        }
+
+        fn get_starting_point(&self) -> InitialSolution {
+            InitialSolution::from_variables(vec![1.0, 0.0])
+        }
+
+        fn eval_residuals(&mut self, x: &[f64], residuals: &mut [f64]) -> bool {
+            for (residual, (t, y)) in residuals.iter_mut().zip(POINTS) {
+                *residual = x[0] * (x[1] * t).exp() - y;
+            }
+            true
+        }
+
+        fn eval_residual_jacobian(&mut self, x: &[f64], values: &mut [f64]) -> bool {
+            for (row, (t, _)) in POINTS.into_iter().enumerate() {
+                let exp = (x[1] * t).exp();
+                values[2 * row] = exp;
+                values[2 * row + 1] = x[0] * t * exp;
+            }
+            true
+        }
+
+        fn eval_second_order(
+            &mut self,
+            x: &[f64],
+            residual_weights: &[f64],
+            _lambda: &[f64],
+            values: &mut [f64],
+        ) -> bool {
+            for (weight, (t, _)) in residual_weights.iter().zip(POINTS) {
+                let exp = (x[1] * t).exp();
+                values[0] += weight * t * exp;
+                values[1] += weight * x[0] * t * t * exp;
+            }
+            true
+        }
+    }
+
+    #[test]
+    fn least_squares_adapter_evaluates_objective_and_gradient() {
+        let mut adapter = LeastSquaresAdapter::new(ExponentialFit { exact: false }).unwrap();
+        let (mut f, mut grad_f) = (0.0, vec![0.0; 2]);
+
+        assert!(adapter.eval_f(&[1.0, 0.0], &mut f));
+        assert!(adapter.eval_grad_f(&[1.0, 0.0], &mut grad_f));
+
+        // r = (-1, -3), J = [[1, 0], [1, 1]].
+        assert_eq!(vec![5.0, -4.0, -3.0], [vec![f], grad_f].concat());
+    }
+
+    #[test]
+    fn least_squares_adapter_gauss_newton_hessian_is_jtj() {
+        let mut adapter = LeastSquaresAdapter::new(ExponentialFit { exact: false }).unwrap();
+        let (mut i_row, mut j_col, mut values) = ([0; 3], [0; 3], vec![0.0; 3]);
+
+        adapter.get_hessian_sparsity(2, 0, &mut i_row, &mut j_col);
+        assert!(adapter.eval_h(&[1.0, 0.0], 2.0, &[], 0, &mut values));
+
+        assert_eq!([0, 1, 1], i_row);
+        assert_eq!([0, 0, 1], j_col);
+        assert_eq!(vec![4.0, 2.0, 2.0], values);
+    }
+
+    #[test]
+    fn least_squares_adapter_exact_hessian_adds_second_order_term() {
+        let mut adapter = LeastSquaresAdapter::new(ExponentialFit { exact: true }).unwrap();
+        let mut values = vec![0.0; 3];
+
+        assert!(adapter.eval_h(&[1.0, 0.0], 1.0, &[], 0, &mut values));
+
+        // Only the residual at t = 1 (r = -3) has second derivatives, [[0, 1], [1, 1]].
+        assert_eq!(3, adapter.hessian_pattern().nnz());
+        assert_eq!(vec![2.0, -2.0, -2.0], values);
+    }
+}
@@ -9,12 +9,60 @@
 //! The `build.rs` script will generate the bindings based on the system's installed
 //! version of Ipopt.
 
+#[cfg(feature = "rust-interface")]
+pub mod active_set;
+#[cfg(feature = "rust-interface")]
+pub mod ampl;
+#[cfg(feature = "rust-interface")]
+pub mod analysis;
 #[cfg(feature = "rust-interface")]
 pub mod application;
+#[cfg(feature = "async")]
+pub mod async_solve;
+#[cfg(feature = "rust-interface")]
+pub mod benchmark;
+#[cfg(feature = "rust-interface")]
+pub mod bounds;
 pub mod c_interface;
 #[cfg(feature = "rust-interface")]
+pub mod cancellation;
+#[cfg(feature = "rust-interface")]
+pub mod dense;
+#[cfg(feature = "serde")]
+mod float_serde;
+#[cfg(feature = "rust-interface")]
+pub mod least_squares;
+#[cfg(feature = "rust-interface")]
+pub mod linear;
+#[cfg(feature = "rust-interface")]
+pub mod modeling;
+#[cfg(feature = "rust-interface")]
+pub mod mps;
+#[cfg(feature = "rust-interface")]
+pub mod multi_start;
+#[cfg(feature = "nalgebra")]
+pub mod nalgebra_interop;
+#[cfg(feature = "ndarray")]
+pub mod ndarray_interop;
+#[cfg(feature = "rust-interface")]
+pub mod observer;
+#[cfg(feature = "rust-interface")]
+pub mod output;
+#[cfg(feature = "rust-interface")]
+pub mod quadratic;
+#[cfg(feature = "rust-interface")]
+pub mod recording;
+#[cfg(feature = "rust-interface")]
+pub mod report;
+#[cfg(feature = "rust-interface")]
 pub mod results;
 #[cfg(feature = "rust-interface")]
+pub mod sparsity;
+#[cfg(feature = "rust-interface")]
+pub mod statistics;
+#[cfg(feature = "test_problems")]
+pub mod test_problems;
+#[cfg(feature = "rust-interface")]
 pub mod tnlp;
 
 // Export everything into the root of the crate (seeing as there aren't many public symbols in this
@@ -22,9 +70,55 @@ pub mod tnlp;
 // generated and ideally consumers will not use them directly anyway, save for creating their own
 // higher-level interface.
 
+#[cfg(feature = "rust-interface")]
+pub use active_set::*;
+#[cfg(feature = "rust-interface")]
+pub use ampl::*;
+#[cfg(feature = "rust-interface")]
+pub use analysis::*;
 #[cfg(feature = "rust-interface")]
 pub use application::*;
+#[cfg(feature = "async")]
+pub use async_solve::*;
+#[cfg(feature = "rust-interface")]
+pub use benchmark::*;
+#[cfg(feature = "rust-interface")]
+pub use bounds::*;
+#[cfg(feature = "rust-interface")]
+pub use cancellation::*;
+#[cfg(feature = "rust-interface")]
+pub use dense::*;
+#[cfg(feature = "rust-interface")]
+pub use least_squares::*;
+#[cfg(feature = "rust-interface")]
+pub use linear::*;
+#[cfg(feature = "rust-interface")]
+pub use modeling::*;
+#[cfg(feature = "rust-interface")]
+pub use mps::*;
+#[cfg(feature = "rust-interface")]
+pub use multi_start::*;
+#[cfg(feature = "nalgebra")]
+pub use nalgebra_interop::*;
+#[cfg(feature = "ndarray")]
+pub use ndarray_interop::*;
+#[cfg(feature = "rust-interface")]
+pub use observer::*;
+#[cfg(feature = "rust-interface")]
+pub use output::OutputDestination;
+#[cfg(feature = "rust-interface")]
+pub use quadratic::*;
+#[cfg(feature = "rust-interface")]
+pub use recording::*;
+#[cfg(feature = "rust-interface")]
+pub use report::*;
 #[cfg(feature = "rust-interface")]
 pub use results::*;
 #[cfg(feature = "rust-interface")]
+pub use sparsity::*;
+#[cfg(feature = "rust-interface")]
+pub use statistics::*;
+#[cfg(feature = "test_problems")]
+pub use test_problems::*;
+#[cfg(feature = "rust-interface")]
 pub use tnlp::*;
@@ -0,0 +1,274 @@
+//! # Ipopt Bindgen Linear
+//!
+//! Defines a linear programming (LP) front-end, which implements `Tnlp` for problems of the form
+//!
+//! ```text
+//! minimize    cᵀx
+//! subject to  g_l ≤ Ax ≤ g_u
+//!             x_l ≤ x ≤ x_u
+//! ```
+//!
+//! so that Ipopt can be used as an interior-point LP solver without writing any callbacks.
+
+use crate::{
+    bounds::Bounds,
+    quadratic::QuadraticProblem,
+    tnlp::{InitialSolution, OptionValue, ProblemNames, ProblemSize, Tnlp},
+};
+use std::error::Error;
+
+/// A linear program.
+///
+/// The Hessian of the Lagrangian is empty and the Jacobian of the constraints is constant, and the
+/// corresponding Ipopt options are set through `Tnlp::default_options`.
+///
+/// Variables are free, constraints are unbounded and the starting point is zero until set
+/// otherwise.
+///
+/// # Example
+///
+/// ```
+/// use ipopt_bindgen::{Bounds, LinearProblem};
+///
+/// // minimize -x0 - x1 subject to x0 + 2 x1 <= 4, x >= 0.
+/// let mut problem = LinearProblem::new(2, 1);
+/// problem
+///     .set_objective(vec![-1.0, -1.0])
+///     .unwrap()
+///     .set_constraints(&[(0, 0, 1.0), (0, 1, 2.0)])
+///     .unwrap()
+///     .set_constraint_bounds(vec![Bounds::upper(4.0)])
+///     .unwrap()
+///     .set_variable_bounds(vec![Bounds::lower(0.0); 2])
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearProblem(QuadraticProblem);
+
+impl LinearProblem {
+    /// Creates a new `LinearProblem` with a zero objective and zero constraint matrix.
+    ///
+    /// # Parameters
+    ///
+    /// - `n` - The number of variables.
+    /// - `m` - The number of linear constraints, i.e. the number of rows of `A`.
+    ///
+    /// # Panics
+    ///
+    /// `n` or `m` is not representable as an `i32`.
+    #[must_use]
+    pub fn new(n: usize, m: usize) -> Self {
+        LinearProblem(QuadraticProblem::new(n, m))
+    }
+
+    /// Gets the number of variables.
+    #[must_use]
+    pub fn number_of_variables(&self) -> usize {
+        self.0.number_of_variables()
+    }
+
+    /// Gets the number of linear constraints.
+    #[must_use]
+    pub fn number_of_constraints(&self) -> usize {
+        self.0.number_of_constraints()
+    }
+
+    /// Sets the objective `cᵀx`.
+    ///
+    /// # Parameters
+    ///
+    /// - `c` - The objective coefficients.
+    ///
+    /// # Errors
+    ///
+    /// The length of `c` is not `n`.
+    pub fn set_objective(&mut self, c: Vec<f64>) -> Result<&mut Self, Box<dyn Error>> {
+        self.0.set_objective(&[], c)?;
+        Ok(self)
+    }
+
+    /// Sets the constraint matrix `A`.
+    ///
+    /// # Parameters
+    ///
+    /// - `a` - The `(row, column, value)` triplets of the `m x n` constraint matrix. Duplicate
+    ///   triplets are summed.
+    ///
+    /// # Errors
+    ///
+    /// A triplet is outside of the `m x n` matrix.
+    pub fn set_constraints(
+        &mut self,
+        a: &[(usize, usize, f64)],
+    ) -> Result<&mut Self, Box<dyn Error>> {
+        self.0.set_constraints(a)?;
+        Ok(self)
+    }
+
+    /// Sets the bounds of the variables.
+    ///
+    /// # Parameters
+    ///
+    /// - `bounds` - The bounds of each variable.
+    ///
+    /// # Errors
+    ///
+    /// The number of bounds is not `n`.
+    pub fn set_variable_bounds(
+        &mut self,
+        bounds: Vec<Bounds>,
+    ) -> Result<&mut Self, Box<dyn Error>> {
+        self.0.set_variable_bounds(bounds)?;
+        Ok(self)
+    }
+
+    /// Sets the bounds of the linear constraints.
+    ///
+    /// # Parameters
+    ///
+    /// - `bounds` - The bounds of each constraint.
+    ///
+    /// # Errors
+    ///
+    /// The number of bounds is not `m`.
+    pub fn set_constraint_bounds(
+        &mut self,
+        bounds: Vec<Bounds>,
+    ) -> Result<&mut Self, Box<dyn Error>> {
+        self.0.set_constraint_bounds(bounds)?;
+        Ok(self)
+    }
+
+    /// Sets the starting point of the optimization.
+    ///
+    /// # Parameters
+    ///
+    /// - `x` - The initial values of the variables.
+    ///
+    /// # Errors
+    ///
+    /// The length of `x` is not `n`.
+    pub fn set_starting_point(&mut self, x: Vec<f64>) -> Result<&mut Self, Box<dyn Error>> {
+        self.0.set_starting_point(x)?;
+        Ok(self)
+    }
+
+    /// Sets the names of the variables and constraints.
+    ///
+    /// # Parameters
+    ///
+    /// - `names` - The variable and constraint names.
+    pub fn set_names(&mut self, names: ProblemNames) -> &mut Self {
+        self.0.set_names(names);
+        self
+    }
+}
+
+impl Tnlp for LinearProblem {
+    fn get_nlp_info(&self) -> ProblemSize {
+        self.0.get_nlp_info()
+    }
+
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
+        self.0.get_bounds_info(x_l, x_u, g_l, g_u);
+    }
+
+    fn get_starting_point(&self) -> InitialSolution {
+        self.0.get_starting_point()
+    }
+
+    fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+        self.0.eval_f(x, obj_value)
+    }
+
+    fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+        self.0.eval_grad_f(x, grad_f)
+    }
+
+    fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+        self.0.eval_g(x, g)
+    }
+
+    fn get_jacobian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.0.get_jacobian_sparsity(n, m, i_row, j_col);
+    }
+
+    fn eval_jac_g(&mut self, x: &[f64], m: i32, values: &mut [f64]) -> bool {
+        self.0.eval_jac_g(x, m, values)
+    }
+
+    fn get_hessian_sparsity(&mut self, _n: i32, _m: i32, _i_row: &mut [i32], _j_col: &mut [i32]) {}
+
+    fn eval_h(
+        &mut self,
+        _x: &[f64],
+        _obj_factor: f64,
+        _lambda: &[f64],
+        _m: i32,
+        _values: &mut [f64],
+    ) -> bool {
+        true
+    }
+
+    fn variable_names(&self) -> Option<Vec<String>> {
+        self.0.variable_names()
+    }
+
+    fn constraint_names(&self) -> Option<Vec<String>> {
+        self.0.constraint_names()
+    }
+
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        ["hessian_constant", "jac_c_constant", "jac_d_constant"]
+            .into_iter()
+            .map(|option| (option.to_string(), OptionValue::String("yes".to_string())))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_problem_has_empty_hessian_and_constant_jacobian() {
+        let mut problem = LinearProblem::new(2, 1);
+        problem
+            .set_objective(vec![-1.0, -1.0])
+            .unwrap()
+            .set_constraints(&[(0, 0, 1.0), (0, 1, 2.0)])
+            .unwrap();
+        let (mut f, mut grad_f, mut jac_g) = (0.0, vec![0.0; 2], vec![0.0; 2]);
+
+        assert!(problem.eval_f(&[1.0, 2.0], &mut f));
+        assert!(problem.eval_grad_f(&[1.0, 2.0], &mut grad_f));
+        assert!(problem.eval_jac_g(&[1.0, 2.0], 1, &mut jac_g));
+
+        assert_eq!(
+            ProblemSize {
+                n: 2,
+                m: 1,
+                nnz_jac: 2,
+                nnz_hess: 0,
+            },
+            problem.get_nlp_info()
+        );
+        assert_eq!(
+            vec![-3.0, -1.0, -1.0, 1.0, 2.0],
+            [vec![f], grad_f, jac_g].concat()
+        );
+    }
+
+    #[test]
+    fn linear_problem_default_options_mark_derivatives_constant() {
+        let problem = LinearProblem::new(1, 0);
+
+        assert!(
+            problem
+                .default_options()
+                .iter()
+                .all(|(_, value)| *value == OptionValue::String("yes".to_string()))
+        );
+        assert_eq!(3, problem.default_options().len());
+    }
+}
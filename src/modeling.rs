@@ -0,0 +1,1205 @@
+//! # Ipopt Bindgen Modeling
+//!
+//! Defines a small algebraic modeling layer, for writing problems as expressions of named
+//! variables instead of implementing `Tnlp` by hand.
+//!
+//! A `Model` is compiled into a `CompiledModel`, which implements `Tnlp`. Compilation
+//! differentiates the expressions symbolically, so the gradient, the sparse Jacobian and the exact
+//! Hessian of the Lagrangian, including their sparsity patterns, are derived from the expressions.
+//!
+//! # Example
+//!
+//! ```
+//! use ipopt_bindgen::Model;
+//!
+//! // minimize (1 - x)² + 100 (y - x²)² subject to x² + y² <= 2.
+//! let mut model = Model::new();
+//! let x = model.add_var("x", -10.0, 10.0, -1.2);
+//! let y = model.add_var("y", -10.0, 10.0, 1.0);
+//!
+//! model.minimize((1.0 - x).powi(2) + 100.0 * (y - x.powi(2)).powi(2));
+//! model.add_constraint(x.powi(2) + y.powi(2), f64::NEG_INFINITY, 2.0);
+//!
+//! let problem = model.compile().unwrap();
+//! ```
+
+use crate::{
+    sparsity::{Slot, SparsityPattern, TripletBuilder, dimension_to_i32},
+    tnlp::{InitialSolution, ProblemSize, Tnlp},
+};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    error::Error,
+    ops::{Add, Div, Mul, Neg, Sub},
+    sync::{Arc, LazyLock},
+};
+
+/// A variable of a `Model`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Var(usize);
+
+impl Var {
+    /// Creates the variable at `index` of a `Model`, for readers that build the expressions before
+    /// the variables are added.
+    pub(crate) fn from_index(index: usize) -> Self {
+        Var(index)
+    }
+
+    /// Gets the index of the variable, i.e. its position in `Solution::x`.
+    #[must_use]
+    pub fn index(self) -> usize {
+        self.0
+    }
+
+    /// Raises the variable to an integer power.
+    #[must_use]
+    pub fn powi(self, n: i32) -> Expr {
+        Expr::from(self).powi(n)
+    }
+
+    /// Takes the exponential of the variable.
+    #[must_use]
+    pub fn exp(self) -> Expr {
+        Expr::from(self).exp()
+    }
+
+    /// Takes the natural logarithm of the variable.
+    #[must_use]
+    pub fn ln(self) -> Expr {
+        Expr::from(self).ln()
+    }
+
+    /// Takes the sine of the variable.
+    #[must_use]
+    pub fn sin(self) -> Expr {
+        Expr::from(self).sin()
+    }
+
+    /// Takes the cosine of the variable.
+    #[must_use]
+    pub fn cos(self) -> Expr {
+        Expr::from(self).cos()
+    }
+
+    /// Takes the square root of the variable.
+    #[must_use]
+    pub fn sqrt(self) -> Expr {
+        Expr::from(self).sqrt()
+    }
+
+    /// Takes the arcsine of the variable.
+    #[must_use]
+    pub fn asin(self) -> Expr {
+        Expr::from(self).asin()
+    }
+
+    /// Takes the arccosine of the variable.
+    #[must_use]
+    pub fn acos(self) -> Expr {
+        Expr::from(self).acos()
+    }
+
+    /// Takes the arctangent of the variable.
+    #[must_use]
+    pub fn atan(self) -> Expr {
+        Expr::from(self).atan()
+    }
+}
+
+/// A node of an expression.
+#[derive(Debug)]
+enum Node {
+    Constant(f64),
+    Variable(usize),
+    Add(Expr, Expr),
+    Sub(Expr, Expr),
+    Mul(Expr, Expr),
+    Div(Expr, Expr),
+    Neg(Expr),
+    Powi(Expr, i32),
+    Exp(Expr),
+    Ln(Expr),
+    Sin(Expr),
+    Cos(Expr),
+    Sqrt(Expr),
+    Asin(Expr),
+    Acos(Expr),
+    Atan(Expr),
+}
+
+impl Node {
+    /// Gets the operands of the node.
+    fn operands(&self) -> impl DoubleEndedIterator<Item = &Expr> {
+        let (a, b) = match self {
+            Node::Constant(_) | Node::Variable(_) => (None, None),
+            Node::Add(a, b) | Node::Sub(a, b) | Node::Mul(a, b) | Node::Div(a, b) => {
+                (Some(a), Some(b))
+            }
+            Node::Neg(a)
+            | Node::Powi(a, _)
+            | Node::Exp(a)
+            | Node::Ln(a)
+            | Node::Sin(a)
+            | Node::Cos(a)
+            | Node::Sqrt(a)
+            | Node::Asin(a)
+            | Node::Acos(a)
+            | Node::Atan(a) => (Some(a), None),
+        };
+        a.into_iter().chain(b)
+    }
+
+    /// Moves the operands of the node onto a stack, leaving placeholders in their place.
+    fn take_operands(&mut self, stack: &mut Vec<Expr>) {
+        let mut take = |expr: &mut Expr| stack.push(std::mem::replace(expr, PLACEHOLDER.clone()));
+        match self {
+            Node::Constant(_) | Node::Variable(_) => {}
+            Node::Add(a, b) | Node::Sub(a, b) | Node::Mul(a, b) | Node::Div(a, b) => {
+                take(a);
+                take(b);
+            }
+            Node::Neg(a)
+            | Node::Powi(a, _)
+            | Node::Exp(a)
+            | Node::Ln(a)
+            | Node::Sin(a)
+            | Node::Cos(a)
+            | Node::Sqrt(a)
+            | Node::Asin(a)
+            | Node::Acos(a)
+            | Node::Atan(a) => take(a),
+        }
+    }
+}
+
+impl Drop for Node {
+    /// Drops the operands that are only owned by this node from an explicit stack, since dropping
+    /// a deep expression recursively would overflow the call stack.
+    fn drop(&mut self) {
+        let mut stack = Vec::new();
+        self.take_operands(&mut stack);
+        while let Some(expr) = stack.pop() {
+            if let Some(mut node) = Arc::into_inner(expr.0) {
+                node.take_operands(&mut stack);
+            }
+        }
+    }
+}
+
+/// The operand left in a node whose operands have been taken while it is dropped.
+static PLACEHOLDER: LazyLock<Expr> = LazyLock::new(|| Expr::constant(0.0));
+
+/// An expression of the variables of a `Model`.
+///
+/// Expressions are built from variables and constants with the `+`, `-`, `*` and `/` operators
+/// and the `powi`, `exp`, `ln`, `sin`, `cos`, `sqrt`, `asin`, `acos` and `atan` methods. Cloning an
+/// expression is cheap, and clones share their nodes, so reusing an expression makes a DAG rather
+/// than a copy, and the shared part is only evaluated once.
+///
+/// Operations on constants are folded, and additions of zero and multiplications by zero or one
+/// are simplified away, so that the derivatives of linear terms are constant.
+#[derive(Debug, Clone)]
+pub struct Expr(Arc<Node>);
+
+impl Expr {
+    /// Creates a constant expression.
+    ///
+    /// # Parameters
+    ///
+    /// - `value` - The value of the constant.
+    #[must_use]
+    pub fn constant(value: f64) -> Self {
+        Expr(Arc::new(Node::Constant(value)))
+    }
+
+    /// Creates the sum of many terms, as a balanced tree so that long sums do not make deep
+    /// expressions.
+    ///
+    /// # Parameters
+    ///
+    /// - `terms` - The terms to sum.
+    #[must_use]
+    pub fn sum<T: Into<Expr>>(terms: impl IntoIterator<Item = T>) -> Self {
+        let mut terms: Vec<Expr> = terms.into_iter().map(Into::into).collect();
+        while terms.len() > 1 {
+            let mut pairs = Vec::with_capacity(terms.len().div_ceil(2));
+            let mut iter = terms.into_iter();
+            while let Some(first) = iter.next() {
+                pairs.push(match iter.next() {
+                    Some(second) => first + second,
+                    None => first,
+                });
+            }
+            terms = pairs;
+        }
+        terms.pop().unwrap_or_else(|| Expr::constant(0.0))
+    }
+
+    /// Raises the expression to an integer power.
+    #[must_use]
+    pub fn powi(&self, n: i32) -> Expr {
+        match (n, self.as_constant()) {
+            (0, _) => Expr::constant(1.0),
+            (1, _) => self.clone(),
+            (_, Some(value)) => Expr::constant(value.powi(n)),
+            _ => Expr::new(Node::Powi(self.clone(), n)),
+        }
+    }
+
+    /// Takes the exponential of the expression.
+    #[must_use]
+    pub fn exp(&self) -> Expr {
+        self.unary(f64::exp, Node::Exp)
+    }
+
+    /// Takes the natural logarithm of the expression.
+    #[must_use]
+    pub fn ln(&self) -> Expr {
+        self.unary(f64::ln, Node::Ln)
+    }
+
+    /// Takes the sine of the expression.
+    #[must_use]
+    pub fn sin(&self) -> Expr {
+        self.unary(f64::sin, Node::Sin)
+    }
+
+    /// Takes the cosine of the expression.
+    #[must_use]
+    pub fn cos(&self) -> Expr {
+        self.unary(f64::cos, Node::Cos)
+    }
+
+    /// Takes the square root of the expression.
+    #[must_use]
+    pub fn sqrt(&self) -> Expr {
+        self.unary(f64::sqrt, Node::Sqrt)
+    }
+
+    /// Takes the arcsine of the expression.
+    #[must_use]
+    pub fn asin(&self) -> Expr {
+        self.unary(f64::asin, Node::Asin)
+    }
+
+    /// Takes the arccosine of the expression.
+    #[must_use]
+    pub fn acos(&self) -> Expr {
+        self.unary(f64::acos, Node::Acos)
+    }
+
+    /// Takes the arctangent of the expression.
+    #[must_use]
+    pub fn atan(&self) -> Expr {
+        self.unary(f64::atan, Node::Atan)
+    }
+
+    fn new(node: Node) -> Self {
+        Expr(Arc::new(node))
+    }
+
+    /// Gets the address of the node, which identifies shared nodes.
+    fn key(&self) -> *const Node {
+        Arc::as_ptr(&self.0)
+    }
+
+    /// Gets the value of the expression if it is a constant.
+    pub(crate) fn as_constant(&self) -> Option<f64> {
+        match *self.0 {
+            Node::Constant(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn is_constant(&self, value: f64) -> bool {
+        self.as_constant() == Some(value)
+    }
+
+    fn unary(&self, fold: fn(f64) -> f64, node: fn(Expr) -> Node) -> Expr {
+        match self.as_constant() {
+            Some(value) => Expr::constant(fold(value)),
+            None => Expr::new(node(self.clone())),
+        }
+    }
+
+    fn add(a: Expr, b: Expr) -> Expr {
+        match (a.as_constant(), b.as_constant()) {
+            (Some(a), Some(b)) => Expr::constant(a + b),
+            _ if a.is_constant(0.0) => b,
+            _ if b.is_constant(0.0) => a,
+            _ => Expr::new(Node::Add(a, b)),
+        }
+    }
+
+    fn sub(a: Expr, b: Expr) -> Expr {
+        match (a.as_constant(), b.as_constant()) {
+            (Some(a), Some(b)) => Expr::constant(a - b),
+            _ if b.is_constant(0.0) => a,
+            _ if a.is_constant(0.0) => -b,
+            _ => Expr::new(Node::Sub(a, b)),
+        }
+    }
+
+    fn mul(a: Expr, b: Expr) -> Expr {
+        match (a.as_constant(), b.as_constant()) {
+            (Some(a), Some(b)) => Expr::constant(a * b),
+            _ if a.is_constant(0.0) || b.is_constant(0.0) => Expr::constant(0.0),
+            _ if a.is_constant(1.0) => b,
+            _ if b.is_constant(1.0) => a,
+            _ => Expr::new(Node::Mul(a, b)),
+        }
+    }
+
+    fn div(a: Expr, b: Expr) -> Expr {
+        match (a.as_constant(), b.as_constant()) {
+            (Some(a), Some(b)) => Expr::constant(a / b),
+            _ if a.is_constant(0.0) => Expr::constant(0.0),
+            _ if b.is_constant(1.0) => a,
+            _ => Expr::new(Node::Div(a, b)),
+        }
+    }
+}
+
+impl From<f64> for Expr {
+    fn from(value: f64) -> Self {
+        Expr::constant(value)
+    }
+}
+
+impl From<Var> for Expr {
+    fn from(variable: Var) -> Self {
+        Expr::new(Node::Variable(variable.0))
+    }
+}
+
+impl From<&Expr> for Expr {
+    fn from(expr: &Expr) -> Self {
+        expr.clone()
+    }
+}
+
+impl Neg for Expr {
+    type Output = Expr;
+
+    fn neg(self) -> Expr {
+        match self.as_constant() {
+            Some(value) => Expr::constant(-value),
+            None => Expr::new(Node::Neg(self)),
+        }
+    }
+}
+
+impl Neg for &Expr {
+    type Output = Expr;
+
+    fn neg(self) -> Expr {
+        -self.clone()
+    }
+}
+
+impl Neg for Var {
+    type Output = Expr;
+
+    fn neg(self) -> Expr {
+        -Expr::from(self)
+    }
+}
+
+/// Implements a binary operator for every combination of expressions, variables and constants.
+macro_rules! binary_operator {
+    ($trait:ident, $method:ident) => {
+        impl<R: Into<Expr>> $trait<R> for Expr {
+            type Output = Expr;
+
+            fn $method(self, rhs: R) -> Expr {
+                Expr::$method(self, rhs.into())
+            }
+        }
+
+        impl<R: Into<Expr>> $trait<R> for &Expr {
+            type Output = Expr;
+
+            fn $method(self, rhs: R) -> Expr {
+                Expr::$method(self.clone(), rhs.into())
+            }
+        }
+
+        impl<R: Into<Expr>> $trait<R> for Var {
+            type Output = Expr;
+
+            fn $method(self, rhs: R) -> Expr {
+                Expr::$method(self.into(), rhs.into())
+            }
+        }
+
+        impl $trait<Expr> for f64 {
+            type Output = Expr;
+
+            fn $method(self, rhs: Expr) -> Expr {
+                Expr::$method(self.into(), rhs)
+            }
+        }
+
+        impl $trait<&Expr> for f64 {
+            type Output = Expr;
+
+            fn $method(self, rhs: &Expr) -> Expr {
+                Expr::$method(self.into(), rhs.clone())
+            }
+        }
+
+        impl $trait<Var> for f64 {
+            type Output = Expr;
+
+            fn $method(self, rhs: Var) -> Expr {
+                Expr::$method(self.into(), rhs.into())
+            }
+        }
+    };
+}
+
+binary_operator!(Add, add);
+binary_operator!(Sub, sub);
+binary_operator!(Mul, mul);
+binary_operator!(Div, div);
+
+/// A variable of a `Model`, with its bounds and initial value.
+#[derive(Debug, Clone)]
+struct Variable {
+    name: String,
+    lower: f64,
+    upper: f64,
+    initial: f64,
+}
+
+/// A constraint of a `Model`, with its bounds.
+#[derive(Debug, Clone)]
+struct Constraint {
+    expr: Expr,
+    lower: f64,
+    upper: f64,
+}
+
+/// An algebraic model of a nonlinear problem.
+///
+/// Use `f64::INFINITY` and `f64::NEG_INFINITY` for missing bounds. The objective is zero until it
+/// is set with `minimize`.
+#[derive(Debug, Clone, Default)]
+pub struct Model {
+    variables: Vec<Variable>,
+    constraints: Vec<Constraint>,
+    objective: Option<Expr>,
+}
+
+impl Model {
+    /// Creates a new, empty `Model`.
+    #[must_use]
+    pub fn new() -> Self {
+        Model {
+            ..Default::default()
+        }
+    }
+
+    /// Adds a variable to the model.
+    ///
+    /// # Parameters
+    ///
+    /// - `name` - The name of the variable, which is used in diagnostics and reports.
+    /// - `lower` - The lower bound of the variable.
+    /// - `upper` - The upper bound of the variable.
+    /// - `initial` - The initial value of the variable.
+    ///
+    /// # Returns
+    ///
+    /// The variable, for use in expressions.
+    pub fn add_var(
+        &mut self,
+        name: impl Into<String>,
+        lower: f64,
+        upper: f64,
+        initial: f64,
+    ) -> Var {
+        self.variables.push(Variable {
+            name: name.into(),
+            lower,
+            upper,
+            initial,
+        });
+        Var(self.variables.len() - 1)
+    }
+
+    /// Adds the constraint `lower ≤ expr ≤ upper` to the model.
+    ///
+    /// # Parameters
+    ///
+    /// - `expr` - The constraint function.
+    /// - `lower` - The lower bound of the constraint.
+    /// - `upper` - The upper bound of the constraint.
+    ///
+    /// # Returns
+    ///
+    /// The index of the constraint, i.e. its position in `Solution::constraints`.
+    pub fn add_constraint(&mut self, expr: impl Into<Expr>, lower: f64, upper: f64) -> usize {
+        self.constraints.push(Constraint {
+            expr: expr.into(),
+            lower,
+            upper,
+        });
+        self.constraints.len() - 1
+    }
+
+    /// Sets the objective function to minimize.
+    ///
+    /// # Parameters
+    ///
+    /// - `expr` - The objective function.
+    pub fn minimize(&mut self, expr: impl Into<Expr>) -> &mut Self {
+        self.objective = Some(expr.into());
+        self
+    }
+
+    /// Gets the number of variables in the model.
+    #[must_use]
+    pub fn number_of_variables(&self) -> usize {
+        self.variables.len()
+    }
+
+    /// Gets the number of constraints in the model.
+    #[must_use]
+    pub fn number_of_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// Compiles the model into a `Tnlp`, deriving the first and second derivatives and their
+    /// sparsity patterns.
+    ///
+    /// # Errors
+    ///
+    /// - An expression uses a variable that does not belong to the model.
+    /// - The dimensions or number of derivative entries are not representable as an `i32`.
+    pub fn compile(&self) -> Result<CompiledModel, Box<dyn Error>> {
+        let n = self.variables.len();
+        let m = self.constraints.len();
+        let objective = self
+            .objective
+            .clone()
+            .unwrap_or_else(|| Expr::constant(0.0));
+        // Function 0 is the objective, and function i + 1 is constraint i.
+        let functions: Vec<Expr> = std::iter::once(objective)
+            .chain(
+                self.constraints
+                    .iter()
+                    .map(|constraint| constraint.expr.clone()),
+            )
+            .collect();
+
+        let (first, second) = Differentiator::default().differentiate(&functions, n)?;
+
+        // The tape is recorded in stages, so that evaluating the functions does not evaluate the
+        // derivatives, and evaluating the first derivatives does not evaluate the second ones.
+        let mut tape = TapeBuilder::default();
+        let outputs: Vec<usize> = functions.iter().map(|expr| tape.record(expr)).collect();
+        let functions_end = tape.ops.len();
+
+        let mut gradient = Vec::new();
+        let mut jacobian = TripletBuilder::new(m, n);
+        let mut jacobian_outputs = Vec::new();
+        for (function, j, derivative) in &first {
+            let output = tape.record(derivative);
+            if *function == 0 {
+                gradient.push((*j, output));
+            } else {
+                let slot = jacobian.entry(function - 1, *j);
+                if slot.index() == jacobian_outputs.len() {
+                    jacobian_outputs.push(output);
+                }
+            }
+        }
+        let first_derivatives_end = tape.ops.len();
+
+        let mut hessian = TripletBuilder::lower_triangular(n);
+        let mut hessian_terms = Vec::new();
+        for (function, j, k, derivative) in &second {
+            let slot = hessian.entry(*j, *k);
+            hessian_terms.push((slot, *function, tape.record(derivative)));
+        }
+
+        let ops = tape.ops;
+        Ok(CompiledModel {
+            x_l: self
+                .variables
+                .iter()
+                .map(|variable| variable.lower)
+                .collect(),
+            x_u: self
+                .variables
+                .iter()
+                .map(|variable| variable.upper)
+                .collect(),
+            g_l: self
+                .constraints
+                .iter()
+                .map(|constraint| constraint.lower)
+                .collect(),
+            g_u: self
+                .constraints
+                .iter()
+                .map(|constraint| constraint.upper)
+                .collect(),
+            starting_point: self
+                .variables
+                .iter()
+                .map(|variable| variable.initial)
+                .collect(),
+            variable_names: self
+                .variables
+                .iter()
+                .map(|variable| variable.name.clone())
+                .collect(),
+            stages: [functions_end, first_derivatives_end, ops.len()],
+            values: vec![0.0; ops.len()],
+            ops,
+            outputs,
+            gradient,
+            jacobian: jacobian.build()?,
+            jacobian_outputs,
+            hessian: hessian.build()?,
+            hessian_terms,
+            evaluated_x: Vec::new(),
+            evaluated_len: 0,
+        })
+    }
+}
+
+/// The first derivatives of functions, as `(function, j, derivative)`.
+type FirstDerivatives = Vec<(usize, usize, Expr)>;
+
+/// The second derivatives of functions, as `(function, j, k, derivative)`.
+type SecondDerivatives = Vec<(usize, usize, usize, Expr)>;
+
+/// Computes symbolic derivatives, and the variables that expressions depend on.
+///
+/// Results are cached by node, and the cache keeps the nodes alive so that their addresses stay
+/// unique.
+#[derive(Debug, Default)]
+struct Differentiator {
+    dependencies: HashMap<*const Node, (Expr, Arc<BTreeSet<usize>>)>,
+    derivatives: HashMap<(*const Node, usize), (Expr, Expr)>,
+}
+
+impl Differentiator {
+    /// Differentiates functions twice, skipping derivatives that are zero.
+    ///
+    /// # Returns
+    ///
+    /// The first derivatives as `(function, j, derivative)`, and the second derivatives in the
+    /// lower triangle as `(function, j, k, derivative)`.
+    fn differentiate(
+        &mut self,
+        functions: &[Expr],
+        n: usize,
+    ) -> Result<(FirstDerivatives, SecondDerivatives), String> {
+        let mut first = Vec::new();
+        let mut second = Vec::new();
+        for (function, expr) in functions.iter().enumerate() {
+            let dependencies = self.dependencies(expr);
+            if let Some(&variable) = dependencies.range(n..).next() {
+                return Err(format!(
+                    "Variable {variable} does not belong to the model with {n} variables."
+                ));
+            }
+
+            for &j in dependencies.iter() {
+                let derivative = self.derivative(expr, j);
+                for &k in self.dependencies(&derivative).range(..=j) {
+                    let second_derivative = self.derivative(&derivative, k);
+                    if !second_derivative.is_constant(0.0) {
+                        second.push((function, j, k, second_derivative));
+                    }
+                }
+                if !derivative.is_constant(0.0) {
+                    first.push((function, j, derivative));
+                }
+            }
+        }
+        Ok((first, second))
+    }
+
+    /// Gets the variables that an expression depends on.
+    fn dependencies(&mut self, expr: &Expr) -> Arc<BTreeSet<usize>> {
+        for node in post_order(expr, |node| self.dependencies.contains_key(&node.key())) {
+            let cached = |expr: &Expr| self.dependencies[&expr.key()].1.clone();
+            let dependencies = match &*node.0 {
+                Node::Constant(_) => Arc::new(BTreeSet::new()),
+                Node::Variable(j) => Arc::new(BTreeSet::from([*j])),
+                Node::Add(a, b) | Node::Sub(a, b) | Node::Mul(a, b) | Node::Div(a, b) => {
+                    let (a, b) = (cached(a), cached(b));
+                    // Reusing the set of an operand keeps long chains from copying it.
+                    if b.is_subset(&a) {
+                        a
+                    } else if a.is_subset(&b) {
+                        b
+                    } else {
+                        Arc::new(a.union(&b).copied().collect())
+                    }
+                }
+                Node::Neg(a)
+                | Node::Powi(a, _)
+                | Node::Exp(a)
+                | Node::Ln(a)
+                | Node::Sin(a)
+                | Node::Cos(a)
+                | Node::Sqrt(a)
+                | Node::Asin(a)
+                | Node::Acos(a)
+                | Node::Atan(a) => cached(a),
+            };
+            self.dependencies
+                .insert(node.key(), (node.clone(), dependencies));
+        }
+        self.dependencies[&expr.key()].1.clone()
+    }
+
+    /// Gets the derivative of an expression with respect to a variable.
+    fn derivative(&mut self, expr: &Expr, variable: usize) -> Expr {
+        self.dependencies(expr);
+        let is_zero = |expr: &Expr| !self.dependencies[&expr.key()].1.contains(&variable);
+        if is_zero(expr) {
+            return Expr::constant(0.0);
+        }
+
+        let order = post_order(expr, |node| {
+            is_zero(node) || self.derivatives.contains_key(&(node.key(), variable))
+        });
+        for node in order {
+            let d = |expr: &Expr| {
+                if is_zero(expr) {
+                    Expr::constant(0.0)
+                } else {
+                    self.derivatives[&(expr.key(), variable)].1.clone()
+                }
+            };
+            let expr = &node;
+            let derivative = match &*node.0 {
+                Node::Constant(_) => Expr::constant(0.0),
+                Node::Variable(_) => Expr::constant(1.0),
+                Node::Add(a, b) => d(a) + d(b),
+                Node::Sub(a, b) => d(a) - d(b),
+                Node::Mul(a, b) => d(a) * b + a * d(b),
+                Node::Div(a, b) => (d(a) - expr * d(b)) / b,
+                Node::Neg(a) => -d(a),
+                Node::Powi(a, n) => f64::from(*n) * a.powi(n - 1) * d(a),
+                Node::Exp(a) => expr * d(a),
+                Node::Ln(a) => d(a) / a,
+                Node::Sin(a) => a.cos() * d(a),
+                Node::Cos(a) => -a.sin() * d(a),
+                Node::Sqrt(a) => d(a) / (2.0 * expr),
+                Node::Asin(a) => d(a) / (1.0 - a.powi(2)).sqrt(),
+                Node::Acos(a) => -d(a) / (1.0 - a.powi(2)).sqrt(),
+                Node::Atan(a) => d(a) / (1.0 + a.powi(2)),
+            };
+            self.derivatives
+                .insert((node.key(), variable), (node.clone(), derivative));
+        }
+        self.derivatives[&(expr.key(), variable)].1.clone()
+    }
+}
+
+/// Lists the nodes of an expression that are not `done` in post-order, so that every node comes
+/// after its operands and shared nodes are listed once.
+///
+/// An explicit stack is used rather than recursion, so that deep expressions, such as long sums
+/// built one term at a time, do not overflow the call stack.
+fn post_order(expr: &Expr, done: impl Fn(&Expr) -> bool) -> Vec<Expr> {
+    let mut order = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack = vec![(expr.clone(), false)];
+    while let Some((node, expanded)) = stack.pop() {
+        if expanded {
+            order.push(node);
+        } else if !done(&node) && visited.insert(node.key()) {
+            stack.push((node.clone(), true));
+            // Pushed in reverse, so that the first operand is listed first.
+            for operand in node.0.operands().rev() {
+                stack.push((operand.clone(), false));
+            }
+        }
+    }
+    order
+}
+
+/// An operation of a tape, whose operands are the indices of earlier operations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Constant(f64),
+    Variable(usize),
+    Add(usize, usize),
+    Sub(usize, usize),
+    Mul(usize, usize),
+    Div(usize, usize),
+    Neg(usize),
+    Powi(usize, i32),
+    Exp(usize),
+    Ln(usize),
+    Sin(usize),
+    Cos(usize),
+    Sqrt(usize),
+    Asin(usize),
+    Acos(usize),
+    Atan(usize),
+}
+
+/// Records expressions as a flat list of operations, recording shared nodes once.
+#[derive(Debug, Default)]
+struct TapeBuilder {
+    ops: Vec<Op>,
+    indices: HashMap<*const Node, (Expr, usize)>,
+}
+
+impl TapeBuilder {
+    /// Records an expression and gets the index of the operation that computes it.
+    fn record(&mut self, expr: &Expr) -> usize {
+        for node in post_order(expr, |node| self.indices.contains_key(&node.key())) {
+            let index = |expr: &Expr| self.indices[&expr.key()].1;
+            let op = match &*node.0 {
+                Node::Constant(value) => Op::Constant(*value),
+                Node::Variable(j) => Op::Variable(*j),
+                Node::Add(a, b) => Op::Add(index(a), index(b)),
+                Node::Sub(a, b) => Op::Sub(index(a), index(b)),
+                Node::Mul(a, b) => Op::Mul(index(a), index(b)),
+                Node::Div(a, b) => Op::Div(index(a), index(b)),
+                Node::Neg(a) => Op::Neg(index(a)),
+                Node::Powi(a, n) => Op::Powi(index(a), *n),
+                Node::Exp(a) => Op::Exp(index(a)),
+                Node::Ln(a) => Op::Ln(index(a)),
+                Node::Sin(a) => Op::Sin(index(a)),
+                Node::Cos(a) => Op::Cos(index(a)),
+                Node::Sqrt(a) => Op::Sqrt(index(a)),
+                Node::Asin(a) => Op::Asin(index(a)),
+                Node::Acos(a) => Op::Acos(index(a)),
+                Node::Atan(a) => Op::Atan(index(a)),
+            };
+            self.ops.push(op);
+            self.indices
+                .insert(node.key(), (node.clone(), self.ops.len() - 1));
+        }
+        self.indices[&expr.key()].1
+    }
+}
+
+/// A compiled `Model`, which implements `Tnlp`.
+///
+/// The functions and their derivatives are evaluated from a tape of operations. The tape is
+/// evaluated lazily and cached for the current point, so the shared parts of the functions and
+/// derivatives are only evaluated once per point.
+#[derive(Debug, Clone)]
+pub struct CompiledModel {
+    x_l: Vec<f64>,
+    x_u: Vec<f64>,
+    g_l: Vec<f64>,
+    g_u: Vec<f64>,
+    starting_point: Vec<f64>,
+    variable_names: Vec<String>,
+    ops: Vec<Op>,
+    stages: [usize; 3],
+    outputs: Vec<usize>,
+    gradient: Vec<(usize, usize)>,
+    jacobian: SparsityPattern,
+    jacobian_outputs: Vec<usize>,
+    hessian: SparsityPattern,
+    hessian_terms: Vec<(Slot, usize, usize)>,
+    values: Vec<f64>,
+    evaluated_x: Vec<f64>,
+    evaluated_len: usize,
+}
+
+impl CompiledModel {
+    /// Gets the sparsity pattern of the Jacobian of the constraints.
+    #[must_use]
+    pub fn jacobian_pattern(&self) -> &SparsityPattern {
+        &self.jacobian
+    }
+
+    /// Gets the sparsity pattern of the Hessian of the Lagrangian.
+    #[must_use]
+    pub fn hessian_pattern(&self) -> &SparsityPattern {
+        &self.hessian
+    }
+
+    /// Evaluates the tape at `x` up to the end of a stage, reusing the values from earlier calls
+    /// at the same point.
+    fn evaluate(&mut self, x: &[f64], stage: usize) {
+        if self.evaluated_x != x {
+            self.evaluated_x.clear();
+            self.evaluated_x.extend_from_slice(x);
+            self.evaluated_len = 0;
+        }
+
+        let end = self.stages[stage];
+        let values = &mut self.values;
+        for index in self.evaluated_len..end {
+            values[index] = match self.ops[index] {
+                Op::Constant(value) => value,
+                Op::Variable(j) => x[j],
+                Op::Add(a, b) => values[a] + values[b],
+                Op::Sub(a, b) => values[a] - values[b],
+                Op::Mul(a, b) => values[a] * values[b],
+                Op::Div(a, b) => values[a] / values[b],
+                Op::Neg(a) => -values[a],
+                Op::Powi(a, n) => values[a].powi(n),
+                Op::Exp(a) => values[a].exp(),
+                Op::Ln(a) => values[a].ln(),
+                Op::Sin(a) => values[a].sin(),
+                Op::Cos(a) => values[a].cos(),
+                Op::Sqrt(a) => values[a].sqrt(),
+                Op::Asin(a) => values[a].asin(),
+                Op::Acos(a) => values[a].acos(),
+                Op::Atan(a) => values[a].atan(),
+            };
+        }
+        self.evaluated_len = self.evaluated_len.max(end);
+    }
+}
+
+/// The stages of the tape of a `CompiledModel`.
+const FUNCTIONS: usize = 0;
+const FIRST_DERIVATIVES: usize = 1;
+const SECOND_DERIVATIVES: usize = 2;
+
+impl Tnlp for CompiledModel {
+    fn get_nlp_info(&self) -> ProblemSize {
+        ProblemSize {
+            n: dimension_to_i32(self.jacobian.columns()),
+            m: dimension_to_i32(self.jacobian.rows()),
+            nnz_jac: dimension_to_i32(self.jacobian.nnz()),
+            nnz_hess: dimension_to_i32(self.hessian.nnz()),
+        }
+    }
+
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
+        x_l.copy_from_slice(&self.x_l);
+        x_u.copy_from_slice(&self.x_u);
+        g_l.copy_from_slice(&self.g_l);
+        g_u.copy_from_slice(&self.g_u);
+    }
+
+    fn get_starting_point(&self) -> InitialSolution {
+        InitialSolution::from_variables(self.starting_point.clone())
+    }
+
+    fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+        self.evaluate(x, FUNCTIONS);
+        *obj_value = self.values[self.outputs[0]];
+        obj_value.is_finite()
+    }
+
+    fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+        self.evaluate(x, FIRST_DERIVATIVES);
+        grad_f.fill(0.0);
+        for &(j, output) in &self.gradient {
+            grad_f[j] = self.values[output];
+        }
+        grad_f.iter().all(|value| value.is_finite())
+    }
+
+    fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+        self.evaluate(x, FUNCTIONS);
+        for (value, &output) in g.iter_mut().zip(&self.outputs[1..]) {
+            *value = self.values[output];
+        }
+        g.iter().all(|value| value.is_finite())
+    }
+
+    fn get_jacobian_sparsity(&mut self, _n: i32, _m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.jacobian.write_indices(i_row, j_col);
+    }
+
+    fn eval_jac_g(&mut self, x: &[f64], _m: i32, values: &mut [f64]) -> bool {
+        self.evaluate(x, FIRST_DERIVATIVES);
+        for (value, &output) in values.iter_mut().zip(&self.jacobian_outputs) {
+            *value = self.values[output];
+        }
+        values.iter().all(|value| value.is_finite())
+    }
+
+    fn get_hessian_sparsity(&mut self, _n: i32, _m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.hessian.write_indices(i_row, j_col);
+    }
+
+    fn eval_h(
+        &mut self,
+        x: &[f64],
+        obj_factor: f64,
+        lambda: &[f64],
+        _m: i32,
+        values: &mut [f64],
+    ) -> bool {
+        self.evaluate(x, SECOND_DERIVATIVES);
+        let mut hessian = self.hessian.values(values);
+        hessian.clear();
+        for &(slot, function, output) in &self.hessian_terms {
+            let factor = if function == 0 {
+                obj_factor
+            } else {
+                lambda[function - 1]
+            };
+            hessian.add(slot, factor * self.values[output]);
+        }
+        values.iter().all(|value| value.is_finite())
+    }
+
+    fn variable_names(&self) -> Option<Vec<String>> {
+        Some(self.variable_names.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The HS071 problem.
+    fn hs071() -> CompiledModel {
+        let mut model = Model::new();
+        let x: Vec<Var> = [1.0, 5.0, 5.0, 1.0]
+            .into_iter()
+            .enumerate()
+            .map(|(j, initial)| model.add_var(format!("x{j}"), 1.0, 5.0, initial))
+            .collect();
+
+        model.minimize(x[0] * x[3] * (x[0] + x[1] + x[2]) + x[2]);
+        model.add_constraint(x[0] * x[1] * x[2] * x[3], 25.0, f64::INFINITY);
+        model.add_constraint(Expr::sum(x.iter().map(|x| x.powi(2))), 40.0, 40.0);
+        model.compile().unwrap()
+    }
+
+    #[test]
+    fn compiled_model_derives_hs071_derivatives() {
+        let mut problem = hs071();
+        let x = [1.0, 5.0, 5.0, 1.0];
+        let (mut f, mut grad_f, mut g, mut jac_g) = (0.0, vec![0.0; 4], vec![0.0; 2], vec![0.0; 8]);
+
+        assert!(problem.eval_f(&x, &mut f));
+        assert!(problem.eval_grad_f(&x, &mut grad_f));
+        assert!(problem.eval_g(&x, &mut g));
+        assert!(problem.eval_jac_g(&x, 2, &mut jac_g));
+
+        assert_eq!(
+            ProblemSize {
+                n: 4,
+                m: 2,
+                nnz_jac: 8,
+                nnz_hess: 10,
+            },
+            problem.get_nlp_info()
+        );
+        assert_eq!(
+            vec![16.0, 12.0, 1.0, 2.0, 11.0, 25.0, 52.0],
+            [vec![f], grad_f, g].concat()
+        );
+        let mut jacobian = vec![0.0; 8];
+        for (&(i, j), value) in problem.jacobian_pattern().entries().iter().zip(jac_g) {
+            jacobian[4 * i + j] = value;
+        }
+        assert_eq!(vec![25.0, 5.0, 5.0, 25.0, 2.0, 10.0, 10.0, 2.0], jacobian);
+    }
+
+    #[test]
+    fn compiled_model_derives_exact_lagrangian_hessian() {
+        let mut problem = hs071();
+        let x = [1.0, 5.0, 5.0, 1.0];
+        let mut values = vec![0.0; 10];
+
+        assert!(problem.eval_h(&x, 1.0, &[1.0, 1.0], 2, &mut values));
+
+        let pattern = problem.hessian_pattern().clone();
+        let value = |row, column| values[pattern.slot(row, column).unwrap().index()];
+        // ∂²/∂x0² = 2 x3 + 2, ∂²/∂x3∂x0 = 2 x0 + x1 + x2 + x1 x2, ∂²/∂x2∂x1 = x0 x3.
+        assert_eq!(
+            vec![4.0, 37.0, 1.0],
+            vec![value(0, 0), value(3, 0), value(2, 1)]
+        );
+    }
+
+    #[test]
+    fn compiled_model_linear_terms_have_constant_derivatives_and_no_hessian() {
+        let mut model = Model::new();
+        let x = model.add_var("x", 0.0, 1.0, 0.5);
+        let y = model.add_var("y", 0.0, 1.0, 0.5);
+        model.minimize(2.0 * x - y / 4.0);
+        model.add_constraint(x + y, 1.0, 1.0);
+
+        let problem = model.compile().unwrap();
+
+        assert_eq!(0, problem.hessian_pattern().nnz());
+        assert_eq!(2, problem.jacobian_pattern().nnz());
+        assert!(
+            problem
+                .ops
+                .iter()
+                .skip(problem.stages[FUNCTIONS])
+                .all(|op| matches!(op, Op::Constant(_)))
+        );
+    }
+
+    #[test]
+    fn compiled_model_gradient_matches_finite_differences() {
+        let mut model = Model::new();
+        let x = model.add_var("x", 0.0, 10.0, 1.0);
+        let y = model.add_var("y", 0.0, 10.0, 1.0);
+        let shared = x * y;
+        model.minimize(
+            shared.sin() + (&shared / (1.0 + y)).exp() - x.ln() * y.sqrt() + x.cos().powi(3),
+        );
+        let mut problem = model.compile().unwrap();
+        let point = [1.3, 0.7];
+        let mut grad_f = vec![0.0; 2];
+
+        assert!(problem.eval_grad_f(&point, &mut grad_f));
+
+        for (j, derivative) in grad_f.into_iter().enumerate() {
+            let (mut plus, mut minus) = (point, point);
+            plus[j] += 1e-6;
+            minus[j] -= 1e-6;
+            let (mut f_plus, mut f_minus) = (0.0, 0.0);
+            assert!(problem.eval_f(&plus, &mut f_plus));
+            assert!(problem.eval_f(&minus, &mut f_minus));
+            assert!((derivative - (f_plus - f_minus) / 2e-6).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn model_compile_handles_deep_expressions() {
+        let mut model = Model::new();
+        let x = model.add_var("x", 0.0, 1.0, 0.5);
+        let mut objective = Expr::constant(0.0);
+        for _ in 0..100_000 {
+            objective = objective + x.powi(2);
+        }
+        model.minimize(objective);
+
+        let mut problem = model.compile().unwrap();
+        drop(model);
+        let (mut f, mut grad_f, mut h) = (0.0, vec![0.0], vec![0.0]);
+
+        assert!(problem.eval_f(&[0.5], &mut f));
+        assert!(problem.eval_grad_f(&[0.5], &mut grad_f));
+        assert!(problem.eval_h(&[0.5], 1.0, &[], 1, &mut h));
+
+        assert_eq!(
+            vec![25_000.0, 100_000.0, 200_000.0],
+            [vec![f], grad_f, h].concat()
+        );
+    }
+
+    #[test]
+    fn model_compile_rejects_foreign_variables() {
+        let mut other = Model::new();
+        other.add_var("a", 0.0, 1.0, 0.0);
+        let foreign = other.add_var("b", 0.0, 1.0, 0.0);
+        let mut model = Model::new();
+        model.add_var("x", 0.0, 1.0, 0.0);
+        model.minimize(foreign);
+
+        assert!(model.compile().is_err());
+    }
+}
@@ -0,0 +1,737 @@
+//! # Ipopt Bindgen MPS
+//!
+//! Defines a reader for linear and quadratic problems in the MPS and QPS formats, which are used
+//! by standard test sets such as Netlib and Maros-Mészáros.
+//!
+//! Both the fixed format, in which fields are found by column and names may contain spaces, and
+//! the free format, in which fields are separated by whitespace, are supported. The `NAME`,
+//! `OBJSENSE`, `ROWS`, `COLUMNS`, `RHS`, `RANGES`, `BOUNDS`, `QUADOBJ`, `QSECTION` and `QMATRIX`
+//! sections are read. Integer markers are ignored, i.e. the continuous relaxation is read, and
+//! quadratic constraints and semi-continuous bounds are reported as errors.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use ipopt_bindgen::{Application, MpsFormat, MpsProblem};
+//!
+//! let problem = MpsProblem::from_file("afiro.mps", MpsFormat::Fixed).unwrap();
+//! let result = Application::new().optimize_tnlp(problem).unwrap();
+//! ```
+
+use crate::{
+    bounds::{Bound, Bounds},
+    linear::LinearProblem,
+    quadratic::QuadraticProblem,
+    tnlp::{InitialSolution, OptionValue, ProblemNames, ProblemSize, Tnlp, UserScaling},
+};
+use std::{collections::HashMap, error::Error, fs, path::Path};
+
+/// The layout of the fields of an MPS file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MpsFormat {
+    /// Fields are found by column, so names may contain spaces.
+    Fixed,
+
+    /// Fields are separated by whitespace.
+    Free,
+}
+
+/// A linear or quadratic problem read from an MPS or QPS file, which implements `Tnlp`.
+///
+/// The problem is a `LinearProblem` if the file has no quadratic section, and a
+/// `QuadraticProblem` otherwise, so the corresponding Ipopt options are set through
+/// `Tnlp::default_options`. The constant of the objective, given by the negated right-hand side of
+/// the objective row, is added to the objective value. A maximization objective is optimized by
+/// setting the `obj_scaling_factor` option to `-1`, so the reported objective keeps its sign.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MpsProblem {
+    name: String,
+    objective_offset: f64,
+    maximize: bool,
+    program: Program,
+}
+
+/// The front-end that an `MpsProblem` forwards to.
+#[derive(Debug, Clone, PartialEq)]
+enum Program {
+    Linear(LinearProblem),
+    Quadratic(QuadraticProblem),
+}
+
+impl MpsProblem {
+    /// Reads a problem from an MPS or QPS file.
+    ///
+    /// # Parameters
+    ///
+    /// - `path` - The path of the file.
+    /// - `format` - The layout of the fields of the file.
+    ///
+    /// # Errors
+    ///
+    /// - The file cannot be read.
+    /// - The file is not a valid MPS file or uses an unsupported feature.
+    pub fn from_file(path: impl AsRef<Path>, format: MpsFormat) -> Result<Self, Box<dyn Error>> {
+        Self::parse(&fs::read_to_string(path)?, format)
+    }
+
+    /// Reads a problem from the contents of an MPS or QPS file.
+    ///
+    /// # Parameters
+    ///
+    /// - `text` - The contents of the file.
+    /// - `format` - The layout of the fields of the file.
+    ///
+    /// # Errors
+    ///
+    /// The contents are not a valid MPS file or use an unsupported feature. The error names the
+    /// offending line.
+    pub fn parse(text: &str, format: MpsFormat) -> Result<Self, Box<dyn Error>> {
+        let mut reader = MpsReader::new(format);
+        for (index, line) in text.lines().enumerate() {
+            if !reader
+                .read_line(line)
+                .map_err(|error| format!("Line {}: {error}", index + 1))?
+            {
+                break;
+            }
+        }
+        reader.into_problem()
+    }
+
+    /// Gets the name of the problem, as given by the `NAME` section.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the constant of the objective.
+    #[must_use]
+    pub fn objective_offset(&self) -> f64 {
+        self.objective_offset
+    }
+
+    /// Checks whether the objective is maximized.
+    #[must_use]
+    pub fn is_maximization(&self) -> bool {
+        self.maximize
+    }
+
+    /// Checks whether the objective has a quadratic term.
+    #[must_use]
+    pub fn is_quadratic(&self) -> bool {
+        matches!(self.program, Program::Quadratic(_))
+    }
+
+    /// Gets the number of variables.
+    #[must_use]
+    pub fn number_of_variables(&self) -> usize {
+        match &self.program {
+            Program::Linear(problem) => problem.number_of_variables(),
+            Program::Quadratic(problem) => problem.number_of_variables(),
+        }
+    }
+
+    /// Gets the number of constraints.
+    #[must_use]
+    pub fn number_of_constraints(&self) -> usize {
+        match &self.program {
+            Program::Linear(problem) => problem.number_of_constraints(),
+            Program::Quadratic(problem) => problem.number_of_constraints(),
+        }
+    }
+
+    fn tnlp(&self) -> &dyn Tnlp {
+        match &self.program {
+            Program::Linear(problem) => problem,
+            Program::Quadratic(problem) => problem,
+        }
+    }
+
+    fn tnlp_mut(&mut self) -> &mut dyn Tnlp {
+        match &mut self.program {
+            Program::Linear(problem) => problem,
+            Program::Quadratic(problem) => problem,
+        }
+    }
+}
+
+impl Tnlp for MpsProblem {
+    fn get_nlp_info(&self) -> ProblemSize {
+        self.tnlp().get_nlp_info()
+    }
+
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
+        self.tnlp().get_bounds_info(x_l, x_u, g_l, g_u);
+    }
+
+    fn get_scaling(&self) -> UserScaling {
+        self.tnlp().get_scaling()
+    }
+
+    fn get_starting_point(&self) -> InitialSolution {
+        self.tnlp().get_starting_point()
+    }
+
+    fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+        let success = self.tnlp_mut().eval_f(x, obj_value);
+        *obj_value += self.objective_offset;
+        success
+    }
+
+    fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+        self.tnlp_mut().eval_grad_f(x, grad_f)
+    }
+
+    fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+        self.tnlp_mut().eval_g(x, g)
+    }
+
+    fn get_jacobian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.tnlp_mut().get_jacobian_sparsity(n, m, i_row, j_col);
+    }
+
+    fn eval_jac_g(&mut self, x: &[f64], m: i32, values: &mut [f64]) -> bool {
+        self.tnlp_mut().eval_jac_g(x, m, values)
+    }
+
+    fn get_hessian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.tnlp_mut().get_hessian_sparsity(n, m, i_row, j_col);
+    }
+
+    fn eval_h(
+        &mut self,
+        x: &[f64],
+        obj_factor: f64,
+        lambda: &[f64],
+        m: i32,
+        values: &mut [f64],
+    ) -> bool {
+        self.tnlp_mut().eval_h(x, obj_factor, lambda, m, values)
+    }
+
+    fn variable_names(&self) -> Option<Vec<String>> {
+        self.tnlp().variable_names()
+    }
+
+    fn constraint_names(&self) -> Option<Vec<String>> {
+        self.tnlp().constraint_names()
+    }
+
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        let mut options = self.tnlp().default_options();
+        if self.maximize {
+            options.push(("obj_scaling_factor".to_string(), OptionValue::Numeric(-1.0)));
+        }
+        options
+    }
+}
+
+/// The sections of an MPS file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Name,
+    ObjSense,
+    Rows,
+    Columns,
+    Rhs,
+    Ranges,
+    Bounds,
+    QuadObj,
+    QMatrix,
+}
+
+/// The kinds of rows of an MPS file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Row {
+    Objective,
+    Free,
+    Constraint(usize),
+}
+
+/// The kinds of constraint rows of an MPS file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowKind {
+    Equal,
+    Less,
+    Greater,
+}
+
+/// The start columns and end columns of the six fields of the fixed format.
+const FIXED_FIELDS: [(usize, usize); 6] = [(1, 3), (4, 12), (14, 22), (24, 36), (39, 47), (49, 61)];
+
+/// A reader of the lines of an MPS file, which gathers the sections before the problem is built.
+struct MpsReader {
+    format: MpsFormat,
+    section: Section,
+    name: String,
+    maximize: bool,
+    objective_offset: f64,
+    rows: HashMap<String, Row>,
+    row_names: Vec<String>,
+    row_kinds: Vec<RowKind>,
+    rhs: Vec<f64>,
+    ranges: Vec<Option<f64>>,
+    columns: HashMap<String, usize>,
+    column_names: Vec<String>,
+    objective: Vec<f64>,
+    bounds: Vec<Bounds>,
+    matrix: Vec<(usize, usize, f64)>,
+    quadratic: Vec<(usize, usize, f64)>,
+}
+
+impl MpsReader {
+    fn new(format: MpsFormat) -> Self {
+        MpsReader {
+            format,
+            section: Section::Name,
+            name: String::new(),
+            maximize: false,
+            objective_offset: 0.0,
+            rows: HashMap::new(),
+            row_names: Vec::new(),
+            row_kinds: Vec::new(),
+            rhs: Vec::new(),
+            ranges: Vec::new(),
+            columns: HashMap::new(),
+            column_names: Vec::new(),
+            objective: Vec::new(),
+            bounds: Vec::new(),
+            matrix: Vec::new(),
+            quadratic: Vec::new(),
+        }
+    }
+
+    /// Reads a line, returning whether the end of the data has not yet been reached.
+    fn read_line(&mut self, line: &str) -> Result<bool, String> {
+        if line.starts_with('*') || line.trim().is_empty() {
+            return Ok(true);
+        }
+
+        let mut tokens = line.split_whitespace();
+        let keyword = tokens.next().unwrap_or_default();
+        if !line.starts_with(char::is_whitespace) {
+            let section = match keyword {
+                "NAME" => {
+                    self.name = line[4..].trim().to_string();
+                    Some(Section::Name)
+                }
+                "OBJSENSE" => {
+                    if let Some(sense) = tokens.next() {
+                        self.read_sense(sense)?;
+                    }
+                    Some(Section::ObjSense)
+                }
+                "ROWS" => Some(Section::Rows),
+                "COLUMNS" => Some(Section::Columns),
+                "RHS" => Some(Section::Rhs),
+                "RANGES" => Some(Section::Ranges),
+                "BOUNDS" => Some(Section::Bounds),
+                "QUADOBJ" | "QSECTION" => Some(Section::QuadObj),
+                "QMATRIX" => Some(Section::QMatrix),
+                "QCMATRIX" => return Err("Quadratic constraints are not supported.".to_string()),
+                "ENDATA" => return Ok(false),
+                _ => None,
+            };
+            if let Some(section) = section {
+                self.section = section;
+                return Ok(true);
+            }
+        }
+
+        let (kind, fields) = self.fields(line);
+        match self.section {
+            Section::Name => Err(format!("Unexpected data `{}`.", line.trim())),
+            Section::ObjSense => self.read_sense(fields.first().copied().unwrap_or(kind)),
+            Section::Rows => self.read_row(kind, &fields),
+            Section::Columns => self.read_column(&fields),
+            Section::Rhs | Section::Ranges => self.read_right_hand_side(&fields),
+            Section::Bounds => self.read_bound(kind, &fields),
+            Section::QuadObj | Section::QMatrix => self.read_quadratic(&fields),
+        }
+        .map(|()| true)
+    }
+
+    /// Splits a data line into its type code, if the section has one, and its other fields.
+    fn fields<'a>(&self, line: &'a str) -> (&'a str, Vec<&'a str>) {
+        let has_kind = matches!(self.section, Section::Rows | Section::Bounds);
+        match self.format {
+            MpsFormat::Fixed => {
+                let field = |(start, end): (usize, usize)| {
+                    line.get(start..end.min(line.len()))
+                        .or_else(|| line.get(start..))
+                        .unwrap_or_default()
+                        .trim()
+                };
+                let fields = FIXED_FIELDS[1..]
+                    .iter()
+                    .map(|&columns| field(columns))
+                    .filter(|field| !field.is_empty())
+                    .collect();
+                (field(FIXED_FIELDS[0]), fields)
+            }
+            MpsFormat::Free => {
+                let mut tokens = line.split_whitespace();
+                let kind = if has_kind { tokens.next() } else { None };
+                (kind.unwrap_or_default(), tokens.collect())
+            }
+        }
+    }
+
+    fn read_sense(&mut self, sense: &str) -> Result<(), String> {
+        self.maximize = match sense {
+            "MAX" | "MAXIMIZE" => true,
+            "MIN" | "MINIMIZE" => false,
+            _ => return Err(format!("Invalid objective sense `{sense}`.")),
+        };
+        Ok(())
+    }
+
+    fn read_row(&mut self, kind: &str, fields: &[&str]) -> Result<(), String> {
+        let name = *fields.first().ok_or("Missing row name.")?;
+        let row = match kind {
+            "N" if self.rows.values().all(|&row| row != Row::Objective) => Row::Objective,
+            "N" => Row::Free,
+            "E" | "L" | "G" => {
+                self.row_kinds.push(match kind {
+                    "E" => RowKind::Equal,
+                    "L" => RowKind::Less,
+                    _ => RowKind::Greater,
+                });
+                self.row_names.push(name.to_string());
+                self.rhs.push(0.0);
+                self.ranges.push(None);
+                Row::Constraint(self.row_kinds.len() - 1)
+            }
+            _ => return Err(format!("Invalid row type `{kind}`.")),
+        };
+        if self.rows.insert(name.to_string(), row).is_some() {
+            return Err(format!("Duplicate row `{name}`."));
+        }
+        Ok(())
+    }
+
+    fn read_column(&mut self, fields: &[&str]) -> Result<(), String> {
+        if fields.get(1) == Some(&"'MARKER'") {
+            return Ok(());
+        }
+        let (&name, pairs) = fields.split_first().ok_or("Missing column name.")?;
+        let column = if let Some(&column) = self.columns.get(name) {
+            column
+        } else {
+            self.columns
+                .insert(name.to_string(), self.column_names.len());
+            self.column_names.push(name.to_string());
+            self.objective.push(0.0);
+            self.bounds.push(Bounds::lower(0.0));
+            self.column_names.len() - 1
+        };
+
+        for (row, value) in self.pairs(pairs)? {
+            match row {
+                Row::Objective => self.objective[column] += value,
+                Row::Constraint(i) => self.matrix.push((i, column, value)),
+                Row::Free => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn read_right_hand_side(&mut self, fields: &[&str]) -> Result<(), String> {
+        // The name of the right-hand side or range vector is optional.
+        let pairs = if fields.len() % 2 == 1 {
+            &fields[1..]
+        } else {
+            fields
+        };
+
+        for (row, value) in self.pairs(pairs)? {
+            match (self.section, row) {
+                (Section::Rhs, Row::Objective) => self.objective_offset = -value,
+                (Section::Rhs, Row::Constraint(i)) => self.rhs[i] = value,
+                (Section::Ranges, Row::Constraint(i)) => self.ranges[i] = Some(value),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads `(row, value)` pairs of fields.
+    fn pairs(&self, fields: &[&str]) -> Result<Vec<(Row, f64)>, String> {
+        if fields.is_empty() || fields.len() % 2 == 1 {
+            return Err("Expected pairs of row names and values.".to_string());
+        }
+        fields
+            .chunks(2)
+            .map(|pair| {
+                let row = *self
+                    .rows
+                    .get(pair[0])
+                    .ok_or_else(|| format!("Unknown row `{}`.", pair[0]))?;
+                Ok((row, parse_value(pair[1])?))
+            })
+            .collect()
+    }
+
+    fn read_bound(&mut self, kind: &str, fields: &[&str]) -> Result<(), String> {
+        let needs_value = !matches!(kind, "FR" | "MI" | "PL" | "BV");
+        // The name of the bound vector is optional.
+        let (name, value) = match (needs_value, fields) {
+            (true, [_, name, value] | [name, value]) => (*name, parse_value(value)?),
+            (false, [_, name] | [_, name, _] | [name]) => (*name, 0.0),
+            _ => return Err(format!("Invalid `{kind}` bound.")),
+        };
+        let column = *self
+            .columns
+            .get(name)
+            .ok_or_else(|| format!("Unknown column `{name}`."))?;
+
+        let bounds = &mut self.bounds[column];
+        match kind {
+            "LO" | "LI" => bounds.lower = Bound::from_value(value),
+            "UP" | "UI" => {
+                // By convention, a negative upper bound on a variable with the default lower
+                // bound of zero makes the variable unbounded below.
+                if value < 0.0 && bounds.lower == Bound::Finite(0.0) {
+                    bounds.lower = Bound::Infinite;
+                }
+                bounds.upper = Bound::from_value(value);
+            }
+            "FX" => *bounds = Bounds::equal(value),
+            "FR" => *bounds = Bounds::free(),
+            "MI" => bounds.lower = Bound::Infinite,
+            "PL" => bounds.upper = Bound::Infinite,
+            "BV" => *bounds = Bounds::range(0.0, 1.0),
+            "SC" => return Err("Semi-continuous variables are not supported.".to_string()),
+            _ => return Err(format!("Invalid bound type `{kind}`.")),
+        }
+        Ok(())
+    }
+
+    fn read_quadratic(&mut self, fields: &[&str]) -> Result<(), String> {
+        let [first, second, value] = fields else {
+            return Err("Expected two column names and a value.".to_string());
+        };
+        let column = |name: &str| {
+            self.columns
+                .get(name)
+                .copied()
+                .ok_or_else(|| format!("Unknown column `{name}`."))
+        };
+        let (i, j, value) = (column(first)?, column(second)?, parse_value(value)?);
+
+        self.quadratic.push((i, j, value));
+        // `QUADOBJ` only lists one triangle of the symmetric matrix, whereas `QMATRIX` lists both.
+        if self.section == Section::QuadObj && i != j {
+            self.quadratic.push((j, i, value));
+        }
+        Ok(())
+    }
+
+    /// Gets the bounds of each constraint, from its type, right-hand side and range.
+    fn constraint_bounds(&self) -> Vec<Bounds> {
+        self.row_kinds
+            .iter()
+            .zip(&self.rhs)
+            .zip(&self.ranges)
+            .map(|((kind, &rhs), range)| match (kind, range) {
+                (RowKind::Equal, None) => Bounds::equal(rhs),
+                (RowKind::Equal, Some(range)) if *range < 0.0 => Bounds::range(rhs + range, rhs),
+                (RowKind::Equal, Some(range)) => Bounds::range(rhs, rhs + range),
+                (RowKind::Less, None) => Bounds::upper(rhs),
+                (RowKind::Less, Some(range)) => Bounds::range(rhs - range.abs(), rhs),
+                (RowKind::Greater, None) => Bounds::lower(rhs),
+                (RowKind::Greater, Some(range)) => Bounds::range(rhs, rhs + range.abs()),
+            })
+            .collect()
+    }
+
+    fn into_problem(self) -> Result<MpsProblem, Box<dyn Error>> {
+        if !self.rows.values().any(|&row| row == Row::Objective) {
+            return Err("The file has no objective row.".into());
+        }
+
+        let (n, m) = (self.column_names.len(), self.row_names.len());
+        let constraint_bounds = self.constraint_bounds();
+        let names = ProblemNames {
+            variables: Some(self.column_names),
+            constraints: Some(self.row_names),
+        };
+
+        let program = if self.quadratic.is_empty() {
+            let mut problem = LinearProblem::new(n, m);
+            problem
+                .set_objective(self.objective)?
+                .set_constraints(&self.matrix)?
+                .set_variable_bounds(self.bounds)?
+                .set_constraint_bounds(constraint_bounds)?
+                .set_names(names);
+            Program::Linear(problem)
+        } else {
+            let mut problem = QuadraticProblem::new(n, m);
+            problem
+                .set_objective(&self.quadratic, self.objective)?
+                .set_constraints(&self.matrix)?
+                .set_variable_bounds(self.bounds)?
+                .set_constraint_bounds(constraint_bounds)?
+                .set_names(names);
+            Program::Quadratic(problem)
+        };
+
+        Ok(MpsProblem {
+            name: self.name,
+            objective_offset: self.objective_offset,
+            maximize: self.maximize,
+            program,
+        })
+    }
+}
+
+fn parse_value(text: &str) -> Result<f64, String> {
+    text.parse().map_err(|_| format!("Invalid value `{text}`."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Formats a line of the fixed format.
+    fn fixed(fields: [&str; 6]) -> String {
+        let [kind, first, second, third, fourth, fifth] = fields;
+        format!(" {kind:<2} {first:<8}  {second:<8}  {third:>12}   {fourth:<8}  {fifth:>12}")
+            .trim_end()
+            .to_string()
+    }
+
+    /// A fixed format LP with a range, an objective constant and a column with a space in its
+    /// name.
+    fn fixed_lp() -> String {
+        [
+            "NAME          TESTLP".to_string(),
+            "ROWS".to_string(),
+            fixed(["N", "COST", "", "", "", ""]),
+            fixed(["L", "LIM1", "", "", "", ""]),
+            fixed(["G", "LIM2", "", "", "", ""]),
+            fixed(["E", "MYEQN", "", "", "", ""]),
+            "COLUMNS".to_string(),
+            fixed(["", "X ONE", "COST", "1", "LIM1", "1"]),
+            fixed(["", "X ONE", "LIM2", "1", "", ""]),
+            fixed(["", "Y", "COST", "2", "LIM1", "1"]),
+            fixed(["", "Y", "MYEQN", "-1", "", ""]),
+            fixed(["", "Z", "COST", "-1", "MYEQN", "1"]),
+            "RHS".to_string(),
+            fixed(["", "", "COST", "-10", "LIM1", "4"]),
+            fixed(["", "", "LIM2", "1", "MYEQN", "7"]),
+            "RANGES".to_string(),
+            fixed(["", "RNG", "LIM1", "2.5", "MYEQN", "-3"]),
+            "BOUNDS".to_string(),
+            fixed(["UP", "BND", "X ONE", "4", "", ""]),
+            fixed(["MI", "BND", "Y", "", "", ""]),
+            fixed(["UP", "BND", "Y", "1", "", ""]),
+            fixed(["UP", "BND", "Z", "-2", "", ""]),
+            "ENDATA".to_string(),
+        ]
+        .join("\n")
+    }
+
+    const FREE_QP: &str = "NAME QP
+OBJSENSE
+    MAX
+ROWS
+ N obj
+ G c1
+COLUMNS
+ x obj 1 c1 1
+ y obj 0 c1 1
+RHS
+ rhs c1 1
+BOUNDS
+ FR bnd x
+ BV bnd y
+QUADOBJ
+ x x -2
+ y x 1
+ENDATA
+";
+
+    fn bounds(problem: &MpsProblem) -> Vec<f64> {
+        let (n, m) = (
+            problem.number_of_variables(),
+            problem.number_of_constraints(),
+        );
+        let (mut x_l, mut x_u, mut g_l, mut g_u) =
+            (vec![0.0; n], vec![0.0; n], vec![0.0; m], vec![0.0; m]);
+        problem.get_bounds_info(&mut x_l, &mut x_u, &mut g_l, &mut g_u);
+        [x_l, x_u, g_l, g_u].concat()
+    }
+
+    #[test]
+    fn mps_problem_parses_fixed_format_lp() {
+        let mut problem = MpsProblem::parse(&fixed_lp(), MpsFormat::Fixed).unwrap();
+        let mut f = 0.0;
+
+        assert!(problem.eval_f(&[1.0, 1.0, 1.0], &mut f));
+
+        assert_eq!("TESTLP", problem.name());
+        assert!(!problem.is_quadratic());
+        assert_eq!(
+            Some(vec!["X ONE".to_string(), "Y".to_string(), "Z".to_string()]),
+            problem.variable_names()
+        );
+        assert_eq!(vec![12.0], vec![f]);
+        let inf = f64::INFINITY;
+        assert_eq!(
+            vec![
+                0.0, -inf, -inf, 4.0, 1.0, -2.0, 1.5, 1.0, 4.0, 4.0, inf, 7.0
+            ],
+            bounds(&problem)
+        );
+    }
+
+    #[test]
+    fn mps_problem_parses_free_format_qp() {
+        let mut problem = MpsProblem::parse(FREE_QP, MpsFormat::Free).unwrap();
+        let (mut f, mut hessian) = (0.0, vec![0.0; 2]);
+
+        assert!(problem.eval_f(&[1.0, 1.0], &mut f));
+        assert!(problem.eval_h(&[1.0, 1.0], 1.0, &[0.0], 1, &mut hessian));
+
+        assert!(problem.is_quadratic());
+        assert!(problem.is_maximization());
+        // ½ xᵀQx + cᵀx = -x² + xy + x.
+        assert_eq!(vec![1.0], vec![f]);
+        assert_eq!(2, problem.get_nlp_info().nnz_hess);
+        assert_eq!(vec![-2.0, 1.0], hessian);
+        assert!(
+            problem
+                .default_options()
+                .contains(&("obj_scaling_factor".to_string(), OptionValue::Numeric(-1.0)))
+        );
+        let inf = f64::INFINITY;
+        assert_eq!(vec![-inf, 0.0, inf, 1.0, 1.0, inf], bounds(&problem));
+    }
+
+    #[test]
+    fn mps_problem_qmatrix_matches_quadobj() {
+        let qmatrix = FREE_QP
+            .replace("QUADOBJ", "QMATRIX")
+            .replace(" y x 1\n", " y x 1\n x y 1\n");
+        let mut quadobj = MpsProblem::parse(FREE_QP, MpsFormat::Free).unwrap();
+        let mut qmatrix = MpsProblem::parse(&qmatrix, MpsFormat::Free).unwrap();
+        let (mut expected, mut actual) = (vec![0.0; 2], vec![0.0; 2]);
+
+        assert!(quadobj.eval_grad_f(&[2.0, 3.0], &mut expected));
+        assert!(qmatrix.eval_grad_f(&[2.0, 3.0], &mut actual));
+
+        assert_eq!(expected, actual);
+        assert_eq!(vec![0.0, 2.0], actual);
+    }
+
+    #[test]
+    fn mps_problem_reports_line_of_unknown_row() {
+        let text = FREE_QP.replace(" y obj 0 c1 1", " y obj 0 c2 1");
+
+        let error = MpsProblem::parse(&text, MpsFormat::Free).unwrap_err();
+
+        assert_eq!("Line 9: Unknown row `c2`.", error.to_string());
+    }
+}
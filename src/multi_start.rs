@@ -0,0 +1,284 @@
+//! # Ipopt Bindgen Multi-Start
+//!
+//! Defines a runner that optimizes a problem from many starting points in parallel, which is a
+//! simple way to look for better local optima of non-convex problems.
+
+use crate::{
+    application::Application,
+    results::OptimizationResult,
+    tnlp::{InitialSolution, Tnlp},
+};
+use std::{
+    cmp::Ordering,
+    sync::atomic::{AtomicUsize, Ordering as AtomicOrdering},
+    thread,
+};
+
+/// The outcome of optimizing from a single starting point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiStartRun {
+    /// The index of the starting point that was used for this run.
+    pub start_index: usize,
+
+    /// The optimization results, or a description of the error if the optimization failed to run.
+    pub result: Result<OptimizationResult, String>,
+}
+
+/// The outcome of a multi-start optimization.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MultiStartResult {
+    /// The result of every run.
+    ///
+    /// Runs that found a feasible solution come first, sorted by increasing objective value.
+    /// These are followed by the remaining runs, also sorted by objective value, and finally by the
+    /// runs that failed to run at all.
+    pub runs: Vec<MultiStartRun>,
+
+    /// The index into `runs` of the best feasible solution, if any run found one.
+    pub best: Option<usize>,
+}
+
+impl MultiStartResult {
+    /// Gets the run that found the best feasible solution, if any run found one.
+    #[must_use]
+    pub fn best_run(&self) -> Option<&MultiStartRun> {
+        self.best.map(|index| &self.runs[index])
+    }
+
+    /// Gets the results of the best feasible solution, if any run found one.
+    #[must_use]
+    pub fn best_result(&self) -> Option<&OptimizationResult> {
+        self.best_run().and_then(|run| run.result.as_ref().ok())
+    }
+
+    fn from_runs(mut runs: Vec<MultiStartRun>) -> Self {
+        runs.sort_by(compare_runs);
+        let best = runs
+            .iter()
+            .position(|run| run.result.as_ref().is_ok_and(OptimizationResult::succeeded));
+
+        MultiStartResult { runs, best }
+    }
+}
+
+/// Orders runs by feasibility, then by objective value, with failed runs last.
+fn compare_runs(a: &MultiStartRun, b: &MultiStartRun) -> Ordering {
+    match (&a.result, &b.result) {
+        (Ok(a_result), Ok(b_result)) => {
+            b_result
+                .succeeded()
+                .cmp(&a_result.succeeded())
+                .then_with(|| {
+                    a_result
+                        .solution
+                        .objective
+                        .total_cmp(&b_result.solution.objective)
+                })
+        }
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => Ordering::Equal,
+    }
+    .then_with(|| a.start_index.cmp(&b.start_index))
+}
+
+/// Optimizes a problem from a set of starting points, optionally spread over a pool of threads.
+///
+/// The runs are sequential by default, since MUMPS, the default linear solver of Ipopt, is not
+/// thread safe, so concurrent solves with it can crash or corrupt each other. Only raise the number
+/// of threads with `set_number_of_threads` if the application uses a thread safe linear solver.
+///
+/// A new instance of the problem is created by the factory for every starting point. Problems are
+/// created and optimized entirely on the worker threads, so they do not need to be `Send`, but the
+/// factory must be `Sync` so that it can be shared by the workers.
+///
+/// # Example
+///
+/// ```no_run
+/// use ipopt_bindgen::{Application, InitialSolution, MultiStart};
+/// # use ipopt_bindgen::{ProblemSize, Tnlp};
+/// # struct Problem;
+/// # impl Tnlp for Problem {
+/// #     fn get_nlp_info(&self) -> ProblemSize { ProblemSize::default() }
+/// #     fn get_bounds_info(&self, _: &mut [f64], _: &mut [f64], _: &mut [f64], _: &mut [f64]) {}
+/// #     fn get_starting_point(&self) -> InitialSolution { InitialSolution::default() }
+/// #     fn eval_f(&mut self, _: &[f64], _: &mut f64) -> bool { true }
+/// #     fn eval_grad_f(&mut self, _: &[f64], _: &mut [f64]) -> bool { true }
+/// #     fn eval_g(&mut self, _: &[f64], _: &mut [f64]) -> bool { true }
+/// #     fn get_jacobian_sparsity(&mut self, _: i32, _: i32, _: &mut [i32], _: &mut [i32]) {}
+/// #     fn eval_jac_g(&mut self, _: &[f64], _: i32, _: &mut [f64]) -> bool { true }
+/// #     fn get_hessian_sparsity(&mut self, _: i32, _: i32, _: &mut [i32], _: &mut [i32]) {}
+/// #     fn eval_h(&mut self, _: &[f64], _: f64, _: &[f64], _: i32, _: &mut [f64]) -> bool { true }
+/// # }
+///
+/// let starting_points = (0..8)
+///     .map(|i| InitialSolution::from_variables(vec![f64::from(i), -f64::from(i)]))
+///     .collect();
+///
+/// // Concurrent solves need a thread safe linear solver.
+/// let mut application = Application::new();
+/// application.set_string_option("linear_solver", "ma57");
+///
+/// let mut multi_start = MultiStart::new(application, || Problem, starting_points);
+/// multi_start.set_number_of_threads(4);
+///
+/// let results = multi_start.run();
+///
+/// if let Some(best) = results.best_result() {
+///     println!("Best objective: {}", best.solution.objective);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiStart<F> {
+    application: Application,
+    factory: F,
+    starting_points: Vec<InitialSolution>,
+    number_of_threads: usize,
+}
+
+impl<P, F> MultiStart<F>
+where
+    P: Tnlp,
+    F: Fn() -> P + Sync,
+{
+    /// Creates a new `MultiStart` runner.
+    ///
+    /// By default, a single thread is used.
+    ///
+    /// # Parameters
+    ///
+    /// - `application` - The application used for every optimization.
+    /// - `factory` - Creates a new instance of the problem for each run.
+    /// - `starting_points` - The starting points to optimize from.
+    #[must_use]
+    pub fn new(
+        application: Application,
+        factory: F,
+        starting_points: Vec<InitialSolution>,
+    ) -> Self {
+        MultiStart {
+            application,
+            factory,
+            starting_points,
+            number_of_threads: 1,
+        }
+    }
+
+    /// Sets the number of worker threads.
+    ///
+    /// Concurrent solves are only safe if the `linear_solver` of the application is thread safe,
+    /// e.g. an HSL solver built with thread safety, or Pardiso. MUMPS, the default, is not, so it
+    /// must be used with a single thread. The available parallelism of the system can be found with
+    /// `std::thread::available_parallelism`.
+    ///
+    /// # Parameters
+    ///
+    /// - `number_of_threads` - The number of threads. Values less than 1 are treated as 1.
+    pub fn set_number_of_threads(&mut self, number_of_threads: usize) -> &mut Self {
+        self.number_of_threads = number_of_threads.max(1);
+        self
+    }
+
+    /// Adds a starting point to optimize from.
+    ///
+    /// # Parameters
+    ///
+    /// - `starting_point` - The starting point.
+    pub fn add_starting_point(&mut self, starting_point: InitialSolution) -> &mut Self {
+        self.starting_points.push(starting_point);
+        self
+    }
+
+    /// Optimizes the problem from every starting point.
+    ///
+    /// # Returns
+    ///
+    /// The results of every run, sorted by objective value, with the best feasible run highlighted.
+    #[must_use]
+    pub fn run(&self) -> MultiStartResult {
+        let next_start = AtomicUsize::new(0);
+        let number_of_workers = self.number_of_threads.min(self.starting_points.len());
+
+        let runs = thread::scope(|scope| {
+            let workers: Vec<_> = (0..number_of_workers)
+                .map(|_| scope.spawn(|| self.work(&next_start)))
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| {
+                    worker
+                        .join()
+                        .unwrap_or_else(|e| std::panic::resume_unwind(e))
+                })
+                .collect()
+        });
+
+        MultiStartResult::from_runs(runs)
+    }
+
+    /// Runs optimizations on the current thread until there are no starting points left.
+    fn work(&self, next_start: &AtomicUsize) -> Vec<MultiStartRun> {
+        let mut runs = Vec::new();
+        loop {
+            let start_index = next_start.fetch_add(1, AtomicOrdering::Relaxed);
+            let Some(starting_point) = self.starting_points.get(start_index) else {
+                return runs;
+            };
+
+            let result = self
+                .application
+                .optimize_tnlp_from((self.factory)(), starting_point.clone())
+                .map_err(|e| e.to_string());
+            runs.push(MultiStartRun {
+                start_index,
+                result,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(start_index: usize, status: i32, objective: f64) -> MultiStartRun {
+        let mut result = OptimizationResult {
+            status,
+            ..Default::default()
+        };
+        result.solution.objective = objective;
+        MultiStartRun {
+            start_index,
+            result: Ok(result),
+        }
+    }
+
+    #[test]
+    fn multi_start_result_from_runs_sorts_and_selects_best_feasible() {
+        let runs = vec![
+            MultiStartRun {
+                start_index: 0,
+                result: Err("error".into()),
+            },
+            run(1, -1, -10.0),
+            run(2, 0, 3.0),
+            run(3, 1, 2.0),
+        ];
+
+        let result = MultiStartResult::from_runs(runs);
+
+        let order: Vec<_> = result.runs.iter().map(|r| r.start_index).collect();
+        assert_eq!(vec![3, 2, 1, 0], order);
+        assert_eq!(Some(0), result.best);
+        assert_eq!(3, result.best_run().unwrap().start_index);
+    }
+
+    #[test]
+    fn multi_start_result_from_runs_has_no_best_without_feasible_runs() {
+        let result = MultiStartResult::from_runs(vec![run(0, -1, 1.0)]);
+
+        assert_eq!(None, result.best);
+        assert!(result.best_result().is_none());
+    }
+}
@@ -0,0 +1,366 @@
+//! # Ipopt Bindgen nalgebra Interop
+//!
+//! Defines integration with the [nalgebra](https://nalgebra.org) and `nalgebra-sparse` crates,
+//! enabled by the `nalgebra` crate feature:
+//!
+//! - `NalgebraTnlp`, a problem trait that receives zero-copy `DVectorView`s instead of slices,
+//!   which is optimized through `NalgebraAdapter`.
+//! - Zero-copy views of the fields of a `Solution`.
+//! - Conversions between `CompressedMatrix`/`SparsityPattern` and the `nalgebra-sparse` CSR, CSC
+//!   and COO matrices.
+
+use crate::{
+    results::Solution,
+    sparsity::{CompressedFormat, CompressedMatrix, SparsityPattern, TripletBuilder},
+    tnlp::{InitialSolution, IntermediateData, OptionValue, ProblemSize, Tnlp, UserScaling},
+};
+use nalgebra::{DVectorView, DVectorViewMut};
+use nalgebra_sparse::{CooMatrix, CscMatrix, CsrMatrix};
+use std::error::Error;
+
+/// A trait for NLPs that use nalgebra vectors.
+///
+/// The methods have the same semantics as those of `Tnlp`, but the dense vectors are passed as
+/// zero-copy views of the Ipopt arrays.
+pub trait NalgebraTnlp {
+    /// Gets the dimensions of the problem.
+    fn get_nlp_info(&self) -> ProblemSize;
+
+    /// Gets the bounds on the variables and constraints.
+    fn get_bounds_info(
+        &self,
+        x_l: DVectorViewMut<f64>,
+        x_u: DVectorViewMut<f64>,
+        g_l: DVectorViewMut<f64>,
+        g_u: DVectorViewMut<f64>,
+    );
+
+    /// Gets the problem scaling parameters.
+    fn get_scaling(&self) -> UserScaling {
+        UserScaling::default()
+    }
+
+    /// Gets the starting point for the optimization.
+    fn get_starting_point(&self) -> InitialSolution;
+
+    /// Evaluates the objective function.
+    fn eval_f(&mut self, x: DVectorView<f64>, obj_value: &mut f64) -> bool;
+
+    /// Evaluates the gradient of the objective function.
+    fn eval_grad_f(&mut self, x: DVectorView<f64>, grad_f: DVectorViewMut<f64>) -> bool;
+
+    /// Evaluates the constraint functions.
+    fn eval_g(&mut self, x: DVectorView<f64>, g: DVectorViewMut<f64>) -> bool;
+
+    /// Gets the sparsity structure of the Jacobian of the constraints.
+    fn get_jacobian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]);
+
+    /// Evaluates the non-zero values of the Jacobian of the constraints.
+    fn eval_jac_g(&mut self, x: DVectorView<f64>, values: DVectorViewMut<f64>) -> bool;
+
+    /// Gets the sparsity structure of the Hessian of the Lagrangian.
+    fn get_hessian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]);
+
+    /// Evaluates the non-zero values of the Hessian of the Lagrangian.
+    fn eval_h(
+        &mut self,
+        x: DVectorView<f64>,
+        obj_factor: f64,
+        lambda: DVectorView<f64>,
+        values: DVectorViewMut<f64>,
+    ) -> bool;
+
+    /// User code that runs at each iteration.
+    ///
+    /// Return `false` to terminate the optimization.
+    fn intermediate(&mut self, _data: IntermediateData) -> bool {
+        true
+    }
+
+    /// Gets the names of the variables, which are used in diagnostics and reports.
+    fn variable_names(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Gets the names of the constraints, which are used in diagnostics and reports.
+    fn constraint_names(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Gets the Ipopt options that suit the structure of the problem.
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        Vec::new()
+    }
+}
+
+/// Adapts a `NalgebraTnlp` into a `Tnlp`.
+#[derive(Debug, Default, Clone)]
+pub struct NalgebraAdapter<P>(pub P);
+
+/// Creates a view of a slice.
+fn view(values: &[f64]) -> DVectorView<'_, f64> {
+    DVectorView::from_slice(values, values.len())
+}
+
+/// Creates a mutable view of a slice.
+fn view_mut(values: &mut [f64]) -> DVectorViewMut<'_, f64> {
+    let len = values.len();
+    DVectorViewMut::from_slice(values, len)
+}
+
+impl<P: NalgebraTnlp> Tnlp for NalgebraAdapter<P> {
+    fn get_nlp_info(&self) -> ProblemSize {
+        self.0.get_nlp_info()
+    }
+
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
+        self.0
+            .get_bounds_info(view_mut(x_l), view_mut(x_u), view_mut(g_l), view_mut(g_u));
+    }
+
+    fn get_scaling(&self) -> UserScaling {
+        self.0.get_scaling()
+    }
+
+    fn get_starting_point(&self) -> InitialSolution {
+        self.0.get_starting_point()
+    }
+
+    fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+        self.0.eval_f(view(x), obj_value)
+    }
+
+    fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+        self.0.eval_grad_f(view(x), view_mut(grad_f))
+    }
+
+    fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+        self.0.eval_g(view(x), view_mut(g))
+    }
+
+    fn get_jacobian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.0.get_jacobian_sparsity(n, m, i_row, j_col);
+    }
+
+    fn eval_jac_g(&mut self, x: &[f64], _m: i32, values: &mut [f64]) -> bool {
+        self.0.eval_jac_g(view(x), view_mut(values))
+    }
+
+    fn get_hessian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.0.get_hessian_sparsity(n, m, i_row, j_col);
+    }
+
+    fn eval_h(
+        &mut self,
+        x: &[f64],
+        obj_factor: f64,
+        lambda: &[f64],
+        _m: i32,
+        values: &mut [f64],
+    ) -> bool {
+        self.0
+            .eval_h(view(x), obj_factor, view(lambda), view_mut(values))
+    }
+
+    fn intermediate(&mut self, data: IntermediateData) -> bool {
+        self.0.intermediate(data)
+    }
+
+    fn variable_names(&self) -> Option<Vec<String>> {
+        self.0.variable_names()
+    }
+
+    fn constraint_names(&self) -> Option<Vec<String>> {
+        self.0.constraint_names()
+    }
+
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        self.0.default_options()
+    }
+}
+
+impl Solution {
+    /// Gets a view of the final values of the optimization variables.
+    #[must_use]
+    pub fn x_dvector(&self) -> DVectorView<'_, f64> {
+        view(&self.x)
+    }
+
+    /// Gets a view of the final values of the constraint functions.
+    #[must_use]
+    pub fn constraints_dvector(&self) -> DVectorView<'_, f64> {
+        view(&self.constraints)
+    }
+
+    /// Gets a view of the final values of the constraint multipliers.
+    #[must_use]
+    pub fn lambda_dvector(&self) -> DVectorView<'_, f64> {
+        view(&self.lambda)
+    }
+
+    /// Gets a view of the final values of the lower bound multipliers.
+    #[must_use]
+    pub fn z_l_dvector(&self) -> DVectorView<'_, f64> {
+        view(&self.z_l)
+    }
+
+    /// Gets a view of the final values of the upper bound multipliers.
+    #[must_use]
+    pub fn z_u_dvector(&self) -> DVectorView<'_, f64> {
+        view(&self.z_u)
+    }
+}
+
+impl From<&CsrMatrix<f64>> for CompressedMatrix {
+    fn from(matrix: &CsrMatrix<f64>) -> Self {
+        CompressedMatrix {
+            format: CompressedFormat::Row,
+            rows: matrix.nrows(),
+            columns: matrix.ncols(),
+            offsets: matrix.row_offsets().to_vec(),
+            indices: matrix.col_indices().to_vec(),
+            values: matrix.values().to_vec(),
+        }
+    }
+}
+
+impl From<&CscMatrix<f64>> for CompressedMatrix {
+    fn from(matrix: &CscMatrix<f64>) -> Self {
+        CompressedMatrix {
+            format: CompressedFormat::Column,
+            rows: matrix.nrows(),
+            columns: matrix.ncols(),
+            offsets: matrix.col_offsets().to_vec(),
+            indices: matrix.row_indices().to_vec(),
+            values: matrix.values().to_vec(),
+        }
+    }
+}
+
+/// Duplicate entries of the COO matrix are summed.
+impl From<&CooMatrix<f64>> for CompressedMatrix {
+    fn from(matrix: &CooMatrix<f64>) -> Self {
+        CompressedMatrix::from(&CsrMatrix::from(matrix))
+    }
+}
+
+impl TryFrom<CompressedMatrix> for CsrMatrix<f64> {
+    type Error = Box<dyn Error>;
+
+    fn try_from(matrix: CompressedMatrix) -> Result<Self, Self::Error> {
+        match matrix.format {
+            CompressedFormat::Row => Ok(CsrMatrix::try_from_csr_data(
+                matrix.rows,
+                matrix.columns,
+                matrix.offsets,
+                matrix.indices,
+                matrix.values,
+            )?),
+            CompressedFormat::Column => Ok(CsrMatrix::from(&CscMatrix::try_from(matrix)?)),
+        }
+    }
+}
+
+impl TryFrom<CompressedMatrix> for CscMatrix<f64> {
+    type Error = Box<dyn Error>;
+
+    fn try_from(matrix: CompressedMatrix) -> Result<Self, Self::Error> {
+        match matrix.format {
+            CompressedFormat::Column => Ok(CscMatrix::try_from_csc_data(
+                matrix.rows,
+                matrix.columns,
+                matrix.offsets,
+                matrix.indices,
+                matrix.values,
+            )?),
+            CompressedFormat::Row => Ok(CscMatrix::from(&CsrMatrix::try_from(matrix)?)),
+        }
+    }
+}
+
+impl SparsityPattern {
+    /// Creates a pattern with the structure of a COO matrix, with entries in the same order so
+    /// that the values of the matrix are already in slot order.
+    ///
+    /// # Parameters
+    ///
+    /// - `matrix` - The COO matrix.
+    ///
+    /// # Errors
+    ///
+    /// - The COO matrix contains duplicate entries.
+    /// - The dimensions or number of entries are not representable as an `i32`.
+    pub fn from_coo(matrix: &CooMatrix<f64>) -> Result<Self, Box<dyn Error>> {
+        let mut builder = TripletBuilder::new(matrix.nrows(), matrix.ncols());
+        for (row, column, _) in matrix.triplet_iter() {
+            let nnz = builder.nnz();
+            if builder.entry(row, column).index() != nnz {
+                return Err(format!("Entry ({row}, {column}) is duplicated.").into());
+            }
+        }
+        builder.build()
+    }
+
+    /// Converts values in slot order to a COO matrix.
+    ///
+    /// # Parameters
+    ///
+    /// - `values` - The values of the entries, in slot order.
+    ///
+    /// # Errors
+    ///
+    /// The number of values does not match the number of entries.
+    pub fn to_coo(&self, values: &[f64]) -> Result<CooMatrix<f64>, Box<dyn Error>> {
+        let (rows, columns) = self.entries().iter().copied().unzip();
+        Ok(CooMatrix::try_from_triplets(
+            self.rows(),
+            self.columns(),
+            rows,
+            columns,
+            values.to_vec(),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compressed_matrix_converts_to_and_from_nalgebra_sparse() {
+        let coo =
+            CooMatrix::try_from_triplets(2, 3, vec![1, 0, 1], vec![2, 1, 0], vec![3.0, 1.0, 2.0])
+                .unwrap();
+        let pattern = SparsityPattern::from_coo(&coo).unwrap();
+
+        let csr = CsrMatrix::try_from(
+            pattern
+                .to_compressed(&[3.0, 1.0, 2.0], CompressedFormat::Column)
+                .unwrap(),
+        )
+        .unwrap();
+        let mut values = vec![0.0; 3];
+        pattern
+            .scatter_compressed(&CompressedMatrix::from(&csr), &mut values)
+            .unwrap();
+
+        assert_eq!(&[0, 1, 3], csr.row_offsets());
+        assert_eq!(&[1, 0, 2], csr.col_indices());
+        assert_eq!(vec![3.0, 1.0, 2.0], values);
+        assert_eq!(coo, pattern.to_coo(&values).unwrap());
+    }
+
+    #[test]
+    fn solution_dvector_views_fields() {
+        let solution = Solution {
+            x: vec![1.0, 2.0],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![1.0, 2.0],
+            solution.x_dvector().iter().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(0, solution.lambda_dvector().len());
+    }
+}
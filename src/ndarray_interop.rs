@@ -0,0 +1,293 @@
+//! # Ipopt Bindgen ndarray Interop
+//!
+//! Defines integration with the [ndarray](https://github.com/rust-ndarray/ndarray) crate, enabled
+//! by the `ndarray` crate feature:
+//!
+//! - `NdarrayTnlp`, a problem trait that receives zero-copy `ArrayView1`s instead of slices, which
+//!   is optimized through `NdarrayAdapter`.
+//! - Zero-copy views of the fields of a `Solution`.
+
+use crate::{
+    results::Solution,
+    tnlp::{InitialSolution, IntermediateData, OptionValue, ProblemSize, Tnlp, UserScaling},
+};
+use ndarray::{ArrayView1, ArrayViewMut1};
+
+/// A trait for NLPs that use ndarray arrays.
+///
+/// The methods have the same semantics as those of `Tnlp`, but the dense vectors are passed as
+/// zero-copy views of the Ipopt arrays.
+pub trait NdarrayTnlp {
+    /// Gets the dimensions of the problem.
+    fn get_nlp_info(&self) -> ProblemSize;
+
+    /// Gets the bounds on the variables and constraints.
+    fn get_bounds_info(
+        &self,
+        x_l: ArrayViewMut1<f64>,
+        x_u: ArrayViewMut1<f64>,
+        g_l: ArrayViewMut1<f64>,
+        g_u: ArrayViewMut1<f64>,
+    );
+
+    /// Gets the problem scaling parameters.
+    fn get_scaling(&self) -> UserScaling {
+        UserScaling::default()
+    }
+
+    /// Gets the starting point for the optimization.
+    fn get_starting_point(&self) -> InitialSolution;
+
+    /// Evaluates the objective function.
+    fn eval_f(&mut self, x: ArrayView1<f64>, obj_value: &mut f64) -> bool;
+
+    /// Evaluates the gradient of the objective function.
+    fn eval_grad_f(&mut self, x: ArrayView1<f64>, grad_f: ArrayViewMut1<f64>) -> bool;
+
+    /// Evaluates the constraint functions.
+    fn eval_g(&mut self, x: ArrayView1<f64>, g: ArrayViewMut1<f64>) -> bool;
+
+    /// Gets the sparsity structure of the Jacobian of the constraints.
+    fn get_jacobian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]);
+
+    /// Evaluates the non-zero values of the Jacobian of the constraints.
+    fn eval_jac_g(&mut self, x: ArrayView1<f64>, values: ArrayViewMut1<f64>) -> bool;
+
+    /// Gets the sparsity structure of the Hessian of the Lagrangian.
+    fn get_hessian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]);
+
+    /// Evaluates the non-zero values of the Hessian of the Lagrangian.
+    fn eval_h(
+        &mut self,
+        x: ArrayView1<f64>,
+        obj_factor: f64,
+        lambda: ArrayView1<f64>,
+        values: ArrayViewMut1<f64>,
+    ) -> bool;
+
+    /// User code that runs at each iteration.
+    ///
+    /// Return `false` to terminate the optimization.
+    fn intermediate(&mut self, _data: IntermediateData) -> bool {
+        true
+    }
+
+    /// Gets the names of the variables, which are used in diagnostics and reports.
+    fn variable_names(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Gets the names of the constraints, which are used in diagnostics and reports.
+    fn constraint_names(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Gets the Ipopt options that suit the structure of the problem.
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        Vec::new()
+    }
+}
+
+/// Adapts an `NdarrayTnlp` into a `Tnlp`.
+#[derive(Debug, Default, Clone)]
+pub struct NdarrayAdapter<P>(pub P);
+
+impl<P: NdarrayTnlp> Tnlp for NdarrayAdapter<P> {
+    fn get_nlp_info(&self) -> ProblemSize {
+        self.0.get_nlp_info()
+    }
+
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
+        self.0
+            .get_bounds_info(x_l.into(), x_u.into(), g_l.into(), g_u.into());
+    }
+
+    fn get_scaling(&self) -> UserScaling {
+        self.0.get_scaling()
+    }
+
+    fn get_starting_point(&self) -> InitialSolution {
+        self.0.get_starting_point()
+    }
+
+    fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+        self.0.eval_f(x.into(), obj_value)
+    }
+
+    fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+        self.0.eval_grad_f(x.into(), grad_f.into())
+    }
+
+    fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+        self.0.eval_g(x.into(), g.into())
+    }
+
+    fn get_jacobian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.0.get_jacobian_sparsity(n, m, i_row, j_col);
+    }
+
+    fn eval_jac_g(&mut self, x: &[f64], _m: i32, values: &mut [f64]) -> bool {
+        self.0.eval_jac_g(x.into(), values.into())
+    }
+
+    fn get_hessian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.0.get_hessian_sparsity(n, m, i_row, j_col);
+    }
+
+    fn eval_h(
+        &mut self,
+        x: &[f64],
+        obj_factor: f64,
+        lambda: &[f64],
+        _m: i32,
+        values: &mut [f64],
+    ) -> bool {
+        self.0
+            .eval_h(x.into(), obj_factor, lambda.into(), values.into())
+    }
+
+    fn intermediate(&mut self, data: IntermediateData) -> bool {
+        self.0.intermediate(data)
+    }
+
+    fn variable_names(&self) -> Option<Vec<String>> {
+        self.0.variable_names()
+    }
+
+    fn constraint_names(&self) -> Option<Vec<String>> {
+        self.0.constraint_names()
+    }
+
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        self.0.default_options()
+    }
+}
+
+impl Solution {
+    /// Gets a view of the final values of the optimization variables.
+    #[must_use]
+    pub fn x_array(&self) -> ArrayView1<'_, f64> {
+        self.x.as_slice().into()
+    }
+
+    /// Gets a view of the final values of the constraint functions.
+    #[must_use]
+    pub fn constraints_array(&self) -> ArrayView1<'_, f64> {
+        self.constraints.as_slice().into()
+    }
+
+    /// Gets a view of the final values of the constraint multipliers.
+    #[must_use]
+    pub fn lambda_array(&self) -> ArrayView1<'_, f64> {
+        self.lambda.as_slice().into()
+    }
+
+    /// Gets a view of the final values of the lower bound multipliers.
+    #[must_use]
+    pub fn z_l_array(&self) -> ArrayView1<'_, f64> {
+        self.z_l.as_slice().into()
+    }
+
+    /// Gets a view of the final values of the upper bound multipliers.
+    #[must_use]
+    pub fn z_u_array(&self) -> ArrayView1<'_, f64> {
+        self.z_u.as_slice().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// min x0^2 + x1^2, unconstrained.
+    struct SumOfSquares;
+
+    impl NdarrayTnlp for SumOfSquares {
+        fn get_nlp_info(&self) -> ProblemSize {
+            ProblemSize {
+                n: 2,
+                m: 0,
+                nnz_jac: 0,
+                nnz_hess: 2,
+            }
+        }
+
+        fn get_bounds_info(
+            &self,
+            _x_l: ArrayViewMut1<f64>,
+            _x_u: ArrayViewMut1<f64>,
+            _g_l: ArrayViewMut1<f64>,
+            _g_u: ArrayViewMut1<f64>,
+        ) {
+        }
+
+        fn get_starting_point(&self) -> InitialSolution {
+            InitialSolution::from_variables(vec![1.0, 1.0])
+        }
+
+        fn eval_f(&mut self, x: ArrayView1<f64>, obj_value: &mut f64) -> bool {
+            *obj_value = x.dot(&x);
+            true
+        }
+
+        fn eval_grad_f(&mut self, x: ArrayView1<f64>, mut grad_f: ArrayViewMut1<f64>) -> bool {
+            grad_f.assign(&(&x * 2.0));
+            true
+        }
+
+        fn eval_g(&mut self, _x: ArrayView1<f64>, _g: ArrayViewMut1<f64>) -> bool {
+            true
+        }
+
+        fn get_jacobian_sparsity(
+            &mut self,
+            _n: i32,
+            _m: i32,
+            _i_row: &mut [i32],
+            _j_col: &mut [i32],
+        ) {
+        }
+
+        fn eval_jac_g(&mut self, _x: ArrayView1<f64>, _values: ArrayViewMut1<f64>) -> bool {
+            true
+        }
+
+        fn get_hessian_sparsity(&mut self, _n: i32, _m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+            i_row.copy_from_slice(&[0, 1]);
+            j_col.copy_from_slice(&[0, 1]);
+        }
+
+        fn eval_h(
+            &mut self,
+            _x: ArrayView1<f64>,
+            obj_factor: f64,
+            _lambda: ArrayView1<f64>,
+            mut values: ArrayViewMut1<f64>,
+        ) -> bool {
+            values.fill(2.0 * obj_factor);
+            true
+        }
+    }
+
+    #[test]
+    fn ndarray_adapter_evaluates_through_views() {
+        let mut adapter = NdarrayAdapter(SumOfSquares);
+        let (mut obj_value, mut grad_f) = (0.0, vec![0.0; 2]);
+
+        assert!(adapter.eval_f(&[1.0, 2.0], &mut obj_value));
+        assert!(adapter.eval_grad_f(&[1.0, 2.0], &mut grad_f));
+
+        assert_eq!(vec![5.0, 2.0, 4.0], [vec![obj_value], grad_f].concat());
+    }
+
+    #[test]
+    fn solution_array_views_fields() {
+        let solution = Solution {
+            z_u: vec![1.0, 2.0],
+            ..Default::default()
+        };
+
+        assert_eq!(vec![1.0, 2.0], solution.z_u_array().to_vec());
+        assert_eq!(0, solution.x_array().len());
+    }
+}
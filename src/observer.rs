@@ -0,0 +1,124 @@
+//! # Ipopt Bindgen Observer
+//!
+//! Defines a trait for monitoring the progress of optimizations independently of the problem being
+//! optimized, e.g. for dashboards, loggers and early stopping rules.
+
+use crate::tnlp::IntermediateData;
+use std::{
+    fmt,
+    sync::{Arc, mpsc::Sender},
+};
+
+/// A trait for types that are notified of the progress of an optimization.
+///
+/// Observers are registered on an `Application` with `add_observer`, and are notified at every
+/// iteration before the problem's own `Tnlp::intermediate` callback. Since an `Application` can be
+/// shared between threads, observers must be `Send + Sync` and are only given shared access to
+/// themselves. Use interior mutability to record state.
+///
+/// This trait is implemented for closures taking `&IntermediateData` and returning `bool`, and for
+/// `mpsc::Sender<IntermediateData>`, which forwards the data of every iteration to a channel.
+pub trait Observer: Send + Sync {
+    /// User code that runs at each iteration.
+    ///
+    /// # Parameters
+    /// - `data` - The intermediate data at the current iteration.
+    ///
+    /// Return `false` to terminate the optimization.
+    fn on_iteration(&self, data: &IntermediateData) -> bool;
+}
+
+impl<F> Observer for F
+where
+    F: Fn(&IntermediateData) -> bool + Send + Sync,
+{
+    fn on_iteration(&self, data: &IntermediateData) -> bool {
+        self(data)
+    }
+}
+
+/// Sends the data of every iteration to the channel.
+///
+/// Sending errors are ignored, so the optimization continues if the receiver is dropped.
+impl Observer for Sender<IntermediateData> {
+    fn on_iteration(&self, data: &IntermediateData) -> bool {
+        let _ = self.send(data.clone());
+        true
+    }
+}
+
+/// The observers registered on an `Application`.
+#[derive(Default, Clone)]
+pub(crate) struct Observers(Vec<Arc<dyn Observer>>);
+
+impl Observers {
+    pub fn push(&mut self, observer: Arc<dyn Observer>) {
+        self.0.push(observer);
+    }
+
+    /// Notifies every observer of the iteration.
+    ///
+    /// # Returns
+    ///
+    /// `false` if any observer requested that the optimization stops, `true` otherwise.
+    pub fn notify(&self, data: &IntermediateData) -> bool {
+        // Every observer is notified, even after one has requested a stop.
+        let mut should_continue = true;
+        for observer in &self.0 {
+            should_continue &= observer.on_iteration(data);
+        }
+        should_continue
+    }
+}
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Observers({})", self.0.len())
+    }
+}
+
+/// Observers are equal if they are the same instances, in the same order.
+impl PartialEq for Observers {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len() && self.0.iter().zip(&other.0).all(|(a, b)| Arc::ptr_eq(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        mpsc,
+    };
+
+    #[test]
+    fn observers_notify_calls_every_observer() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let mut observers = Observers::default();
+        for should_continue in [false, true] {
+            let calls = Arc::clone(&calls);
+            observers.push(Arc::new(move |_: &IntermediateData| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                should_continue
+            }));
+        }
+
+        let should_continue = observers.notify(&IntermediateData::default());
+
+        assert!(!should_continue);
+        assert_eq!(2, calls.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn sender_observer_sends_data() {
+        let (sender, receiver) = mpsc::channel();
+        let data = IntermediateData {
+            iter_count: 3,
+            ..Default::default()
+        };
+
+        assert!(sender.on_iteration(&data));
+        assert_eq!(data, receiver.recv().unwrap());
+    }
+}
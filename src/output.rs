@@ -0,0 +1,199 @@
+//! # Ipopt Bindgen Output
+//!
+//! Defines how the console output of Ipopt (the iteration table, statistics, etc.) is handled.
+//!
+//! Ipopt writes its output directly to `stdout`, which interleaves badly with structured logging.
+//! Instead, the output can be redirected to a temporary file via `OpenIpoptOutputFile`, which is
+//! read back once the optimization has finished. The file is created securely, with a random name
+//! and exclusive access, before Ipopt opens it.
+
+#[allow(clippy::wildcard_imports)]
+use crate::c_interface::*;
+use std::{error::Error, ffi::CString, fs, io};
+use tempfile::NamedTempFile;
+
+/// Where the console output of Ipopt is sent.
+///
+/// The `Log` and `Tracing` destinations are always available, so that enabling a crate feature
+/// does not change this type, but optimizations fail if the corresponding feature is not enabled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum OutputDestination {
+    /// Ipopt prints directly to `stdout`. This is the Ipopt default.
+    #[default]
+    Console,
+
+    /// The output is captured into `OptimizationResult::output`.
+    Capture,
+
+    /// The output is captured, and each line is also forwarded to the `log` crate at the `info`
+    /// level with the `ipopt` target. Requires the crate feature `log`.
+    Log,
+
+    /// The output is captured, and each line is also forwarded to the `tracing` crate at the
+    /// `info` level with the `ipopt` target. Requires the crate feature `tracing`.
+    Tracing,
+}
+
+impl OutputDestination {
+    /// Checks whether the output is captured rather than printed to the console.
+    #[must_use]
+    pub fn is_captured(self) -> bool {
+        self != OutputDestination::Console
+    }
+
+    /// Checks that the crate feature needed by the destination is enabled.
+    ///
+    /// # Errors
+    ///
+    /// The destination forwards to a crate whose feature is not enabled.
+    pub(crate) fn validate(self) -> Result<(), Box<dyn Error>> {
+        match self {
+            OutputDestination::Log if !cfg!(feature = "log") => {
+                Err("Forwarding output to `log` requires the crate feature `log`.".into())
+            }
+            OutputDestination::Tracing if !cfg!(feature = "tracing") => {
+                Err("Forwarding output to `tracing` requires the crate feature `tracing`.".into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Forwards the captured output to the destination, line by line. Blank lines are skipped.
+    pub(crate) fn forward(self, output: &str) {
+        let lines = output.lines().filter(|line| !line.trim().is_empty());
+        match self {
+            OutputDestination::Console | OutputDestination::Capture => drop(lines),
+            OutputDestination::Log => {
+                #[cfg(feature = "log")]
+                lines.for_each(|line| log::info!(target: "ipopt", "{line}"));
+                #[cfg(not(feature = "log"))]
+                drop(lines);
+            }
+            OutputDestination::Tracing => {
+                #[cfg(feature = "tracing")]
+                lines.for_each(|line| tracing::info!(target: "ipopt", "{line}"));
+                #[cfg(not(feature = "tracing"))]
+                drop(lines);
+            }
+        }
+    }
+}
+
+/// A temporary file that Ipopt writes its output to, which is deleted when dropped.
+///
+/// The file is created with a random name and exclusive access, so that other users of a shared
+/// temporary directory can neither redirect Ipopt's writes nor read the output. It must be kept
+/// alive until the output has been read.
+#[derive(Debug)]
+pub(crate) struct OutputFile {
+    file: NamedTempFile,
+}
+
+impl OutputFile {
+    /// Creates a new output file in the system temporary directory.
+    ///
+    /// # Errors
+    ///
+    /// The file could not be created.
+    pub fn new() -> io::Result<Self> {
+        let file = tempfile::Builder::new()
+            .prefix("ipopt_bindgen_")
+            .suffix(".out")
+            .tempfile()?;
+        Ok(OutputFile { file })
+    }
+
+    /// Asks Ipopt to write its output to this file.
+    ///
+    /// # Parameters
+    ///
+    /// - `ipopt_problem` - The C problem.
+    /// - `print_level` - The Ipopt print level for the file.
+    pub fn open(
+        &self,
+        ipopt_problem: IpoptProblem,
+        print_level: i32,
+    ) -> Result<(), Box<dyn Error>> {
+        let path = self
+            .file
+            .path()
+            .to_str()
+            .ok_or("The temporary output file path is not valid UTF-8.")?;
+        let file_name = CString::new(path)?;
+
+        let opened =
+            unsafe { OpenIpoptOutputFile(ipopt_problem, file_name.into_raw(), print_level) };
+        if opened {
+            Ok(())
+        } else {
+            Err(format!("Ipopt could not open the output file `{path}`.").into())
+        }
+    }
+
+    /// Reads the contents of the file.
+    ///
+    /// Ipopt only flushes and closes the file when the problem is freed, so this should be called
+    /// after `FreeIpoptProblem`.
+    pub fn read(&self) -> Result<String, Box<dyn Error>> {
+        let bytes = fs::read(self.file.path())?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_file_new_creates_unique_paths() {
+        let a = OutputFile::new().unwrap();
+        let b = OutputFile::new().unwrap();
+
+        assert_ne!(a.file.path(), b.file.path());
+        assert!(a.file.path().exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn output_file_new_is_only_accessible_by_owner() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let file = OutputFile::new().unwrap();
+        let mode = fs::metadata(file.file.path()).unwrap().permissions().mode();
+
+        assert_eq!(0, mode & 0o077);
+    }
+
+    #[test]
+    fn output_file_drop_removes_file() {
+        let file = OutputFile::new().unwrap();
+        let path = file.file.path().to_path_buf();
+        fs::write(&path, "Number of Iterations....: 8").unwrap();
+
+        assert_eq!("Number of Iterations....: 8", file.read().unwrap());
+
+        drop(file);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn output_destination_is_captured_for_non_console_destinations() {
+        assert!(!OutputDestination::Console.is_captured());
+        assert!(OutputDestination::Capture.is_captured());
+    }
+
+    #[test]
+    fn output_destination_validate_requires_feature() {
+        assert!(OutputDestination::Capture.validate().is_ok());
+        assert_eq!(
+            cfg!(feature = "log"),
+            OutputDestination::Log.validate().is_ok()
+        );
+        assert_eq!(
+            cfg!(feature = "tracing"),
+            OutputDestination::Tracing.validate().is_ok()
+        );
+    }
+}
@@ -0,0 +1,456 @@
+//! # Ipopt Bindgen Quadratic
+//!
+//! Defines a quadratic programming (QP) front-end, which implements `Tnlp` for problems of the form
+//!
+//! ```text
+//! minimize    ½ xᵀQx + cᵀx
+//! subject to  g_l ≤ Ax ≤ g_u
+//!             x_l ≤ x ≤ x_u
+//! ```
+//!
+//! with exact, constant derivatives, and which sets the Ipopt options that suit QPs.
+
+use crate::{
+    bounds::Bounds,
+    sparsity::{SparsityPattern, TripletBuilder, dimension_to_i32},
+    tnlp::{InitialSolution, OptionValue, ProblemNames, ProblemSize, Tnlp},
+};
+use std::error::Error;
+
+/// A quadratic program.
+///
+/// The matrices are given as `(row, column, value)` triplets, and duplicate triplets are summed.
+/// `Q` does not need to be symmetric, since only its symmetric part `½(Q + Qᵀ)` affects the
+/// objective, so either the full matrix or a single triangle of a symmetric matrix can be given,
+/// provided that the off-diagonal values of a single triangle are doubled.
+///
+/// Variables are free, constraints are unbounded, the starting point is zero and the problem is not
+/// assumed to be convex until set otherwise.
+///
+/// # Example
+///
+/// ```
+/// use ipopt_bindgen::{Bounds, QuadraticProblem};
+///
+/// // minimize x0² + x1² - x0 subject to x0 + x1 >= 1.
+/// let mut problem = QuadraticProblem::new(2, 1);
+/// problem
+///     .set_objective(&[(0, 0, 2.0), (1, 1, 2.0)], vec![-1.0, 0.0])
+///     .unwrap()
+///     .set_constraints(&[(0, 0, 1.0), (0, 1, 1.0)])
+///     .unwrap()
+///     .set_constraint_bounds(vec![Bounds::lower(1.0)])
+///     .unwrap()
+///     .set_convex(true);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuadraticProblem {
+    hessian: SparsityPattern,
+    hessian_values: Vec<f64>,
+    linear: Vec<f64>,
+    jacobian: SparsityPattern,
+    jacobian_values: Vec<f64>,
+    variable_bounds: Vec<Bounds>,
+    constraint_bounds: Vec<Bounds>,
+    starting_point: Vec<f64>,
+    names: ProblemNames,
+    convex: bool,
+}
+
+impl QuadraticProblem {
+    /// Creates a new `QuadraticProblem` with a zero objective and zero constraint matrix.
+    ///
+    /// # Parameters
+    ///
+    /// - `n` - The number of variables.
+    /// - `m` - The number of linear constraints, i.e. the number of rows of `A`.
+    ///
+    /// # Panics
+    ///
+    /// `n` or `m` is not representable as an `i32`.
+    #[must_use]
+    pub fn new(n: usize, m: usize) -> Self {
+        let pattern = |builder: TripletBuilder| {
+            builder
+                .build()
+                .expect("The problem dimensions are too large for Ipopt.")
+        };
+
+        QuadraticProblem {
+            hessian: pattern(TripletBuilder::lower_triangular(n)),
+            hessian_values: Vec::new(),
+            linear: vec![0.0; n],
+            jacobian: pattern(TripletBuilder::new(m, n)),
+            jacobian_values: Vec::new(),
+            variable_bounds: vec![Bounds::free(); n],
+            constraint_bounds: vec![Bounds::free(); m],
+            starting_point: vec![0.0; n],
+            names: ProblemNames::default(),
+            convex: false,
+        }
+    }
+
+    /// Gets the number of variables.
+    #[must_use]
+    pub fn number_of_variables(&self) -> usize {
+        self.linear.len()
+    }
+
+    /// Gets the number of linear constraints.
+    #[must_use]
+    pub fn number_of_constraints(&self) -> usize {
+        self.constraint_bounds.len()
+    }
+
+    /// Sets the objective `½ xᵀQx + cᵀx`.
+    ///
+    /// # Parameters
+    ///
+    /// - `q` - The triplets of the quadratic term `Q`.
+    /// - `c` - The linear term `c`.
+    ///
+    /// # Errors
+    ///
+    /// - A triplet is outside of the `n x n` matrix.
+    /// - The length of `c` is not `n`.
+    pub fn set_objective(
+        &mut self,
+        q: &[(usize, usize, f64)],
+        c: Vec<f64>,
+    ) -> Result<&mut Self, Box<dyn Error>> {
+        let n = self.number_of_variables();
+        check_length("linear objective coefficients", c.len(), n)?;
+
+        // The Hessian of ½ xᵀQx is ½(Q + Qᵀ), so each off-diagonal value is split between the
+        // lower and upper triangles, and only the lower one is stored.
+        let lower = q.iter().map(|&(row, column, value)| {
+            if row == column {
+                (row, column, value)
+            } else {
+                (row.max(column), row.min(column), 0.5 * value)
+            }
+        });
+        let (hessian, hessian_values) = assemble(TripletBuilder::lower_triangular(n), lower)?;
+
+        self.hessian = hessian;
+        self.hessian_values = hessian_values;
+        self.linear = c;
+        Ok(self)
+    }
+
+    /// Sets the constraint matrix `A`.
+    ///
+    /// # Parameters
+    ///
+    /// - `a` - The triplets of the `m x n` constraint matrix.
+    ///
+    /// # Errors
+    ///
+    /// A triplet is outside of the `m x n` matrix.
+    pub fn set_constraints(
+        &mut self,
+        a: &[(usize, usize, f64)],
+    ) -> Result<&mut Self, Box<dyn Error>> {
+        let builder = TripletBuilder::new(self.number_of_constraints(), self.number_of_variables());
+        let (jacobian, jacobian_values) = assemble(builder, a.iter().copied())?;
+
+        self.jacobian = jacobian;
+        self.jacobian_values = jacobian_values;
+        Ok(self)
+    }
+
+    /// Sets the bounds of the variables.
+    ///
+    /// # Parameters
+    ///
+    /// - `bounds` - The bounds of each variable.
+    ///
+    /// # Errors
+    ///
+    /// The number of bounds is not `n`.
+    pub fn set_variable_bounds(
+        &mut self,
+        bounds: Vec<Bounds>,
+    ) -> Result<&mut Self, Box<dyn Error>> {
+        check_length("variable bounds", bounds.len(), self.number_of_variables())?;
+        self.variable_bounds = bounds;
+        Ok(self)
+    }
+
+    /// Sets the bounds of the linear constraints.
+    ///
+    /// # Parameters
+    ///
+    /// - `bounds` - The bounds of each constraint.
+    ///
+    /// # Errors
+    ///
+    /// The number of bounds is not `m`.
+    pub fn set_constraint_bounds(
+        &mut self,
+        bounds: Vec<Bounds>,
+    ) -> Result<&mut Self, Box<dyn Error>> {
+        check_length(
+            "constraint bounds",
+            bounds.len(),
+            self.number_of_constraints(),
+        )?;
+        self.constraint_bounds = bounds;
+        Ok(self)
+    }
+
+    /// Sets the starting point of the optimization.
+    ///
+    /// # Parameters
+    ///
+    /// - `x` - The initial values of the variables.
+    ///
+    /// # Errors
+    ///
+    /// The length of `x` is not `n`.
+    pub fn set_starting_point(&mut self, x: Vec<f64>) -> Result<&mut Self, Box<dyn Error>> {
+        check_length("starting point values", x.len(), self.number_of_variables())?;
+        self.starting_point = x;
+        Ok(self)
+    }
+
+    /// Sets the names of the variables and constraints.
+    ///
+    /// # Parameters
+    ///
+    /// - `names` - The variable and constraint names.
+    pub fn set_names(&mut self, names: ProblemNames) -> &mut Self {
+        self.names = names;
+        self
+    }
+
+    /// Sets whether the problem is convex, i.e. whether `Q` is positive semidefinite.
+    ///
+    /// Convex problems enable Ipopt's `mehrotra_algorithm` option, which usually speeds up convex
+    /// QPs considerably but is unreliable for nonconvex ones. Problems are not assumed to be convex
+    /// by default, since convexity is not checked.
+    ///
+    /// # Parameters
+    ///
+    /// - `convex` - Whether the problem is convex.
+    pub fn set_convex(&mut self, convex: bool) -> &mut Self {
+        self.convex = convex;
+        self
+    }
+}
+
+/// Adds the triplets to the builder, summing duplicates, and builds the pattern and its values.
+fn assemble(
+    mut builder: TripletBuilder,
+    triplets: impl Iterator<Item = (usize, usize, f64)>,
+) -> Result<(SparsityPattern, Vec<f64>), Box<dyn Error>> {
+    let mut values = Vec::new();
+    for (row, column, value) in triplets {
+        let slot = builder.entry(row, column).index();
+        if slot == values.len() {
+            values.push(0.0);
+        }
+        values[slot] += value;
+    }
+    Ok((builder.build()?, values))
+}
+
+/// Checks that a vector has the expected length.
+fn check_length(what: &str, length: usize, expected: usize) -> Result<(), String> {
+    if length == expected {
+        Ok(())
+    } else {
+        Err(format!("Expected {expected} {what}, but got {length}."))
+    }
+}
+
+impl Tnlp for QuadraticProblem {
+    fn get_nlp_info(&self) -> ProblemSize {
+        ProblemSize {
+            n: dimension_to_i32(self.jacobian.columns()),
+            m: dimension_to_i32(self.jacobian.rows()),
+            nnz_jac: dimension_to_i32(self.jacobian.nnz()),
+            nnz_hess: dimension_to_i32(self.hessian.nnz()),
+        }
+    }
+
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
+        for ((bounds, lower), upper) in self.variable_bounds.iter().zip(x_l).zip(x_u) {
+            bounds.write_to(lower, upper);
+        }
+        for ((bounds, lower), upper) in self.constraint_bounds.iter().zip(g_l).zip(g_u) {
+            bounds.write_to(lower, upper);
+        }
+    }
+
+    fn get_starting_point(&self) -> InitialSolution {
+        InitialSolution::from_variables(self.starting_point.clone())
+    }
+
+    fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+        let quadratic: f64 = self
+            .hessian
+            .entries()
+            .iter()
+            .zip(&self.hessian_values)
+            .map(|(&(row, column), value)| {
+                let product = value * x[row] * x[column];
+                if row == column {
+                    0.5 * product
+                } else {
+                    product
+                }
+            })
+            .sum();
+        let linear: f64 = self.linear.iter().zip(x).map(|(c, x)| c * x).sum();
+
+        *obj_value = quadratic + linear;
+        true
+    }
+
+    fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+        grad_f.copy_from_slice(&self.linear);
+        for (&(row, column), value) in self.hessian.entries().iter().zip(&self.hessian_values) {
+            grad_f[row] += value * x[column];
+            if row != column {
+                grad_f[column] += value * x[row];
+            }
+        }
+        true
+    }
+
+    fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+        g.fill(0.0);
+        for (&(row, column), value) in self.jacobian.entries().iter().zip(&self.jacobian_values) {
+            g[row] += value * x[column];
+        }
+        true
+    }
+
+    fn get_jacobian_sparsity(&mut self, _n: i32, _m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.jacobian.write_indices(i_row, j_col);
+    }
+
+    fn eval_jac_g(&mut self, _x: &[f64], _m: i32, values: &mut [f64]) -> bool {
+        values.copy_from_slice(&self.jacobian_values);
+        true
+    }
+
+    fn get_hessian_sparsity(&mut self, _n: i32, _m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.hessian.write_indices(i_row, j_col);
+    }
+
+    fn eval_h(
+        &mut self,
+        _x: &[f64],
+        obj_factor: f64,
+        _lambda: &[f64],
+        _m: i32,
+        values: &mut [f64],
+    ) -> bool {
+        for (value, hessian) in values.iter_mut().zip(&self.hessian_values) {
+            *value = obj_factor * hessian;
+        }
+        true
+    }
+
+    fn variable_names(&self) -> Option<Vec<String>> {
+        self.names.variables.clone()
+    }
+
+    fn constraint_names(&self) -> Option<Vec<String>> {
+        self.names.constraints.clone()
+    }
+
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        let yes = || OptionValue::String("yes".to_string());
+        let mut options = vec![
+            ("jac_c_constant".to_string(), yes()),
+            ("jac_d_constant".to_string(), yes()),
+            ("hessian_constant".to_string(), yes()),
+        ];
+        if self.convex {
+            options.push(("mehrotra_algorithm".to_string(), yes()));
+        }
+        options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// minimize x0² + x0 x1 + x1² - x0 subject to 1 ≤ x0 + 2 x1 ≤ 2, with `Q` given in full.
+    fn problem() -> QuadraticProblem {
+        let mut problem = QuadraticProblem::new(2, 1);
+        problem
+            .set_objective(
+                &[(0, 0, 2.0), (0, 1, 1.0), (1, 0, 1.0), (1, 1, 2.0)],
+                vec![-1.0, 0.0],
+            )
+            .unwrap()
+            .set_constraints(&[(0, 0, 1.0), (0, 1, 2.0)])
+            .unwrap()
+            .set_constraint_bounds(vec![Bounds::range(1.0, 2.0)])
+            .unwrap();
+        problem
+    }
+
+    #[test]
+    fn quadratic_problem_evaluates_objective_and_constraints() {
+        let mut problem = problem();
+        let x = [1.0, 2.0];
+        let (mut f, mut grad_f, mut g) = (0.0, vec![0.0; 2], vec![0.0; 1]);
+
+        assert!(problem.eval_f(&x, &mut f));
+        assert!(problem.eval_grad_f(&x, &mut grad_f));
+        assert!(problem.eval_g(&x, &mut g));
+
+        // f = 1 + 2 + 4 - 1, ∇f = (2 + 2 - 1, 1 + 4), g = 1 + 4.
+        assert_eq!(vec![6.0, 3.0, 5.0, 5.0], [vec![f], grad_f, g].concat());
+    }
+
+    #[test]
+    fn quadratic_problem_stores_lower_triangle_of_symmetric_part() {
+        let mut problem = problem();
+        let (mut i_row, mut j_col, mut values) = ([0; 3], [0; 3], [0.0; 3]);
+
+        problem.get_hessian_sparsity(2, 1, &mut i_row, &mut j_col);
+        assert!(problem.eval_h(&[0.0, 0.0], 0.5, &[1.0], 1, &mut values));
+
+        assert_eq!([0, 1, 1], i_row);
+        assert_eq!([0, 0, 1], j_col);
+        assert_eq!(vec![1.0, 0.5, 1.0], values.to_vec());
+    }
+
+    #[test]
+    fn quadratic_problem_rejects_invalid_data() {
+        let mut problem = QuadraticProblem::new(2, 1);
+
+        assert!(problem.set_objective(&[(2, 0, 1.0)], vec![0.0; 2]).is_err());
+        assert!(problem.set_objective(&[], vec![0.0; 3]).is_err());
+        assert!(problem.set_constraints(&[(1, 0, 1.0)]).is_err());
+        assert!(problem.set_variable_bounds(vec![Bounds::free()]).is_err());
+    }
+
+    #[test]
+    fn quadratic_problem_default_options_follow_convexity() {
+        let mut problem = problem();
+        let names = |problem: &QuadraticProblem| {
+            problem
+                .default_options()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            vec!["jac_c_constant", "jac_d_constant", "hessian_constant"],
+            names(&problem)
+        );
+
+        problem.set_convex(true);
+
+        assert!(names(&problem).contains(&"mehrotra_algorithm".to_string()));
+    }
+}
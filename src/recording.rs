@@ -0,0 +1,1072 @@
+//! # Ipopt Bindgen Recording
+//!
+//! Defines a recorder, which captures a `Tnlp` and the evaluations of its callbacks during an
+//! optimization, and a replayer, which implements `Tnlp` from the recording.
+//!
+//! This allows a failing problem to be reproduced, e.g. in a test, without the code of the model
+//! that produced it. Ipopt is deterministic, so replaying a recording with the same options
+//! reproduces the same sequence of iterates. `Application::record` also records the options of the
+//! application, which the replayer applies as its default options.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use ipopt_bindgen::Tnlp;
+//! # fn problem() -> impl Tnlp { ipopt_bindgen::LinearProblem::new(1, 0) }
+//! use ipopt_bindgen::{Application, Recording, Replayer};
+//!
+//! let mut application = Application::new();
+//! application.set_numeric_option("tol", 1e-10);
+//! let (_, recording) = application.record(problem()).unwrap();
+//! recording.save("problem.recording").unwrap();
+//!
+//! // Later, e.g. in a test.
+//! let replayer = Replayer::new(Recording::load("problem.recording").unwrap()).unwrap();
+//! let result = Application::new().optimize_tnlp(replayer).unwrap();
+//! ```
+
+use crate::{
+    application::Application,
+    results::OptimizationResult,
+    tnlp::{
+        InitialSolution, IntermediateData, OptionValue, ProblemBounds, ProblemNames, ProblemSize,
+        Tnlp, UserScaling,
+    },
+};
+use std::{
+    error::Error,
+    fmt::Display,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+/// The header line of a recording file.
+const HEADER: &str = "ipopt_bindgen recording 1";
+
+/// A callback of a `Tnlp` that evaluates the problem at a point.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Callback {
+    /// `Tnlp::eval_f`.
+    Objective,
+
+    /// `Tnlp::eval_grad_f`.
+    ObjectiveGradient,
+
+    /// `Tnlp::eval_g`.
+    Constraints,
+
+    /// `Tnlp::eval_jac_g`.
+    Jacobian,
+
+    /// `Tnlp::eval_h`, with its factors.
+    Hessian {
+        /// The factor of the objective.
+        obj_factor: f64,
+
+        /// The constraint multipliers.
+        lambda: Vec<f64>,
+    },
+}
+
+/// A recorded evaluation of a `Tnlp` callback.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluation {
+    /// The callback that was evaluated.
+    pub callback: Callback,
+
+    /// The point at which the callback was evaluated.
+    pub x: Vec<f64>,
+
+    /// The values written by the callback. The objective is recorded as a single value.
+    pub values: Vec<f64>,
+
+    /// Whether the callback reported a successful evaluation.
+    pub success: bool,
+}
+
+/// A snapshot of a `Tnlp` and the evaluations of its callbacks.
+///
+/// A recording can be saved to and loaded from a line-based text file, in which values are written
+/// so that they are read back exactly.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Recording {
+    /// The dimensions of the problem.
+    pub size: ProblemSize,
+
+    /// The bounds of the problem.
+    pub bounds: ProblemBounds,
+
+    /// The starting point of the problem.
+    pub starting_point: InitialSolution,
+
+    /// The scaling of the problem.
+    pub scaling: UserScaling,
+
+    /// The default options of the problem, as given by `Tnlp::default_options`.
+    pub options: Vec<(String, OptionValue)>,
+
+    /// The options of the `Application` that optimized the problem, as given by
+    /// `Application::options`, which take precedence over the default options of the problem.
+    pub application_options: Vec<(String, OptionValue)>,
+
+    /// The names of the variables and constraints.
+    pub names: ProblemNames,
+
+    /// The row indices of the Jacobian sparsity pattern.
+    pub jacobian_rows: Vec<i32>,
+
+    /// The column indices of the Jacobian sparsity pattern.
+    pub jacobian_columns: Vec<i32>,
+
+    /// The row indices of the Hessian sparsity pattern.
+    pub hessian_rows: Vec<i32>,
+
+    /// The column indices of the Hessian sparsity pattern.
+    pub hessian_columns: Vec<i32>,
+
+    /// The evaluations of the callbacks, in the order they were made.
+    pub evaluations: Vec<Evaluation>,
+}
+
+impl Recording {
+    /// Records the description of a problem, and evaluates every callback at the starting point.
+    ///
+    /// The Hessian is evaluated with an objective factor of one and the initial constraint
+    /// multipliers, or multipliers of one if the starting point has none.
+    ///
+    /// # Parameters
+    ///
+    /// - `problem` - The problem to record.
+    ///
+    /// # Errors
+    ///
+    /// - The problem dimensions are not representable as a usize.
+    /// - The problem returns a different number of names than it has variables or constraints.
+    pub fn from_problem<P: Tnlp + ?Sized>(problem: &mut P) -> Result<Self, Box<dyn Error>> {
+        let size = problem.get_nlp_info();
+        let (n, m) = (usize::try_from(size.n)?, usize::try_from(size.m)?);
+        let (nnz_jac, nnz_hess) = (
+            usize::try_from(size.nnz_jac)?,
+            usize::try_from(size.nnz_hess)?,
+        );
+
+        let mut recording = Recording {
+            bounds: ProblemBounds::from_problem(problem)?,
+            starting_point: problem.get_starting_point(),
+            scaling: problem.get_scaling(),
+            options: problem.default_options(),
+            application_options: Vec::new(),
+            names: ProblemNames::from_problem(problem)?,
+            jacobian_rows: vec![0; nnz_jac],
+            jacobian_columns: vec![0; nnz_jac],
+            hessian_rows: vec![0; nnz_hess],
+            hessian_columns: vec![0; nnz_hess],
+            evaluations: Vec::new(),
+            size: size.clone(),
+        };
+        problem.get_jacobian_sparsity(
+            size.n,
+            size.m,
+            &mut recording.jacobian_rows,
+            &mut recording.jacobian_columns,
+        );
+        problem.get_hessian_sparsity(
+            size.n,
+            size.m,
+            &mut recording.hessian_rows,
+            &mut recording.hessian_columns,
+        );
+
+        let x = recording.starting_point.x.clone();
+        let lambda = (recording.starting_point.lambda.clone()).unwrap_or_else(|| vec![1.0; m]);
+        let mut record = |callback, length, evaluate: &mut dyn FnMut(&mut [f64]) -> bool| {
+            let mut values = vec![0.0; length];
+            let success = evaluate(&mut values);
+            recording.evaluations.push(Evaluation {
+                callback,
+                x: x.clone(),
+                values,
+                success,
+            });
+        };
+        record(Callback::Objective, 1, &mut |values| {
+            problem.eval_f(&x, &mut values[0])
+        });
+        record(Callback::ObjectiveGradient, n, &mut |values| {
+            problem.eval_grad_f(&x, values)
+        });
+        record(Callback::Constraints, m, &mut |values| {
+            problem.eval_g(&x, values)
+        });
+        record(Callback::Jacobian, nnz_jac, &mut |values| {
+            problem.eval_jac_g(&x, size.m, values)
+        });
+        let hessian = Callback::Hessian {
+            obj_factor: 1.0,
+            lambda: lambda.clone(),
+        };
+        record(hessian, nnz_hess, &mut |values| {
+            problem.eval_h(&x, 1.0, &lambda, size.m, values)
+        });
+
+        Ok(recording)
+    }
+
+    /// Saves the recording to a file.
+    ///
+    /// # Parameters
+    ///
+    /// - `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// The file cannot be created or written.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Loads a recording from a file.
+    ///
+    /// # Parameters
+    ///
+    /// - `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// - The file cannot be opened or read.
+    /// - The file is not a valid recording.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Writes the recording in the text format.
+    ///
+    /// # Parameters
+    ///
+    /// - `writer` - The destination of the recording.
+    ///
+    /// # Errors
+    ///
+    /// - Writing to `writer` fails.
+    /// - A name or string option value contains a line break, or an option name contains
+    ///   whitespace, so that it cannot be read back.
+    pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
+        let size = &self.size;
+        writeln!(writer, "{HEADER}")?;
+        write_line(
+            &mut writer,
+            "size",
+            &[size.n, size.m, size.nnz_jac, size.nnz_hess],
+        )?;
+        write_line(&mut writer, "x_l", &self.bounds.x_l)?;
+        write_line(&mut writer, "x_u", &self.bounds.x_u)?;
+        write_line(&mut writer, "g_l", &self.bounds.g_l)?;
+        write_line(&mut writer, "g_u", &self.bounds.g_u)?;
+
+        let start = &self.starting_point;
+        write_line(&mut writer, "x", &start.x)?;
+        let optional_lines = [
+            ("z_l", start.z_l.as_deref()),
+            ("z_u", start.z_u.as_deref()),
+            ("lambda", start.lambda.as_deref()),
+            (
+                "objective_scaling",
+                self.scaling.objective.as_ref().map(std::slice::from_ref),
+            ),
+            ("x_scaling", self.scaling.x.as_deref()),
+            ("g_scaling", self.scaling.g.as_deref()),
+        ];
+        for (key, values) in optional_lines {
+            if let Some(values) = values {
+                write_line(&mut writer, key, values)?;
+            }
+        }
+
+        write_line(&mut writer, "jacobian_rows", &self.jacobian_rows)?;
+        write_line(&mut writer, "jacobian_columns", &self.jacobian_columns)?;
+        write_line(&mut writer, "hessian_rows", &self.hessian_rows)?;
+        write_line(&mut writer, "hessian_columns", &self.hessian_columns)?;
+
+        let options = [
+            ("option", &self.options),
+            ("application_option", &self.application_options),
+        ];
+        for (key, options) in options {
+            for (name, value) in options {
+                if name.is_empty() || name.contains(char::is_whitespace) {
+                    return Err(invalid_text(key, name));
+                }
+                match value {
+                    OptionValue::Integer(value) => writeln!(writer, "{key} integer {name} {value}"),
+                    OptionValue::Numeric(value) => writeln!(writer, "{key} numeric {name} {value}"),
+                    OptionValue::String(value) => {
+                        single_line(key, value)?;
+                        writeln!(writer, "{key} string {name} {value}")
+                    }
+                }?;
+            }
+        }
+        let names = [
+            ("variable_name", &self.names.variables),
+            ("constraint_name", &self.names.constraints),
+        ];
+        for (key, names) in names {
+            for name in names.iter().flatten() {
+                single_line(key, name)?;
+                writeln!(writer, "{key} {name}")?;
+            }
+        }
+
+        for evaluation in &self.evaluations {
+            evaluation.write(&mut writer)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a recording in the text format.
+    ///
+    /// # Parameters
+    ///
+    /// - `reader` - The source of the recording.
+    ///
+    /// # Errors
+    ///
+    /// - Reading from `reader` fails.
+    /// - The text is not a valid recording.
+    /// - The lengths of the vectors of the recording do not match its `size`.
+    pub fn read(reader: impl BufRead) -> Result<Self, Box<dyn Error>> {
+        let mut lines = reader.lines().enumerate().map(|(index, line)| {
+            line.map(|line| {
+                let (key, rest) = line.split_once(' ').unwrap_or((&line, ""));
+                (index + 1, key.to_string(), rest.to_string())
+            })
+        });
+        if lines
+            .next()
+            .transpose()?
+            .is_none_or(|(_, key, rest)| format!("{key} {rest}") != HEADER)
+        {
+            return Err(format!("A recording must start with `{HEADER}`.").into());
+        }
+
+        let mut recording = Recording::default();
+        let mut next_line = || lines.next().transpose();
+        while let Some((number, key, rest)) = next_line()? {
+            let error = |error: String| format!("Line {number}: {error}");
+            recording
+                .read_line(&key, &rest, &mut next_line)
+                .map_err(error)?;
+        }
+        recording.validate()?;
+        Ok(recording)
+    }
+
+    /// Checks that the lengths of the vectors of the recording match its `size`, and that the
+    /// sparsity patterns index variables and constraints of the problem.
+    fn validate(&self) -> Result<(), String> {
+        let size = &self.size;
+        let dimension = |name: &str, value: i32| {
+            usize::try_from(value).map_err(|_| format!("The size `{name}` is negative: {value}."))
+        };
+        let (n, m) = (dimension("n", size.n)?, dimension("m", size.m)?);
+        let (nnz_jac, nnz_hess) = (
+            dimension("nnz_jac", size.nnz_jac)?,
+            dimension("nnz_hess", size.nnz_hess)?,
+        );
+        let check = |name: &str, len: Option<usize>, expected: usize| match len {
+            Some(len) if len != expected => Err(format!(
+                "`{name}` has length {len}, but the recorded size is {expected}."
+            )),
+            _ => Ok(()),
+        };
+
+        let bounds = &self.bounds;
+        check("x_l", Some(bounds.x_l.len()), n)?;
+        check("x_u", Some(bounds.x_u.len()), n)?;
+        check("g_l", Some(bounds.g_l.len()), m)?;
+        check("g_u", Some(bounds.g_u.len()), m)?;
+        self.starting_point.validate(n, m)?;
+        check("x_scaling", self.scaling.x.as_ref().map(Vec::len), n)?;
+        check("g_scaling", self.scaling.g.as_ref().map(Vec::len), m)?;
+        check(
+            "variable_name",
+            self.names.variables.as_ref().map(Vec::len),
+            n,
+        )?;
+        check(
+            "constraint_name",
+            self.names.constraints.as_ref().map(Vec::len),
+            m,
+        )?;
+
+        let patterns = [
+            ("jacobian_rows", &self.jacobian_rows, nnz_jac, m),
+            ("jacobian_columns", &self.jacobian_columns, nnz_jac, n),
+            ("hessian_rows", &self.hessian_rows, nnz_hess, n),
+            ("hessian_columns", &self.hessian_columns, nnz_hess, n),
+        ];
+        for (name, indices, nnz, dimension) in patterns {
+            check(name, Some(indices.len()), nnz)?;
+            let in_range = |&&index: &&i32| usize::try_from(index).is_ok_and(|i| i < dimension);
+            if let Some(index) = indices.iter().find(|index| !in_range(index)) {
+                return Err(format!(
+                    "`{name}` has index {index}, which is not in the range 0..{dimension}."
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a line of the text format, and the lines that follow it for an evaluation.
+    fn read_line(
+        &mut self,
+        key: &str,
+        rest: &str,
+        next_line: &mut dyn FnMut() -> io::Result<Option<(usize, String, String)>>,
+    ) -> Result<(), String> {
+        let start = &mut self.starting_point;
+        let names = &mut self.names;
+        match key {
+            "size" => {
+                let size: Vec<i32> = parse_values(rest)?;
+                let [n, m, nnz_jac, nnz_hess] = size[..] else {
+                    return Err("Expected 4 sizes.".to_string());
+                };
+                self.size = ProblemSize {
+                    n,
+                    m,
+                    nnz_jac,
+                    nnz_hess,
+                };
+            }
+            "x_l" => self.bounds.x_l = parse_values(rest)?,
+            "x_u" => self.bounds.x_u = parse_values(rest)?,
+            "g_l" => self.bounds.g_l = parse_values(rest)?,
+            "g_u" => self.bounds.g_u = parse_values(rest)?,
+            "x" => start.x = parse_values(rest)?,
+            "z_l" => start.z_l = Some(parse_values(rest)?),
+            "z_u" => start.z_u = Some(parse_values(rest)?),
+            "lambda" => start.lambda = Some(parse_values(rest)?),
+            "objective_scaling" => self.scaling.objective = Some(parse_value(rest)?),
+            "x_scaling" => self.scaling.x = Some(parse_values(rest)?),
+            "g_scaling" => self.scaling.g = Some(parse_values(rest)?),
+            "jacobian_rows" => self.jacobian_rows = parse_values(rest)?,
+            "jacobian_columns" => self.jacobian_columns = parse_values(rest)?,
+            "hessian_rows" => self.hessian_rows = parse_values(rest)?,
+            "hessian_columns" => self.hessian_columns = parse_values(rest)?,
+            "option" => self.options.push(parse_option(rest)?),
+            "application_option" => self.application_options.push(parse_option(rest)?),
+            "variable_name" => names
+                .variables
+                .get_or_insert_default()
+                .push(rest.to_string()),
+            "constraint_name" => (names.constraints.get_or_insert_default()).push(rest.to_string()),
+            _ => {
+                let evaluation = Evaluation::read(key, rest, next_line)?;
+                self.evaluations.push(evaluation);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Evaluation {
+    /// Writes the evaluation in the text format of a `Recording`.
+    fn write(&self, writer: &mut impl Write) -> io::Result<()> {
+        let success = u8::from(self.success);
+        match &self.callback {
+            Callback::Objective => writeln!(writer, "eval_f {success}"),
+            Callback::ObjectiveGradient => writeln!(writer, "eval_grad_f {success}"),
+            Callback::Constraints => writeln!(writer, "eval_g {success}"),
+            Callback::Jacobian => writeln!(writer, "eval_jac_g {success}"),
+            Callback::Hessian { obj_factor, .. } => {
+                writeln!(writer, "eval_h {success} {obj_factor}")
+            }
+        }?;
+        write_line(writer, "x", &self.x)?;
+        if let Callback::Hessian { lambda, .. } = &self.callback {
+            write_line(writer, "lambda", lambda)?;
+        }
+        write_line(writer, "values", &self.values)
+    }
+
+    /// Reads an evaluation in the text format of a `Recording`, from its first line and the lines
+    /// that follow it.
+    fn read(
+        key: &str,
+        rest: &str,
+        next_line: &mut dyn FnMut() -> io::Result<Option<(usize, String, String)>>,
+    ) -> Result<Self, String> {
+        let (success, obj_factor) = rest.split_once(' ').unwrap_or((rest, ""));
+        let success = match success {
+            "1" => true,
+            "0" => false,
+            _ => return Err(format!("Invalid success flag `{success}`.")),
+        };
+        let mut expect = |expected: &str| match next_line() {
+            Ok(Some((_, key, rest))) if key == expected => parse_values(&rest),
+            Ok(_) => Err(format!("Expected a `{expected}` line after `{key}`.")),
+            Err(error) => Err(error.to_string()),
+        };
+
+        let callback = match key {
+            "eval_f" => Callback::Objective,
+            "eval_grad_f" => Callback::ObjectiveGradient,
+            "eval_g" => Callback::Constraints,
+            "eval_jac_g" => Callback::Jacobian,
+            "eval_h" => Callback::Hessian {
+                obj_factor: parse_value(obj_factor)?,
+                lambda: Vec::new(),
+            },
+            _ => return Err(format!("Unknown key `{key}`.")),
+        };
+        let x = expect("x")?;
+        let callback = match callback {
+            Callback::Hessian { obj_factor, .. } => Callback::Hessian {
+                obj_factor,
+                lambda: expect("lambda")?,
+            },
+            callback => callback,
+        };
+
+        Ok(Evaluation {
+            callback,
+            x,
+            values: expect("values")?,
+            success,
+        })
+    }
+
+    /// Checks whether the evaluation was made by `callback` at exactly `x`.
+    fn matches(&self, callback: &Callback, x: &[f64]) -> bool {
+        let same = |a: &[f64], b: &[f64]| {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.to_bits() == b.to_bits())
+        };
+        let same_callback = match (&self.callback, callback) {
+            (
+                Callback::Hessian { obj_factor, lambda },
+                Callback::Hessian {
+                    obj_factor: other_obj_factor,
+                    lambda: other_lambda,
+                },
+            ) => obj_factor.to_bits() == other_obj_factor.to_bits() && same(lambda, other_lambda),
+            (callback, other) => callback == other,
+        };
+        same_callback && same(&self.x, x)
+    }
+}
+
+/// Writes a line of a key and space separated values.
+fn write_line<T: Display>(writer: &mut impl Write, key: &str, values: &[T]) -> io::Result<()> {
+    write!(writer, "{key}")?;
+    for value in values {
+        write!(writer, " {value}")?;
+    }
+    writeln!(writer)
+}
+
+/// Checks that a text written to a single line of a recording does not contain a line break.
+fn single_line(key: &str, text: &str) -> io::Result<()> {
+    if text.contains(['\n', '\r']) {
+        Err(invalid_text(key, text))
+    } else {
+        Ok(())
+    }
+}
+
+/// Creates the error for a text that cannot be written to a recording.
+fn invalid_text(key: &str, text: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("The `{key}` {text:?} cannot be written to a recording."),
+    )
+}
+
+fn parse_value<T: std::str::FromStr>(text: &str) -> Result<T, String> {
+    text.parse().map_err(|_| format!("Invalid value `{text}`."))
+}
+
+fn parse_values<T: std::str::FromStr>(text: &str) -> Result<Vec<T>, String> {
+    text.split_whitespace().map(parse_value).collect()
+}
+
+fn parse_option(text: &str) -> Result<(String, OptionValue), String> {
+    let mut parts = text.splitn(3, ' ');
+    let (Some(kind), Some(name), Some(value)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(format!("Invalid option `{text}`."));
+    };
+    let value = match kind {
+        "integer" => OptionValue::Integer(parse_value(value)?),
+        "numeric" => OptionValue::Numeric(parse_value(value)?),
+        "string" => OptionValue::String(value.to_string()),
+        _ => return Err(format!("Invalid option type `{kind}`.")),
+    };
+    Ok((name.to_string(), value))
+}
+
+/// A `Tnlp` wrapper that records the problem and every evaluation of its callbacks.
+///
+/// The problem is recorded, and its callbacks evaluated at the starting point, when the recorder
+/// is created. Every evaluation made by Ipopt is then appended to the recording.
+#[derive(Debug, Clone)]
+pub struct Recorder<P: Tnlp> {
+    problem: P,
+    recording: Recording,
+}
+
+impl<P: Tnlp> Recorder<P> {
+    /// Creates a new `Recorder`, recording the problem and its callbacks at the starting point.
+    ///
+    /// # Parameters
+    ///
+    /// - `problem` - The problem to record.
+    ///
+    /// # Errors
+    ///
+    /// The problem cannot be recorded, see `Recording::from_problem`.
+    pub fn new(mut problem: P) -> Result<Self, Box<dyn Error>> {
+        let recording = Recording::from_problem(&mut problem)?;
+        Ok(Recorder { problem, recording })
+    }
+
+    /// Gets the recording so far.
+    #[must_use]
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Gets the recorded problem.
+    #[must_use]
+    pub fn problem(&self) -> &P {
+        &self.problem
+    }
+
+    /// Consumes the recorder, returning the problem and the recording.
+    #[must_use]
+    pub fn into_parts(self) -> (P, Recording) {
+        (self.problem, self.recording)
+    }
+
+    /// Appends an evaluation to the recording.
+    fn record(&mut self, callback: Callback, x: &[f64], values: &[f64], success: bool) -> bool {
+        self.recording.evaluations.push(Evaluation {
+            callback,
+            x: x.to_vec(),
+            values: values.to_vec(),
+            success,
+        });
+        success
+    }
+}
+
+impl<P: Tnlp> Tnlp for Recorder<P> {
+    fn get_nlp_info(&self) -> ProblemSize {
+        self.problem.get_nlp_info()
+    }
+
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
+        self.problem.get_bounds_info(x_l, x_u, g_l, g_u);
+    }
+
+    fn get_scaling(&self) -> UserScaling {
+        self.problem.get_scaling()
+    }
+
+    fn get_starting_point(&self) -> InitialSolution {
+        self.problem.get_starting_point()
+    }
+
+    fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+        let success = self.problem.eval_f(x, obj_value);
+        self.record(Callback::Objective, x, &[*obj_value], success)
+    }
+
+    fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+        let success = self.problem.eval_grad_f(x, grad_f);
+        self.record(Callback::ObjectiveGradient, x, grad_f, success)
+    }
+
+    fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+        let success = self.problem.eval_g(x, g);
+        self.record(Callback::Constraints, x, g, success)
+    }
+
+    fn get_jacobian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.problem.get_jacobian_sparsity(n, m, i_row, j_col);
+    }
+
+    fn eval_jac_g(&mut self, x: &[f64], m: i32, values: &mut [f64]) -> bool {
+        let success = self.problem.eval_jac_g(x, m, values);
+        self.record(Callback::Jacobian, x, values, success)
+    }
+
+    fn get_hessian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.problem.get_hessian_sparsity(n, m, i_row, j_col);
+    }
+
+    fn eval_h(
+        &mut self,
+        x: &[f64],
+        obj_factor: f64,
+        lambda: &[f64],
+        m: i32,
+        values: &mut [f64],
+    ) -> bool {
+        let success = self.problem.eval_h(x, obj_factor, lambda, m, values);
+        let callback = Callback::Hessian {
+            obj_factor,
+            lambda: lambda.to_vec(),
+        };
+        self.record(callback, x, values, success)
+    }
+
+    fn intermediate(&mut self, data: IntermediateData) -> bool {
+        self.problem.intermediate(data)
+    }
+
+    fn variable_names(&self) -> Option<Vec<String>> {
+        self.problem.variable_names()
+    }
+
+    fn constraint_names(&self) -> Option<Vec<String>> {
+        self.problem.constraint_names()
+    }
+
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        self.problem.default_options()
+    }
+}
+
+impl Application {
+    /// Optimizes the problem while recording it, along with the options of the application.
+    ///
+    /// # Parameters
+    ///
+    /// - `problem` - The problem to optimize and record.
+    ///
+    /// # Returns
+    ///
+    /// The optimization results and the recording.
+    ///
+    /// # Errors
+    ///
+    /// - The problem cannot be recorded, see `Recording::from_problem`.
+    /// - The optimization fails, see `Application::optimize_tnlp`.
+    pub fn record<P: Tnlp>(
+        &self,
+        problem: P,
+    ) -> Result<(OptimizationResult, Recording), Box<dyn Error>> {
+        let mut recorder = Recorder::new(problem)?;
+        recorder.recording.application_options = self.options();
+        let result = self.optimize_tnlp(&mut recorder)?;
+        Ok((result, recorder.recording))
+    }
+}
+
+/// A `Tnlp` that replays a `Recording`.
+///
+/// Each evaluation returns the recorded values for the same callback at exactly the same point.
+/// Evaluations are looked up from the position of the previous match, so replaying the recorded
+/// sequence is fast. An evaluation that was not recorded fails, and is counted by `misses`.
+///
+/// The default options of the replayer are the recorded default options of the problem followed
+/// by the recorded application options, so replaying with `Application::new()` uses the options of
+/// the recorded optimization.
+#[derive(Debug, Clone)]
+pub struct Replayer {
+    recording: Recording,
+    position: usize,
+    misses: usize,
+}
+
+impl Replayer {
+    /// Creates a new `Replayer`.
+    ///
+    /// # Parameters
+    ///
+    /// - `recording` - The recording to replay.
+    ///
+    /// # Errors
+    ///
+    /// The lengths of the vectors of the recording do not match its `size`, e.g. because it was
+    /// edited by hand, in which case replaying it would fail in the middle of the optimization.
+    pub fn new(recording: Recording) -> Result<Self, Box<dyn Error>> {
+        recording.validate()?;
+        Ok(Replayer {
+            recording,
+            position: 0,
+            misses: 0,
+        })
+    }
+
+    /// Gets the recording being replayed.
+    #[must_use]
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Gets the number of evaluations that were not found in the recording.
+    ///
+    /// This is zero if the optimization was reproduced exactly.
+    #[must_use]
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Writes the recorded values of an evaluation, returning whether it succeeded.
+    fn replay(&mut self, callback: &Callback, x: &[f64], values: &mut [f64]) -> bool {
+        let evaluations = &self.recording.evaluations;
+        let found = (self.position..evaluations.len())
+            .chain(0..self.position)
+            .find(|&index| {
+                let evaluation = &evaluations[index];
+                evaluation.matches(callback, x) && evaluation.values.len() == values.len()
+            });
+
+        if let Some(index) = found {
+            let evaluation = &evaluations[index];
+            values.copy_from_slice(&evaluation.values);
+            self.position = index + 1;
+            evaluation.success
+        } else {
+            self.misses += 1;
+            false
+        }
+    }
+}
+
+impl Tnlp for Replayer {
+    fn get_nlp_info(&self) -> ProblemSize {
+        self.recording.size.clone()
+    }
+
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
+        let bounds = &self.recording.bounds;
+        x_l.copy_from_slice(&bounds.x_l);
+        x_u.copy_from_slice(&bounds.x_u);
+        g_l.copy_from_slice(&bounds.g_l);
+        g_u.copy_from_slice(&bounds.g_u);
+    }
+
+    fn get_scaling(&self) -> UserScaling {
+        self.recording.scaling.clone()
+    }
+
+    fn get_starting_point(&self) -> InitialSolution {
+        self.recording.starting_point.clone()
+    }
+
+    fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+        self.replay(&Callback::Objective, x, std::slice::from_mut(obj_value))
+    }
+
+    fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+        self.replay(&Callback::ObjectiveGradient, x, grad_f)
+    }
+
+    fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+        self.replay(&Callback::Constraints, x, g)
+    }
+
+    fn get_jacobian_sparsity(&mut self, _n: i32, _m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        i_row.copy_from_slice(&self.recording.jacobian_rows);
+        j_col.copy_from_slice(&self.recording.jacobian_columns);
+    }
+
+    fn eval_jac_g(&mut self, x: &[f64], _m: i32, values: &mut [f64]) -> bool {
+        self.replay(&Callback::Jacobian, x, values)
+    }
+
+    fn get_hessian_sparsity(&mut self, _n: i32, _m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        i_row.copy_from_slice(&self.recording.hessian_rows);
+        j_col.copy_from_slice(&self.recording.hessian_columns);
+    }
+
+    fn eval_h(
+        &mut self,
+        x: &[f64],
+        obj_factor: f64,
+        lambda: &[f64],
+        _m: i32,
+        values: &mut [f64],
+    ) -> bool {
+        let callback = Callback::Hessian {
+            obj_factor,
+            lambda: lambda.to_vec(),
+        };
+        self.replay(&callback, x, values)
+    }
+
+    fn variable_names(&self) -> Option<Vec<String>> {
+        self.recording.names.variables.clone()
+    }
+
+    fn constraint_names(&self) -> Option<Vec<String>> {
+        self.recording.names.constraints.clone()
+    }
+
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        let recording = &self.recording;
+        [&recording.options[..], &recording.application_options[..]].concat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bounds::Bounds, quadratic::QuadraticProblem};
+
+    /// minimize x0² + ½ x0 x1 + x1² - x0 subject to x0 + x1 = 1, x >= 0.
+    fn problem() -> QuadraticProblem {
+        let mut problem = QuadraticProblem::new(2, 1);
+        problem
+            .set_objective(&[(0, 0, 2.0), (1, 0, 1.0), (1, 1, 2.0)], vec![-1.0, 0.0])
+            .unwrap()
+            .set_constraints(&[(0, 0, 1.0), (0, 1, 1.0)])
+            .unwrap()
+            .set_constraint_bounds(vec![Bounds::equal(1.0)])
+            .unwrap()
+            .set_variable_bounds(vec![Bounds::lower(0.0); 2])
+            .unwrap()
+            .set_starting_point(vec![0.1, 0.3])
+            .unwrap();
+        problem
+    }
+
+    #[test]
+    fn recorder_records_starting_point_and_solver_evaluations() {
+        let mut recorder = Recorder::new(problem()).unwrap();
+        let mut grad_f = vec![0.0; 2];
+
+        assert!(recorder.eval_grad_f(&[1.0, 2.0], &mut grad_f));
+
+        let recording = recorder.recording();
+        assert_eq!(6, recording.evaluations.len());
+        assert_eq!(vec![0.1, 0.3], recording.evaluations[0].x);
+        assert_eq!(
+            Evaluation {
+                callback: Callback::ObjectiveGradient,
+                x: vec![1.0, 2.0],
+                values: vec![2.0, 4.5],
+                success: true,
+            },
+            recording.evaluations[5]
+        );
+        assert_eq!(problem().default_options(), recording.options);
+    }
+
+    #[test]
+    fn recording_round_trips_through_text_format() {
+        let mut recorder = Recorder::new(problem()).unwrap();
+        let mut f = 0.0;
+        assert!(recorder.eval_f(&[1.0 / 3.0, f64::MIN_POSITIVE], &mut f));
+        let (_, mut recording) = recorder.into_parts();
+        recording.names.variables = Some(vec!["flow [a]".to_string(), "y".to_string()]);
+        recording.scaling.objective = Some(-1.0);
+        let mut text = Vec::new();
+
+        recording.write(&mut text).unwrap();
+
+        assert_eq!(recording, Recording::read(text.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn replayer_applies_recorded_application_options() {
+        let mut application = Application::new();
+        application.set_numeric_option("tol", 1e-10);
+        let mut recording = Recording::from_problem(&mut problem()).unwrap();
+        recording.application_options = application.options();
+        let mut text = Vec::new();
+
+        recording.write(&mut text).unwrap();
+        let replayer = Replayer::new(Recording::read(text.as_slice()).unwrap()).unwrap();
+
+        assert_eq!(
+            Some(&("tol".to_string(), OptionValue::Numeric(1e-10))),
+            replayer.default_options().last()
+        );
+        assert_eq!(
+            problem().default_options().len() + 1,
+            replayer.default_options().len()
+        );
+    }
+
+    #[test]
+    fn recording_read_reports_line_of_invalid_value() {
+        let text = format!("{HEADER}\nsize 1 0 0 0\nx_l zero\n");
+
+        let error = Recording::read(text.as_bytes()).unwrap_err();
+
+        assert_eq!("Line 3: Invalid value `zero`.", error.to_string());
+    }
+
+    #[test]
+    fn recording_read_rejects_lengths_that_do_not_match_size() {
+        let mut text = Vec::new();
+        Recording::from_problem(&mut problem())
+            .unwrap()
+            .write(&mut text)
+            .unwrap();
+        let text = String::from_utf8(text).unwrap();
+        let truncated = text.replace("\njacobian_rows 0 0\n", "\njacobian_rows 0\n");
+        let out_of_range = text.replace("\njacobian_columns 0 1\n", "\njacobian_columns 0 2\n");
+
+        let errors = [truncated, out_of_range]
+            .map(|text| Recording::read(text.as_bytes()).unwrap_err().to_string());
+
+        assert_eq!(
+            [
+                "`jacobian_rows` has length 1, but the recorded size is 2.",
+                "`jacobian_columns` has index 2, which is not in the range 0..2."
+            ],
+            errors
+        );
+    }
+
+    #[test]
+    fn replayer_new_rejects_lengths_that_do_not_match_size() {
+        let mut recording = Recording::from_problem(&mut problem()).unwrap();
+        recording.bounds.g_u.clear();
+
+        let error = Replayer::new(recording).unwrap_err();
+
+        assert_eq!(
+            "`g_u` has length 0, but the recorded size is 1.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn recording_write_rejects_line_breaks() {
+        let mut recording = Recording::from_problem(&mut problem()).unwrap();
+        recording.names.constraints = Some(vec!["balance\nx_l 0".to_string()]);
+
+        let error = recording.write(io::sink()).unwrap_err();
+
+        assert_eq!(io::ErrorKind::InvalidInput, error.kind());
+    }
+
+    #[test]
+    fn replayer_reproduces_recorded_evaluations() {
+        let mut original = problem();
+        let recording = Recording::from_problem(&mut original).unwrap();
+        let mut replayer = Replayer::new(recording).unwrap();
+        let (mut f, mut jac_g, mut hessian) = (0.0, vec![0.0; 2], vec![0.0; 3]);
+        let (mut expected_f, mut expected_hessian) = (0.0, vec![0.0; 3]);
+
+        assert!(replayer.eval_f(&[0.1, 0.3], &mut f));
+        assert!(replayer.eval_jac_g(&[0.1, 0.3], 1, &mut jac_g));
+        assert!(replayer.eval_h(&[0.1, 0.3], 1.0, &[1.0], 1, &mut hessian));
+        assert!(!replayer.eval_f(&[0.1, 0.4], &mut f));
+
+        assert!(original.eval_f(&[0.1, 0.3], &mut expected_f));
+        assert!(original.eval_h(&[0.1, 0.3], 1.0, &[1.0], 1, &mut expected_hessian));
+        assert_eq!(vec![expected_f, 1.0, 1.0], [vec![f], jac_g].concat());
+        assert_eq!(expected_hessian, hessian);
+        assert_eq!(1, replayer.misses());
+        assert_eq!(replayer.recording().size, replayer.get_nlp_info());
+    }
+}
@@ -0,0 +1,400 @@
+//! # Ipopt Bindgen Report
+//!
+//! Defines a tabular report of the solution of an optimization, which can be pretty printed with
+//! `Display` or written as CSV.
+
+use crate::{
+    active_set::{ActiveSet, ActiveSetTolerances, ConstraintStatus, VariableStatus},
+    bounds::BoundInfinities,
+    results::OptimizationResult,
+    tnlp::{ProblemBounds, ProblemNames},
+};
+use std::{
+    error::Error,
+    fmt,
+    io::{self, Write},
+};
+
+/// A tabular report of the solution of an optimization.
+///
+/// The report contains a header with the status, objective and performance counters, one row per
+/// variable with its value, bounds, bound multipliers and active status, and one row per
+/// constraint with its value, bounds, multiplier and active status.
+///
+/// Variables and constraints are named with the names in `OptimizationResult::names`, which are
+/// supplied by the problem, unless other names are set.
+#[derive(Debug, Clone)]
+pub struct SolutionReport<'a> {
+    result: &'a OptimizationResult,
+    bounds: &'a ProblemBounds,
+    active_set: ActiveSet,
+    infinities: BoundInfinities,
+    names: ProblemNames,
+}
+
+impl<'a> SolutionReport<'a> {
+    /// Creates a new `SolutionReport`.
+    ///
+    /// The active status of each row is identified with the default `ActiveSetTolerances`.
+    ///
+    /// # Parameters
+    ///
+    /// - `result` - The result of the optimization.
+    /// - `bounds` - The bounds of the problem that was optimized.
+    ///
+    /// # Errors
+    ///
+    /// The dimensions of the bounds do not match the solution.
+    pub fn new(
+        result: &'a OptimizationResult,
+        bounds: &'a ProblemBounds,
+    ) -> Result<Self, Box<dyn Error>> {
+        SolutionReport::with_tolerances(result, bounds, &ActiveSetTolerances::default())
+    }
+
+    /// Creates a new `SolutionReport`, identifying the active status of each row with the given
+    /// tolerances.
+    ///
+    /// Bounds are classified as infinite, both for the active status and when displayed, with
+    /// `ActiveSetTolerances::bound_infinities`.
+    ///
+    /// # Parameters
+    ///
+    /// - `result` - The result of the optimization.
+    /// - `bounds` - The bounds of the problem that was optimized.
+    /// - `tolerances` - The tolerances for identifying active bounds and constraints.
+    ///
+    /// # Errors
+    ///
+    /// The dimensions of the bounds do not match the solution.
+    pub fn with_tolerances(
+        result: &'a OptimizationResult,
+        bounds: &'a ProblemBounds,
+        tolerances: &ActiveSetTolerances,
+    ) -> Result<Self, Box<dyn Error>> {
+        let active_set = result.active_set(bounds, tolerances)?;
+        Ok(SolutionReport {
+            result,
+            bounds,
+            active_set,
+            infinities: tolerances.bound_infinities,
+            names: result.names.clone(),
+        })
+    }
+
+    /// Sets the names of the variables.
+    ///
+    /// Variables without a name fall back to `x[j]`.
+    ///
+    /// # Parameters
+    ///
+    /// - `names` - The variable names, in index order.
+    pub fn set_variable_names(&mut self, names: Vec<String>) -> &mut Self {
+        self.names.variables = Some(names);
+        self
+    }
+
+    /// Sets the names of the constraints.
+    ///
+    /// Constraints without a name fall back to `g[i]`.
+    ///
+    /// # Parameters
+    ///
+    /// - `names` - The constraint names, in index order.
+    pub fn set_constraint_names(&mut self, names: Vec<String>) -> &mut Self {
+        self.names.constraints = Some(names);
+        self
+    }
+
+    /// Sets the names of the variables and constraints.
+    ///
+    /// # Parameters
+    ///
+    /// - `names` - The variable and constraint names.
+    pub fn set_names(&mut self, names: ProblemNames) -> &mut Self {
+        self.names = names;
+        self
+    }
+
+    /// Writes the report as CSV.
+    ///
+    /// The status and performance header is omitted. Variables and constraints share a single table
+    /// with the columns `kind,index,name,value,lower_bound,upper_bound,z_l,z_u,lambda,status`.
+    /// Cells that do not apply to a row are left empty, and infinite bounds are written as `inf`
+    /// and `-inf`.
+    ///
+    /// # Parameters
+    ///
+    /// - `writer` - The writer to write the CSV to.
+    ///
+    /// # Errors
+    ///
+    /// Writing to the writer fails.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let solution = &self.result.solution;
+
+        writeln!(
+            writer,
+            "kind,index,name,value,lower_bound,upper_bound,z_l,z_u,lambda,status"
+        )?;
+        for (j, status) in self.active_set.variables.iter().enumerate() {
+            writeln!(
+                writer,
+                "variable,{j},{0},{1},{2},{3},{4},{5},,{6}",
+                csv_field(&self.names.variable(j)),
+                solution.x[j],
+                self.lower_bound_cell(self.bounds.x_l[j]),
+                self.upper_bound_cell(self.bounds.x_u[j]),
+                solution.z_l[j],
+                solution.z_u[j],
+                variable_status_name(*status)
+            )?;
+        }
+        for (i, status) in self.active_set.constraints.iter().enumerate() {
+            writeln!(
+                writer,
+                "constraint,{i},{0},{1},{2},{3},,,{4},{5}",
+                csv_field(&self.names.constraint(i)),
+                solution.constraints[i],
+                self.lower_bound_cell(self.bounds.g_l[i]),
+                self.upper_bound_cell(self.bounds.g_u[i]),
+                solution.lambda[i],
+                constraint_status_name(*status)
+            )?;
+        }
+
+        writer.flush()
+    }
+}
+
+impl SolutionReport<'_> {
+    /// Gets the cell for a lower bound, which is infinite at or below the lower bound infinity.
+    fn lower_bound_cell(&self, value: f64) -> BoundCell {
+        BoundCell(self.infinities.lower_bound(value).lower_value())
+    }
+
+    /// Gets the cell for an upper bound, which is infinite at or above the upper bound infinity.
+    fn upper_bound_cell(&self, value: f64) -> BoundCell {
+        BoundCell(self.infinities.upper_bound(value).upper_value())
+    }
+}
+
+impl fmt::Display for SolutionReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let result = self.result;
+        let solution = &result.solution;
+        let performance = &result.performance;
+
+        writeln!(
+            f,
+            "Status:      {0} ({1})",
+            result.status_name(),
+            result.status
+        )?;
+        if let Some(stop_reason) = result.stop_reason {
+            writeln!(f, "Stop reason: {stop_reason:?}")?;
+        }
+        writeln!(f, "Objective:   {0:.8e}", solution.objective)?;
+        writeln!(f, "Iterations:  {0}", performance.number_of_iterations)?;
+        writeln!(
+            f,
+            "Evaluations: f = {0}, grad_f = {1}, g = {2}, jac_g = {3}, h = {4}",
+            performance.number_of_objective_evaluations,
+            performance.number_of_objective_gradient_evaluations,
+            performance.number_of_constraint_evaluations,
+            performance.number_of_jacobian_evaluations,
+            performance.number_of_hessian_evaluations
+        )?;
+
+        let variable_names: Vec<_> = (0..solution.x.len())
+            .map(|j| self.names.variable(j))
+            .collect();
+        let width = name_width(&variable_names);
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{0:<width$} {1:>15} {2:>15} {3:>15} {4:>15} {5:>15}  Status",
+            "Variable", "Value", "Lower", "Upper", "z_l", "z_u"
+        )?;
+        for (j, name) in variable_names.iter().enumerate() {
+            writeln!(
+                f,
+                "{name:<width$} {0:>15.8e} {1:>15.8} {2:>15.8} {3:>15.8e} {4:>15.8e}  {5}",
+                solution.x[j],
+                self.lower_bound_cell(self.bounds.x_l[j]),
+                self.upper_bound_cell(self.bounds.x_u[j]),
+                solution.z_l[j],
+                solution.z_u[j],
+                variable_status_name(self.active_set.variables[j])
+            )?;
+        }
+
+        let constraint_names: Vec<_> = (0..solution.constraints.len())
+            .map(|i| self.names.constraint(i))
+            .collect();
+        let width = name_width(&constraint_names);
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{0:<width$} {1:>15} {2:>15} {3:>15} {4:>15}  Status",
+            "Constraint", "Value", "Lower", "Upper", "lambda"
+        )?;
+        for (i, name) in constraint_names.iter().enumerate() {
+            writeln!(
+                f,
+                "{name:<width$} {0:>15.8e} {1:>15.8} {2:>15.8} {3:>15.8e}  {4}",
+                solution.constraints[i],
+                self.lower_bound_cell(self.bounds.g_l[i]),
+                self.upper_bound_cell(self.bounds.g_u[i]),
+                solution.lambda[i],
+                constraint_status_name(self.active_set.constraints[i])
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A bound that is displayed as `inf` or `-inf` if it is infinite.
+struct BoundCell(f64);
+
+impl fmt::Display for BoundCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = if self.0 == f64::INFINITY {
+            "inf".to_string()
+        } else if self.0 == f64::NEG_INFINITY {
+            "-inf".to_string()
+        } else if let Some(precision) = f.precision() {
+            format!("{0:.precision$e}", self.0)
+        } else {
+            self.0.to_string()
+        };
+        f.pad(&text)
+    }
+}
+
+/// Gets the width of the name column, which fits the longest name and the column title.
+fn name_width(names: &[String]) -> usize {
+    names
+        .iter()
+        .map(|name| name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Constraint".len())
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break.
+pub(crate) fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{0}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Gets the report name of a variable status.
+fn variable_status_name(status: VariableStatus) -> &'static str {
+    match status {
+        VariableStatus::Free => "free",
+        VariableStatus::AtLower => "at_lower",
+        VariableStatus::AtUpper => "at_upper",
+        VariableStatus::Fixed => "fixed",
+    }
+}
+
+/// Gets the report name of a constraint status.
+fn constraint_status_name(status: ConstraintStatus) -> &'static str {
+    match status {
+        ConstraintStatus::Inactive => "inactive",
+        ConstraintStatus::ActiveLower => "active_lower",
+        ConstraintStatus::ActiveUpper => "active_upper",
+        ConstraintStatus::Equality => "equality",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::Solution;
+
+    fn result() -> OptimizationResult {
+        OptimizationResult {
+            solution: Solution {
+                x: vec![0.5, 0.0],
+                constraints: vec![0.5],
+                lambda: vec![-1.0],
+                z_l: vec![0.0, 2.0],
+                z_u: vec![0.0, 0.0],
+                objective: 0.25,
+            },
+            ..Default::default()
+        }
+    }
+
+    fn bounds() -> ProblemBounds {
+        ProblemBounds {
+            x_l: vec![-2e19, 0.0],
+            x_u: vec![2e19, 1.0],
+            g_l: vec![0.5],
+            g_u: vec![2e19],
+        }
+    }
+
+    #[test]
+    fn solution_report_write_csv_writes_rows() {
+        let (result, bounds) = (result(), bounds());
+        let mut report = SolutionReport::new(&result, &bounds).unwrap();
+        report.set_variable_names(vec!["flow, pipe 1".to_string()]);
+        let mut csv = Vec::new();
+
+        report.write_csv(&mut csv).unwrap();
+
+        assert_eq!(
+            "kind,index,name,value,lower_bound,upper_bound,z_l,z_u,lambda,status\n\
+             variable,0,\"flow, pipe 1\",0.5,-inf,inf,0,0,,free\n\
+             variable,1,x[1],0,0,1,2,0,,at_lower\n\
+             constraint,0,g[0],0.5,0.5,inf,,,-1,active_lower\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
+
+    #[test]
+    fn solution_report_with_tolerances_uses_bound_infinities() {
+        let result = result();
+        let bounds = ProblemBounds {
+            x_l: vec![-2e10, 0.0],
+            x_u: vec![2e10, 1.0],
+            g_l: vec![0.5],
+            g_u: vec![2e10],
+        };
+        let tolerances = ActiveSetTolerances {
+            bound_infinities: BoundInfinities {
+                lower: -1e10,
+                upper: 1e10,
+            },
+            ..Default::default()
+        };
+        let report = SolutionReport::with_tolerances(&result, &bounds, &tolerances).unwrap();
+        let mut csv = Vec::new();
+
+        report.write_csv(&mut csv).unwrap();
+
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.contains("variable,0,x[0],0.5,-inf,inf,0,0,,free\n"));
+        assert!(csv.contains("constraint,0,g[0],0.5,0.5,inf,,,-1,active_lower\n"));
+    }
+
+    #[test]
+    fn solution_report_display_uses_names() {
+        let (result, bounds) = (result(), bounds());
+        let mut report = SolutionReport::new(&result, &bounds).unwrap();
+        report.set_constraint_names(vec!["demand".to_string()]);
+
+        let text = report.to_string();
+
+        assert!(text.starts_with("Status:      Solve_Succeeded (0)\n"));
+        assert!(text.contains("\nx[1] "));
+        assert!(text.contains("\ndemand "));
+        assert!(text.contains("active_lower"));
+    }
+}
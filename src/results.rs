@@ -1,9 +1,16 @@
 //! # Ipopt Bindgen Results
 //!
 //! This module defines Rust types for Ipopt results structures.
+//!
+//! All of the results types are plain data, and so are `Send + Sync`.
+
+#[allow(clippy::wildcard_imports)]
+use crate::c_interface::*;
+use crate::{statistics::FinalStatistics, tnlp::ProblemNames};
 
 /// Contains results related for the performance of the optimization.
 #[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PerformanceResults {
     /// The number of evaluations of the objective function.
     pub number_of_objective_evaluations: u32,
@@ -19,32 +26,65 @@ pub struct PerformanceResults {
 
     /// The number of evaluations of the Hessian of the Lagrangian.
     pub number_of_hessian_evaluations: u32,
+
+    /// The number of iterations performed, as last reported to the intermediate callback.
+    pub number_of_iterations: u32,
 }
 
 /// Contains the numeric solution to the problem.
 #[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Solution {
     /// The final values of the optimization variables.
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::vec"))]
     pub x: Vec<f64>,
 
     /// The final values of the constraint functions.
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::vec"))]
     pub constraints: Vec<f64>,
 
     /// The final values of the constraint multipliers.
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::vec"))]
     pub lambda: Vec<f64>,
 
     /// The final values of the lower bound multipliers.
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::vec"))]
     pub z_l: Vec<f64>,
 
     /// The final values of the upper bound multipliers.
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::vec"))]
     pub z_u: Vec<f64>,
 
     /// The final value of the objective function.
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::value"))]
     pub objective: f64,
 }
 
+/// The reason that the Rust layer asked Ipopt to stop an optimization early.
+///
+/// In all cases, Ipopt returns the `User_Requested_Stop` status along with the most recent iterate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StopReason {
+    /// The problem's `Tnlp::intermediate` callback returned `false`.
+    Problem,
+
+    /// An `Observer` registered on the application returned `false`.
+    Observer,
+
+    /// The application's `CancellationToken` was cancelled.
+    Cancelled,
+
+    /// The application's wall-clock deadline or timeout passed.
+    DeadlineExceeded,
+
+    /// The application's iteration budget was used up.
+    IterationBudgetExhausted,
+}
+
 /// An initial solution to a nonlinear problem.
 #[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OptimizationResult {
     /// The numeric solution to the problem.
     pub solution: Solution,
@@ -54,4 +94,120 @@ pub struct OptimizationResult {
 
     /// The raw status return code from Ipopt.
     pub status: i32,
+
+    /// Why the optimization was stopped early by the Rust layer, if it was.
+    pub stop_reason: Option<StopReason>,
+
+    /// The console output of Ipopt, if it was captured.
+    ///
+    /// See `Application::set_output`.
+    pub output: Option<String>,
+
+    /// The final statistics parsed from the console output of Ipopt, if it was captured and
+    /// contained a final statistics block.
+    pub final_statistics: Option<FinalStatistics>,
+
+    /// The names of the variables and constraints, as supplied by the problem.
+    pub names: ProblemNames,
+}
+
+impl OptimizationResult {
+    /// Checks whether Ipopt found a feasible solution to the problem.
+    ///
+    /// This is the case if the status is `Solve_Succeeded`, `Solved_To_Acceptable_Level` or
+    /// `Feasible_Point_Found`.
+    #[must_use]
+    pub fn succeeded(&self) -> bool {
+        [
+            ApplicationReturnStatus_Solve_Succeeded,
+            ApplicationReturnStatus_Solved_To_Acceptable_Level,
+            ApplicationReturnStatus_Feasible_Point_Found,
+        ]
+        .contains(&self.status)
+    }
+
+    /// Gets the name of the Ipopt status, e.g. `Solve_Succeeded`.
+    ///
+    /// Returns `Unknown` if the status is not one of the known Ipopt return codes.
+    #[must_use]
+    pub fn status_name(&self) -> &'static str {
+        const STATUS_NAMES: [(ApplicationReturnStatus, &str); 20] = [
+            (ApplicationReturnStatus_Solve_Succeeded, "Solve_Succeeded"),
+            (
+                ApplicationReturnStatus_Solved_To_Acceptable_Level,
+                "Solved_To_Acceptable_Level",
+            ),
+            (
+                ApplicationReturnStatus_Infeasible_Problem_Detected,
+                "Infeasible_Problem_Detected",
+            ),
+            (
+                ApplicationReturnStatus_Search_Direction_Becomes_Too_Small,
+                "Search_Direction_Becomes_Too_Small",
+            ),
+            (
+                ApplicationReturnStatus_Diverging_Iterates,
+                "Diverging_Iterates",
+            ),
+            (
+                ApplicationReturnStatus_User_Requested_Stop,
+                "User_Requested_Stop",
+            ),
+            (
+                ApplicationReturnStatus_Feasible_Point_Found,
+                "Feasible_Point_Found",
+            ),
+            (
+                ApplicationReturnStatus_Maximum_Iterations_Exceeded,
+                "Maximum_Iterations_Exceeded",
+            ),
+            (
+                ApplicationReturnStatus_Restoration_Failed,
+                "Restoration_Failed",
+            ),
+            (
+                ApplicationReturnStatus_Error_In_Step_Computation,
+                "Error_In_Step_Computation",
+            ),
+            (
+                ApplicationReturnStatus_Maximum_CpuTime_Exceeded,
+                "Maximum_CpuTime_Exceeded",
+            ),
+            (
+                ApplicationReturnStatus_Maximum_WallTime_Exceeded,
+                "Maximum_WallTime_Exceeded",
+            ),
+            (
+                ApplicationReturnStatus_Not_Enough_Degrees_Of_Freedom,
+                "Not_Enough_Degrees_Of_Freedom",
+            ),
+            (
+                ApplicationReturnStatus_Invalid_Problem_Definition,
+                "Invalid_Problem_Definition",
+            ),
+            (ApplicationReturnStatus_Invalid_Option, "Invalid_Option"),
+            (
+                ApplicationReturnStatus_Invalid_Number_Detected,
+                "Invalid_Number_Detected",
+            ),
+            (
+                ApplicationReturnStatus_Unrecoverable_Exception,
+                "Unrecoverable_Exception",
+            ),
+            (
+                ApplicationReturnStatus_NonIpopt_Exception_Thrown,
+                "NonIpopt_Exception_Thrown",
+            ),
+            (
+                ApplicationReturnStatus_Insufficient_Memory,
+                "Insufficient_Memory",
+            ),
+            (ApplicationReturnStatus_Internal_Error, "Internal_Error"),
+        ];
+
+        STATUS_NAMES
+            .iter()
+            .find(|(status, _)| *status == self.status)
+            .map_or("Unknown", |(_, name)| name)
+    }
 }
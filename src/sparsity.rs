@@ -0,0 +1,592 @@
+//! # Ipopt Bindgen Sparsity
+//!
+//! Defines types for building the sparsity patterns of the Jacobian and Hessian once, and then
+//! writing their values by handle, so that the structure in `get_jacobian_sparsity` and
+//! `get_hessian_sparsity` cannot drift out of sync with the values in `eval_jac_g` and `eval_h`.
+//!
+//! Conversions to and from compressed sparse row (CSR) and column (CSC) matrices are also provided
+//! for derivatives that are computed in compressed formats.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    ops::{Index, IndexMut},
+};
+
+/// A stable handle to an entry of a sparsity pattern, i.e. its position in the triplet arrays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Slot(usize);
+
+impl Slot {
+    /// Gets the position of the entry in the triplet arrays.
+    #[must_use]
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Records the structure of a sparse matrix, one entry at a time.
+///
+/// # Example
+///
+/// ```
+/// use ipopt_bindgen::TripletBuilder;
+///
+/// let mut jacobian = TripletBuilder::new(2, 3);
+/// let a = jacobian.entry(0, 0);
+/// let b = jacobian.entry(1, 2);
+///
+/// assert_eq!(a, jacobian.entry(0, 0));
+///
+/// let pattern = jacobian.build().unwrap();
+///
+/// let mut values = vec![0.0; pattern.nnz()];
+/// let mut triplets = pattern.values(&mut values);
+/// triplets[a] = 1.0;
+/// triplets[b] = 2.0;
+///
+/// assert_eq!(vec![1.0, 2.0], values);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TripletBuilder {
+    rows: usize,
+    columns: usize,
+    lower_triangular: bool,
+    entries: Vec<(usize, usize)>,
+    slots: HashMap<(usize, usize), Slot>,
+}
+
+impl TripletBuilder {
+    /// Creates a new `TripletBuilder` for a general `rows x columns` matrix, e.g. a Jacobian.
+    ///
+    /// # Parameters
+    ///
+    /// - `rows` - The number of rows.
+    /// - `columns` - The number of columns.
+    #[must_use]
+    pub fn new(rows: usize, columns: usize) -> Self {
+        TripletBuilder {
+            rows,
+            columns,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `TripletBuilder` for the lower triangle of a symmetric `n x n` matrix, e.g.
+    /// the Hessian of the Lagrangian.
+    ///
+    /// # Parameters
+    ///
+    /// - `n` - The number of rows and columns.
+    #[must_use]
+    pub fn lower_triangular(n: usize) -> Self {
+        TripletBuilder {
+            lower_triangular: true,
+            ..TripletBuilder::new(n, n)
+        }
+    }
+
+    /// Adds an entry to the pattern, if it has not already been added.
+    ///
+    /// # Parameters
+    ///
+    /// - `row` - The row of the entry.
+    /// - `column` - The column of the entry.
+    ///
+    /// # Returns
+    ///
+    /// The slot of the entry, which is the same for repeated calls with the same position.
+    pub fn entry(&mut self, row: usize, column: usize) -> Slot {
+        *self.slots.entry((row, column)).or_insert_with(|| {
+            self.entries.push((row, column));
+            Slot(self.entries.len() - 1)
+        })
+    }
+
+    /// Gets the number of entries added so far.
+    #[must_use]
+    pub fn nnz(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Validates the entries and builds the pattern.
+    ///
+    /// # Errors
+    ///
+    /// - An entry is outside of the matrix.
+    /// - An entry is in the strict upper triangle of a lower triangular pattern.
+    /// - The dimensions or number of entries are not representable as an `i32`.
+    pub fn build(self) -> Result<SparsityPattern, Box<dyn Error>> {
+        i32::try_from(self.rows)?;
+        i32::try_from(self.columns)?;
+        i32::try_from(self.entries.len())?;
+
+        for (slot, &(row, column)) in self.entries.iter().enumerate() {
+            if row >= self.rows || column >= self.columns {
+                return Err(format!(
+                    "Entry {slot} at ({row}, {column}) is outside of the {0} x {1} matrix.",
+                    self.rows, self.columns
+                )
+                .into());
+            }
+            if self.lower_triangular && column > row {
+                return Err(format!(
+                    "Entry {slot} at ({row}, {column}) is not in the lower triangle."
+                )
+                .into());
+            }
+        }
+
+        Ok(SparsityPattern {
+            rows: self.rows,
+            columns: self.columns,
+            lower_triangular: self.lower_triangular,
+            entries: self.entries,
+            slots: self.slots,
+        })
+    }
+}
+
+/// Converts a dimension of a pattern that has already been validated by `TripletBuilder::build`,
+/// e.g. for `Tnlp::get_nlp_info`.
+///
+/// # Panics
+///
+/// The value is not representable as an i32, which `TripletBuilder::build` rules out.
+pub(crate) fn dimension_to_i32(value: usize) -> i32 {
+    i32::try_from(value).expect("Sparsity pattern dimensions are validated when built.")
+}
+
+/// A validated sparsity pattern in triplet form.
+///
+/// The order of the entries is the order that they were added to the `TripletBuilder`, which is
+/// the order of the `i_row`, `j_col` and `values` arrays passed to the `Tnlp`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SparsityPattern {
+    rows: usize,
+    columns: usize,
+    lower_triangular: bool,
+    entries: Vec<(usize, usize)>,
+    slots: HashMap<(usize, usize), Slot>,
+}
+
+impl SparsityPattern {
+    /// Gets the number of rows.
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Gets the number of columns.
+    #[must_use]
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Gets the number of entries.
+    #[must_use]
+    pub fn nnz(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks whether the pattern is the lower triangle of a symmetric matrix.
+    #[must_use]
+    pub fn is_lower_triangular(&self) -> bool {
+        self.lower_triangular
+    }
+
+    /// Gets the `(row, column)` positions of the entries, in slot order.
+    #[must_use]
+    pub fn entries(&self) -> &[(usize, usize)] {
+        &self.entries
+    }
+
+    /// Gets the slot of the entry at a position, if it is in the pattern.
+    #[must_use]
+    pub fn slot(&self, row: usize, column: usize) -> Option<Slot> {
+        self.slots.get(&(row, column)).copied()
+    }
+
+    /// Writes the pattern to the index arrays of `get_jacobian_sparsity` or
+    /// `get_hessian_sparsity`.
+    ///
+    /// # Parameters
+    ///
+    /// - `i_row` - The row indices of the entries.
+    /// - `j_col` - The column indices of the entries.
+    ///
+    /// # Panics
+    ///
+    /// The index arrays are shorter than the number of entries.
+    pub fn write_indices(&self, i_row: &mut [i32], j_col: &mut [i32]) {
+        assert!(
+            i_row.len() >= self.nnz() && j_col.len() >= self.nnz(),
+            "The index arrays are shorter than the sparsity pattern."
+        );
+
+        // The dimensions were checked to fit in an i32 when the pattern was built.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        for (k, &(row, column)) in self.entries.iter().enumerate() {
+            i_row[k] = row as i32;
+            j_col[k] = column as i32;
+        }
+    }
+
+    /// Wraps the values array of `eval_jac_g` or `eval_h` so that it can be written by slot.
+    ///
+    /// # Parameters
+    ///
+    /// - `values` - The values of the entries, in slot order.
+    pub fn values<'a>(&self, values: &'a mut [f64]) -> TripletValues<'a> {
+        debug_assert_eq!(self.nnz(), values.len());
+        TripletValues(values)
+    }
+
+    /// Converts values in slot order to a compressed matrix.
+    ///
+    /// # Parameters
+    ///
+    /// - `values` - The values of the entries, in slot order.
+    /// - `format` - The compressed format to convert to.
+    ///
+    /// # Errors
+    ///
+    /// The number of values does not match the number of entries.
+    pub fn to_compressed(
+        &self,
+        values: &[f64],
+        format: CompressedFormat,
+    ) -> Result<CompressedMatrix, Box<dyn Error>> {
+        if values.len() != self.nnz() {
+            return Err(format!(
+                "There are {0} values, but the sparsity pattern has {1} entries.",
+                values.len(),
+                self.nnz()
+            )
+            .into());
+        }
+
+        let major_dimension = match format {
+            CompressedFormat::Row => self.rows,
+            CompressedFormat::Column => self.columns,
+        };
+        let major_minor = |&(row, column): &(usize, usize)| match format {
+            CompressedFormat::Row => (row, column),
+            CompressedFormat::Column => (column, row),
+        };
+
+        let mut order: Vec<usize> = (0..self.nnz()).collect();
+        order.sort_by_key(|&k| major_minor(&self.entries[k]));
+
+        let mut offsets = vec![0; major_dimension + 1];
+        for entry in &self.entries {
+            offsets[major_minor(entry).0 + 1] += 1;
+        }
+        for major in 0..major_dimension {
+            offsets[major + 1] += offsets[major];
+        }
+
+        Ok(CompressedMatrix {
+            format,
+            rows: self.rows,
+            columns: self.columns,
+            offsets,
+            indices: order
+                .iter()
+                .map(|&k| major_minor(&self.entries[k]).1)
+                .collect(),
+            values: order.iter().map(|&k| values[k]).collect(),
+        })
+    }
+
+    /// Creates a pattern with the structure of a compressed matrix, with entries in storage order
+    /// so that the values of the matrix are already in slot order.
+    ///
+    /// # Parameters
+    ///
+    /// - `matrix` - The compressed matrix.
+    ///
+    /// # Errors
+    ///
+    /// - The compressed matrix is malformed.
+    /// - The compressed matrix contains duplicate entries.
+    /// - The dimensions or number of entries are not representable as an `i32`.
+    pub fn from_compressed(matrix: &CompressedMatrix) -> Result<Self, Box<dyn Error>> {
+        matrix.validate()?;
+
+        let mut builder = TripletBuilder::new(matrix.rows, matrix.columns);
+        for (row, column) in matrix.positions() {
+            let nnz = builder.nnz();
+            if builder.entry(row, column).index() != nnz {
+                return Err(format!("Entry ({row}, {column}) is duplicated.").into());
+            }
+        }
+        builder.build()
+    }
+
+    /// Copies the values of a compressed matrix into slot order.
+    ///
+    /// For lower triangular patterns, entries in the strict upper triangle of the matrix are
+    /// ignored, so a full symmetric matrix can be given. Duplicate entries are summed.
+    ///
+    /// # Parameters
+    ///
+    /// - `matrix` - The compressed matrix.
+    /// - `values` - The values of the entries, in slot order.
+    ///
+    /// # Errors
+    ///
+    /// - The compressed matrix is malformed or has different dimensions to the pattern.
+    /// - The compressed matrix contains an entry that is not in the pattern.
+    /// - The number of values does not match the number of entries.
+    pub fn scatter_compressed(
+        &self,
+        matrix: &CompressedMatrix,
+        values: &mut [f64],
+    ) -> Result<(), Box<dyn Error>> {
+        matrix.validate()?;
+        if (matrix.rows, matrix.columns) != (self.rows, self.columns) {
+            return Err(format!(
+                "The matrix is {0} x {1}, but the sparsity pattern is {2} x {3}.",
+                matrix.rows, matrix.columns, self.rows, self.columns
+            )
+            .into());
+        }
+        if values.len() != self.nnz() {
+            return Err(format!(
+                "There are {0} values, but the sparsity pattern has {1} entries.",
+                values.len(),
+                self.nnz()
+            )
+            .into());
+        }
+
+        values.fill(0.0);
+        for ((row, column), &value) in matrix.positions().zip(&matrix.values) {
+            if self.lower_triangular && column > row {
+                continue;
+            }
+            let slot = self.slot(row, column).ok_or_else(|| {
+                format!("Entry ({row}, {column}) is not in the sparsity pattern.")
+            })?;
+            values[slot.index()] += value;
+        }
+        Ok(())
+    }
+}
+
+/// Values in slot order, which can be indexed by `Slot`.
+#[derive(Debug)]
+pub struct TripletValues<'a>(&'a mut [f64]);
+
+impl TripletValues<'_> {
+    /// Sets every value to zero.
+    pub fn clear(&mut self) {
+        self.0.fill(0.0);
+    }
+
+    /// Adds to the value of an entry, e.g. to accumulate the terms of the Hessian.
+    ///
+    /// # Parameters
+    ///
+    /// - `slot` - The slot of the entry.
+    /// - `value` - The value to add.
+    pub fn add(&mut self, slot: Slot, value: f64) {
+        self.0[slot.0] += value;
+    }
+}
+
+impl Index<Slot> for TripletValues<'_> {
+    type Output = f64;
+
+    fn index(&self, slot: Slot) -> &f64 {
+        &self.0[slot.0]
+    }
+}
+
+impl IndexMut<Slot> for TripletValues<'_> {
+    fn index_mut(&mut self, slot: Slot) -> &mut f64 {
+        &mut self.0[slot.0]
+    }
+}
+
+/// The storage order of a compressed sparse matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressedFormat {
+    /// Compressed sparse row (CSR), where `offsets` indexes rows and `indices` are columns.
+    Row,
+
+    /// Compressed sparse column (CSC), where `offsets` indexes columns and `indices` are rows.
+    Column,
+}
+
+/// A compressed sparse matrix in CSR or CSC format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompressedMatrix {
+    /// The storage order.
+    pub format: CompressedFormat,
+
+    /// The number of rows.
+    pub rows: usize,
+
+    /// The number of columns.
+    pub columns: usize,
+
+    /// The offsets of each row (CSR) or column (CSC) in `indices` and `values`, with one more
+    /// element than the number of rows or columns.
+    pub offsets: Vec<usize>,
+
+    /// The column (CSR) or row (CSC) index of each entry.
+    pub indices: Vec<usize>,
+
+    /// The value of each entry.
+    pub values: Vec<f64>,
+}
+
+impl CompressedMatrix {
+    /// Gets the dimensions in storage order.
+    fn major_minor_dimensions(&self) -> (usize, usize) {
+        match self.format {
+            CompressedFormat::Row => (self.rows, self.columns),
+            CompressedFormat::Column => (self.columns, self.rows),
+        }
+    }
+
+    /// Gets the `(row, column)` position of each entry, in storage order.
+    fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets
+            .windows(2)
+            .enumerate()
+            .flat_map(move |(major, range)| {
+                self.indices[range[0]..range[1]]
+                    .iter()
+                    .map(move |&minor| match self.format {
+                        CompressedFormat::Row => (major, minor),
+                        CompressedFormat::Column => (minor, major),
+                    })
+            })
+    }
+
+    /// Checks that the offsets, indices and values are consistent.
+    fn validate(&self) -> Result<(), String> {
+        let (major_dimension, minor_dimension) = self.major_minor_dimensions();
+        let nnz = self.indices.len();
+
+        if self.offsets.len() != major_dimension + 1 {
+            return Err(format!(
+                "The matrix has {0} offsets, but should have {1}.",
+                self.offsets.len(),
+                major_dimension + 1
+            ));
+        }
+        if self.values.len() != nnz {
+            return Err(format!(
+                "The matrix has {0} indices, but {1} values.",
+                nnz,
+                self.values.len()
+            ));
+        }
+        if self.offsets[0] != 0
+            || self.offsets[major_dimension] != nnz
+            || self.offsets.windows(2).any(|range| range[0] > range[1])
+        {
+            return Err("The matrix offsets are not non-decreasing from 0 to nnz.".to_string());
+        }
+        if let Some(index) = self.indices.iter().find(|&&index| index >= minor_dimension) {
+            return Err(format!("The matrix index {index} is out of range."));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jacobian_pattern() -> SparsityPattern {
+        let mut builder = TripletBuilder::new(2, 3);
+        builder.entry(1, 2);
+        builder.entry(0, 1);
+        builder.entry(1, 0);
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn triplet_builder_build_rejects_invalid_entries() {
+        let mut builder = TripletBuilder::new(2, 2);
+        builder.entry(2, 0);
+        assert!(builder.build().is_err());
+
+        let mut builder = TripletBuilder::lower_triangular(2);
+        builder.entry(0, 1);
+        assert!(builder.build().is_err());
+    }
+
+    #[test]
+    fn sparsity_pattern_write_indices_uses_slot_order() {
+        let pattern = jacobian_pattern();
+        let (mut i_row, mut j_col) = ([0; 3], [0; 3]);
+
+        pattern.write_indices(&mut i_row, &mut j_col);
+
+        assert_eq!([1, 0, 1], i_row);
+        assert_eq!([2, 1, 0], j_col);
+        assert_eq!(Some(Slot(1)), pattern.slot(0, 1));
+        assert_eq!(None, pattern.slot(0, 0));
+    }
+
+    #[test]
+    fn sparsity_pattern_to_compressed_sorts_entries() {
+        let pattern = jacobian_pattern();
+
+        let csr = pattern
+            .to_compressed(&[3.0, 1.0, 2.0], CompressedFormat::Row)
+            .unwrap();
+        let csc = pattern
+            .to_compressed(&[3.0, 1.0, 2.0], CompressedFormat::Column)
+            .unwrap();
+
+        assert_eq!(vec![0, 1, 3], csr.offsets);
+        assert_eq!(vec![1, 0, 2], csr.indices);
+        assert_eq!(vec![1.0, 2.0, 3.0], csr.values);
+        assert_eq!(vec![0, 1, 2, 3], csc.offsets);
+        assert_eq!(vec![1, 0, 1], csc.indices);
+        assert_eq!(vec![2.0, 1.0, 3.0], csc.values);
+    }
+
+    #[test]
+    fn sparsity_pattern_compressed_round_trips() {
+        let pattern = jacobian_pattern();
+        let csc = pattern
+            .to_compressed(&[3.0, 1.0, 2.0], CompressedFormat::Column)
+            .unwrap();
+
+        let from_csc = SparsityPattern::from_compressed(&csc).unwrap();
+        let mut values = vec![0.0; 3];
+        pattern.scatter_compressed(&csc, &mut values).unwrap();
+
+        assert_eq!(&[(1, 0), (0, 1), (1, 2)], from_csc.entries());
+        assert_eq!(vec![3.0, 1.0, 2.0], values);
+    }
+
+    #[test]
+    fn sparsity_pattern_scatter_compressed_ignores_upper_triangle() {
+        let mut builder = TripletBuilder::lower_triangular(2);
+        builder.entry(1, 0);
+        builder.entry(0, 0);
+        let pattern = builder.build().unwrap();
+        let full = CompressedMatrix {
+            format: CompressedFormat::Row,
+            rows: 2,
+            columns: 2,
+            offsets: vec![0, 2, 3],
+            indices: vec![0, 1, 0],
+            values: vec![4.0, 5.0, 5.0],
+        };
+        let mut values = vec![0.0; 2];
+
+        pattern.scatter_compressed(&full, &mut values).unwrap();
+
+        assert_eq!(vec![5.0, 4.0], values);
+    }
+}
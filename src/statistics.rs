@@ -0,0 +1,312 @@
+//! # Ipopt Bindgen Statistics
+//!
+//! Defines a parser for the final statistics block that Ipopt prints at the end of an
+//! optimization. This contains values that are not returned by the C interface, such as the scaled
+//! and unscaled optimality measures.
+
+/// A value that Ipopt reports for both the scaled and unscaled problem.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScaledValue {
+    /// The value for the scaled problem (as seen by the algorithm).
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::value"))]
+    pub scaled: f64,
+
+    /// The value for the original, unscaled problem.
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::value"))]
+    pub unscaled: f64,
+}
+
+/// The final statistics printed by Ipopt at the end of an optimization.
+///
+/// Each field is `None` if the corresponding line was not found in the output, e.g. because the
+/// print level was too low or the line is not printed by the installed version of Ipopt.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FinalStatistics {
+    /// The number of iterations.
+    pub number_of_iterations: Option<u32>,
+
+    /// The objective function value.
+    pub objective: Option<ScaledValue>,
+
+    /// The dual infeasibility.
+    pub dual_infeasibility: Option<ScaledValue>,
+
+    /// The constraint violation.
+    pub constraint_violation: Option<ScaledValue>,
+
+    /// The variable bound violation.
+    pub variable_bound_violation: Option<ScaledValue>,
+
+    /// The complementarity.
+    pub complementarity: Option<ScaledValue>,
+
+    /// The overall NLP error.
+    pub overall_nlp_error: Option<ScaledValue>,
+
+    /// The number of objective function evaluations.
+    pub number_of_objective_evaluations: Option<u32>,
+
+    /// The number of objective gradient evaluations.
+    pub number_of_objective_gradient_evaluations: Option<u32>,
+
+    /// The number of equality constraint evaluations.
+    pub number_of_equality_constraint_evaluations: Option<u32>,
+
+    /// The number of inequality constraint evaluations.
+    pub number_of_inequality_constraint_evaluations: Option<u32>,
+
+    /// The number of equality constraint Jacobian evaluations.
+    pub number_of_equality_jacobian_evaluations: Option<u32>,
+
+    /// The number of inequality constraint Jacobian evaluations.
+    pub number_of_inequality_jacobian_evaluations: Option<u32>,
+
+    /// The number of Lagrangian Hessian evaluations.
+    pub number_of_hessian_evaluations: Option<u32>,
+
+    /// The total number of seconds spent in Ipopt (`Total seconds in IPOPT`).
+    #[cfg_attr(feature = "serde", serde(default, with = "crate::float_serde::option"))]
+    pub total_seconds: Option<f64>,
+
+    /// The CPU seconds spent in Ipopt, excluding function evaluations. Only reported by older
+    /// versions of Ipopt.
+    #[cfg_attr(feature = "serde", serde(default, with = "crate::float_serde::option"))]
+    pub ipopt_cpu_seconds: Option<f64>,
+
+    /// The CPU seconds spent in function evaluations. Only reported by older versions of Ipopt.
+    #[cfg_attr(feature = "serde", serde(default, with = "crate::float_serde::option"))]
+    pub function_evaluation_cpu_seconds: Option<f64>,
+
+    /// The exit message, e.g. `Optimal Solution Found.`
+    pub exit_message: Option<String>,
+}
+
+impl FinalStatistics {
+    /// Parses the final statistics from the console output of Ipopt.
+    ///
+    /// If the output contains more than one optimization, the statistics of the last one are
+    /// parsed.
+    ///
+    /// # Parameters
+    ///
+    /// - `output` - The console output of Ipopt.
+    ///
+    /// # Returns
+    ///
+    /// The final statistics, or `None` if the output does not contain a final statistics block.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ipopt_bindgen::FinalStatistics;
+    ///
+    /// let output = "\
+    /// Number of Iterations....: 8
+    ///
+    ///                                    (scaled)                 (unscaled)
+    /// Objective...............:   1.7014017145179164e+01    1.7014017145179164e+01
+    ///
+    /// EXIT: Optimal Solution Found.
+    /// ";
+    ///
+    /// let statistics = FinalStatistics::parse(output).unwrap();
+    ///
+    /// assert_eq!(Some(8), statistics.number_of_iterations);
+    /// assert_eq!(Some("Optimal Solution Found."), statistics.exit_message.as_deref());
+    /// ```
+    #[must_use]
+    pub fn parse(output: &str) -> Option<Self> {
+        const ITERATIONS_LABEL: &str = "Number of Iterations";
+
+        let start = output.rfind(ITERATIONS_LABEL)?;
+        let mut statistics = FinalStatistics::default();
+
+        for line in output[start..].lines() {
+            let line = line.trim();
+            if let Some(message) = line.strip_prefix("EXIT:") {
+                statistics.exit_message = Some(message.trim().to_string());
+            } else if let Some((label, values)) = line.split_once(':') {
+                statistics.parse_colon_line(label.trim_end_matches('.').trim(), values);
+            } else if let Some((label, value)) = line.split_once('=') {
+                statistics.parse_equals_line(label.trim(), value.trim());
+            }
+        }
+
+        Some(statistics)
+    }
+
+    /// Parses a line of the form `Label.....: value [value]`.
+    fn parse_colon_line(&mut self, label: &str, values: &str) {
+        let field = match label {
+            "Number of Iterations" => {
+                self.number_of_iterations = values.trim().parse().ok();
+                return;
+            }
+            "Objective" => &mut self.objective,
+            "Dual infeasibility" => &mut self.dual_infeasibility,
+            "Constraint violation" => &mut self.constraint_violation,
+            "Variable bound violation" => &mut self.variable_bound_violation,
+            "Complementarity" => &mut self.complementarity,
+            "Overall NLP error" => &mut self.overall_nlp_error,
+            _ => return,
+        };
+
+        let mut numbers = values.split_whitespace().map(str::parse::<f64>);
+        if let (Some(Ok(scaled)), Some(Ok(unscaled))) = (numbers.next(), numbers.next()) {
+            *field = Some(ScaledValue { scaled, unscaled });
+        }
+    }
+
+    /// Parses a line of the form `Label      = value`.
+    fn parse_equals_line(&mut self, label: &str, value: &str) {
+        if let Some(count) = self.count_field(label) {
+            *count = value.parse().ok();
+        } else if let Some(seconds) = self.seconds_field(label) {
+            *seconds = value.parse().ok();
+        }
+    }
+
+    /// Gets the evaluation count field for a label.
+    fn count_field(&mut self, label: &str) -> Option<&mut Option<u32>> {
+        match label {
+            "Number of objective function evaluations" => {
+                Some(&mut self.number_of_objective_evaluations)
+            }
+            "Number of objective gradient evaluations" => {
+                Some(&mut self.number_of_objective_gradient_evaluations)
+            }
+            "Number of equality constraint evaluations" => {
+                Some(&mut self.number_of_equality_constraint_evaluations)
+            }
+            "Number of inequality constraint evaluations" => {
+                Some(&mut self.number_of_inequality_constraint_evaluations)
+            }
+            "Number of equality constraint Jacobian evaluations" => {
+                Some(&mut self.number_of_equality_jacobian_evaluations)
+            }
+            "Number of inequality constraint Jacobian evaluations" => {
+                Some(&mut self.number_of_inequality_jacobian_evaluations)
+            }
+            "Number of Lagrangian Hessian evaluations" => {
+                Some(&mut self.number_of_hessian_evaluations)
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the timing field for a label.
+    fn seconds_field(&mut self, label: &str) -> Option<&mut Option<f64>> {
+        match label {
+            "Total seconds in IPOPT" => Some(&mut self.total_seconds),
+            "Total CPU secs in IPOPT (w/o function evaluations)" => {
+                Some(&mut self.ipopt_cpu_seconds)
+            }
+            "Total CPU secs in NLP function evaluations" => {
+                Some(&mut self.function_evaluation_cpu_seconds)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IPOPT_3_14_OUTPUT: &str = "
+iter    objective    inf_pr   inf_du lg(mu)  ||d||  lg(rg) alpha_du alpha_pr  ls
+   0  1.6109693e+01 1.12e+01 5.28e-01   0.0 0.00e+00    -  0.00e+00 0.00e+00   0
+   8  1.7014017e+01 1.42e-14 1.07e-14  -8.6 1.48e-07    -  1.00e+00 1.00e+00h  1
+
+Number of Iterations....: 8
+
+                                   (scaled)                 (unscaled)
+Objective...............:   1.7014017145179164e+01    1.7014017145179164e+01
+Dual infeasibility......:   1.0658141036401503e-14    1.0658141036401503e-14
+Constraint violation....:   1.4210854715202004e-14    1.4210854715202004e-14
+Variable bound violation:   9.9999992642575627e-09    9.9999992642575627e-09
+Complementarity.........:   2.5252844952972291e-09    2.5252844952972291e-09
+Overall NLP error.......:   2.5252844952972291e-09    2.5252844952972291e-09
+
+
+Number of objective function evaluations             = 9
+Number of objective gradient evaluations             = 9
+Number of equality constraint evaluations            = 9
+Number of inequality constraint evaluations          = 9
+Number of equality constraint Jacobian evaluations   = 9
+Number of inequality constraint Jacobian evaluations = 9
+Number of Lagrangian Hessian evaluations             = 8
+Total seconds in IPOPT                               = 0.004
+
+EXIT: Optimal Solution Found.
+";
+
+    #[test]
+    fn final_statistics_parse_reads_ipopt_3_14_output() {
+        let statistics = FinalStatistics::parse(IPOPT_3_14_OUTPUT).unwrap();
+
+        assert_eq!(Some(8), statistics.number_of_iterations);
+        assert_eq!(
+            Some(ScaledValue {
+                scaled: 1.701_401_714_517_916_4e1,
+                unscaled: 1.701_401_714_517_916_4e1,
+            }),
+            statistics.objective
+        );
+        assert_eq!(
+            Some(9.999_999_264_257_562e-9),
+            statistics.variable_bound_violation.map(|v| v.unscaled)
+        );
+        assert_eq!(
+            Some(2.525_284_495_297_229_1e-9),
+            statistics.overall_nlp_error.map(|v| v.scaled)
+        );
+        assert_eq!(Some(9), statistics.number_of_objective_evaluations);
+        assert_eq!(
+            Some(9),
+            statistics.number_of_inequality_jacobian_evaluations
+        );
+        assert_eq!(Some(8), statistics.number_of_hessian_evaluations);
+        assert_eq!(Some(0.004), statistics.total_seconds);
+        assert_eq!(None, statistics.ipopt_cpu_seconds);
+        assert_eq!(
+            Some("Optimal Solution Found."),
+            statistics.exit_message.as_deref()
+        );
+    }
+
+    #[test]
+    fn final_statistics_parse_reads_older_timing_lines() {
+        let output = "\
+Number of Iterations....: 3
+Total CPU secs in IPOPT (w/o function evaluations)   =      0.008
+Total CPU secs in NLP function evaluations           =      0.001
+";
+
+        let statistics = FinalStatistics::parse(output).unwrap();
+
+        assert_eq!(Some(3), statistics.number_of_iterations);
+        assert_eq!(Some(0.008), statistics.ipopt_cpu_seconds);
+        assert_eq!(Some(0.001), statistics.function_evaluation_cpu_seconds);
+        assert_eq!(None, statistics.total_seconds);
+    }
+
+    #[test]
+    fn final_statistics_parse_uses_last_block() {
+        let output = "Number of Iterations....: 3\nNumber of Iterations....: 5\n";
+
+        let statistics = FinalStatistics::parse(output).unwrap();
+
+        assert_eq!(Some(5), statistics.number_of_iterations);
+    }
+
+    #[test]
+    fn final_statistics_parse_returns_none_without_statistics() {
+        assert_eq!(
+            None,
+            FinalStatistics::parse("EXIT: Optimal Solution Found.")
+        );
+    }
+}
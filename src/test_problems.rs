@@ -0,0 +1,2341 @@
+//! # Ipopt Bindgen Test Problems
+//!
+//! Defines a collection of standard test problems with known optimal objective values, for
+//! regression testing solver settings and wrappers. Enabled by the crate feature `test_problems`.
+//!
+//! The collection contains the following problems of the Hock-Schittkowski collection, with the
+//! starting points, optimal objective values and solutions given by Hock and Schittkowski (1981):
+//! HS001-HS024, HS026-HS056, HS059-HS066, HS071-HS081, HS083, HS084, HS093, HS095-HS100,
+//! HS106-HS114 and HS116-HS119. The solutions of HS059, HS072, HS074, HS075, HS093, HS095-HS099,
+//! HS109, HS114 and HS119 are refined to satisfy the first-order optimality conditions to about
+//! ten digits, so that they satisfy their constraints accurately.
+//!
+//! Some problems of the collection cannot be expressed with the modeling layer: HS067 is defined
+//! through an iterative computation, HS068 and HS069 use the cumulative distribution function of
+//! the normal distribution, and HS087 has a piecewise objective. The other numbers between HS001
+//! and HS119 are not included yet.
+//!
+//! It also contains the following scalable problems:
+//!
+//! - `rosenbrock` - The generalized Rosenbrock function.
+//! - `chained_wood` - The chained Wood function.
+//! - `broyden_tridiagonal` - The Broyden tridiagonal system of equations, as constraints.
+//! - `bratu` - The finite difference discretization of the two-dimensional Bratu problem, as
+//!   constraints.
+//! - `optimal_control` - The forward Euler discretization of a linear-quadratic optimal control
+//!   problem, whose optimal objective is found with the discrete Riccati equation.
+//!
+//! The problems are written with the modeling layer, so the derivatives are exact.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use ipopt_bindgen::{Application, hock_schittkowski_problems};
+//!
+//! let application = Application::new();
+//! for problem in hock_schittkowski_problems() {
+//!     let name = problem.name().to_string();
+//!     let expected = problem.clone();
+//!     let result = application.optimize_tnlp(problem).unwrap();
+//!     println!("{name}: {}", expected.is_solved_by(&result, 1e-6));
+//! }
+//! ```
+
+// The problems are written with the single-letter names of the literature.
+#![allow(clippy::many_single_char_names)]
+
+use crate::{
+    modeling::{CompiledModel, Expr, Model, Var},
+    results::OptimizationResult,
+    tnlp::{InitialSolution, IntermediateData, OptionValue, ProblemSize, Tnlp, UserScaling},
+};
+use std::f64::consts::{FRAC_PI_2, PI, SQRT_2};
+
+const INF: f64 = f64::INFINITY;
+const FREE: (f64, f64) = (f64::NEG_INFINITY, f64::INFINITY);
+
+/// A test problem with a known optimal objective value, which implements `Tnlp`.
+#[derive(Debug, Clone)]
+pub struct TestProblem {
+    name: String,
+    optimal_objective: f64,
+    solution: Option<Vec<f64>>,
+    model: CompiledModel,
+}
+
+impl TestProblem {
+    /// Compiles a model of a test problem.
+    fn new(name: impl Into<String>, model: &Model, optimal_objective: f64) -> Self {
+        TestProblem {
+            name: name.into(),
+            optimal_objective,
+            solution: None,
+            model: model
+                .compile()
+                .expect("The test problems only use their own variables."),
+        }
+    }
+
+    /// Sets the known solution of the test problem.
+    fn with_solution(mut self, solution: Vec<f64>) -> Self {
+        self.solution = Some(solution);
+        self
+    }
+
+    /// Gets the name of the problem, e.g. `HS071`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the known optimal objective value.
+    #[must_use]
+    pub fn optimal_objective(&self) -> f64 {
+        self.optimal_objective
+    }
+
+    /// Gets the known solution, if the problem has a unique one that is known.
+    ///
+    /// Solutions from the literature are only given to a limited number of digits.
+    #[must_use]
+    pub fn solution(&self) -> Option<&[f64]> {
+        self.solution.as_deref()
+    }
+
+    /// Checks whether an optimization of this problem succeeded and found the known optimal
+    /// objective value.
+    ///
+    /// # Parameters
+    ///
+    /// - `result` - The result of optimizing this problem.
+    /// - `tolerance` - The tolerance on the objective, relative to the magnitude of the optimal
+    ///   objective if that is greater than one.
+    #[must_use]
+    pub fn is_solved_by(&self, result: &OptimizationResult, tolerance: f64) -> bool {
+        let error = (result.solution.objective - self.optimal_objective).abs();
+        result.succeeded() && error <= tolerance * self.optimal_objective.abs().max(1.0)
+    }
+}
+
+impl Tnlp for TestProblem {
+    fn get_nlp_info(&self) -> ProblemSize {
+        self.model.get_nlp_info()
+    }
+
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
+        self.model.get_bounds_info(x_l, x_u, g_l, g_u);
+    }
+
+    fn get_scaling(&self) -> UserScaling {
+        self.model.get_scaling()
+    }
+
+    fn get_starting_point(&self) -> InitialSolution {
+        self.model.get_starting_point()
+    }
+
+    fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+        self.model.eval_f(x, obj_value)
+    }
+
+    fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+        self.model.eval_grad_f(x, grad_f)
+    }
+
+    fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+        self.model.eval_g(x, g)
+    }
+
+    fn get_jacobian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.model.get_jacobian_sparsity(n, m, i_row, j_col);
+    }
+
+    fn eval_jac_g(&mut self, x: &[f64], m: i32, values: &mut [f64]) -> bool {
+        self.model.eval_jac_g(x, m, values)
+    }
+
+    fn get_hessian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        self.model.get_hessian_sparsity(n, m, i_row, j_col);
+    }
+
+    fn eval_h(
+        &mut self,
+        x: &[f64],
+        obj_factor: f64,
+        lambda: &[f64],
+        m: i32,
+        values: &mut [f64],
+    ) -> bool {
+        self.model.eval_h(x, obj_factor, lambda, m, values)
+    }
+
+    fn intermediate(&mut self, data: IntermediateData) -> bool {
+        self.model.intermediate(data)
+    }
+
+    fn variable_names(&self) -> Option<Vec<String>> {
+        self.model.variable_names()
+    }
+
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        self.model.default_options()
+    }
+}
+
+/// Gets a problem of the Hock-Schittkowski collection.
+///
+/// # Parameters
+///
+/// - `number` - The number of the problem, e.g. `71` for HS071.
+///
+/// # Returns
+///
+/// The problem, or `None` if it is not in this collection.
+#[must_use]
+pub fn hock_schittkowski(number: u32) -> Option<TestProblem> {
+    HOCK_SCHITTKOWSKI
+        .iter()
+        .find(|(known, _)| *known == number)
+        .map(|(_, problem)| problem())
+}
+
+/// Gets all problems of the Hock-Schittkowski collection that this collection contains, in
+/// order.
+#[must_use]
+pub fn hock_schittkowski_problems() -> Vec<TestProblem> {
+    HOCK_SCHITTKOWSKI
+        .iter()
+        .map(|(_, problem)| problem())
+        .collect()
+}
+
+/// Gets the generalized Rosenbrock problem,
+/// `Σ 100 (x_{i+1} - x_i²)² + (1 - x_i)²`, which has a minimum of zero at `x = 1`.
+///
+/// # Parameters
+///
+/// - `n` - The number of variables, which must be at least two.
+///
+/// # Panics
+///
+/// `n` is less than two.
+#[must_use]
+pub fn rosenbrock(n: usize) -> TestProblem {
+    assert!(
+        n >= 2,
+        "The Rosenbrock problem needs at least two variables."
+    );
+    let start: Vec<f64> = (0..n)
+        .map(|i| if i % 2 == 0 { -1.2 } else { 1.0 })
+        .collect();
+    let (mut model, x) = variables(&vec![FREE; n], &start);
+    model.minimize(Expr::sum(x.windows(2).map(|pair| {
+        100.0 * (pair[1] - pair[0].powi(2)).powi(2) + (1.0 - pair[0]).powi(2)
+    })));
+    TestProblem::new(format!("ROSENBROCK{n}"), &model, 0.0).with_solution(vec![1.0; n])
+}
+
+/// Gets the chained Wood problem, which has a minimum of zero at `x = 1`.
+///
+/// # Parameters
+///
+/// - `n` - The number of variables, which must be a multiple of two and at least four.
+///
+/// # Panics
+///
+/// `n` is not an even number of at least four.
+#[must_use]
+pub fn chained_wood(n: usize) -> TestProblem {
+    assert!(
+        n >= 4 && n.is_multiple_of(2),
+        "The chained Wood problem needs an even number of at least four variables."
+    );
+    let start: Vec<f64> = (0..n)
+        .map(|i| if i % 2 == 0 { -3.0 } else { -1.0 })
+        .collect();
+    let (mut model, x) = variables(&vec![FREE; n], &start);
+    model.minimize(Expr::sum((0..n - 3).step_by(2).map(|j| {
+        100.0 * (x[j + 1] - x[j].powi(2)).powi(2)
+            + (1.0 - x[j]).powi(2)
+            + 90.0 * (x[j + 3] - x[j + 2].powi(2)).powi(2)
+            + (1.0 - x[j + 2]).powi(2)
+            + 10.1 * ((x[j + 1] - 1.0).powi(2) + (x[j + 3] - 1.0).powi(2))
+            + 19.8 * (x[j + 1] - 1.0) * (x[j + 3] - 1.0)
+    })));
+    TestProblem::new(format!("CHAINWOO{n}"), &model, 0.0).with_solution(vec![1.0; n])
+}
+
+/// Gets the Broyden tridiagonal system `(3 - 2 x_i) x_i - x_{i-1} - 2 x_{i+1} + 1 = 0`, with
+/// `x_0 = x_{n+1} = 0`, as a feasibility problem with a zero objective.
+///
+/// # Parameters
+///
+/// - `n` - The number of variables and equations.
+#[must_use]
+pub fn broyden_tridiagonal(n: usize) -> TestProblem {
+    let (mut model, x) = variables(&vec![FREE; n], &vec![-1.0; n]);
+    let at = |i: Option<usize>| {
+        i.and_then(|i| x.get(i))
+            .map_or(Expr::constant(0.0), |&x| x.into())
+    };
+    for (i, &x_i) in x.iter().enumerate() {
+        let residual = (3.0 - 2.0 * x_i) * x_i - at(i.checked_sub(1)) - 2.0 * at(Some(i + 1)) + 1.0;
+        model.add_constraint(residual, 0.0, 0.0);
+    }
+    TestProblem::new(format!("BROYDNBD{n}"), &model, 0.0)
+}
+
+/// Gets the two-dimensional Bratu problem `-Δu = λ exp(u)` on the unit square with `u = 0` on
+/// the boundary, discretized with central differences on an `n x n` grid of interior points, as a
+/// feasibility problem with a zero objective.
+///
+/// # Parameters
+///
+/// - `n` - The number of interior grid points in each direction.
+/// - `lambda` - The parameter `λ`, for which a solution exists if it is at most about `6.8`.
+#[must_use]
+pub fn bratu(n: usize, lambda: f64) -> TestProblem {
+    let (mut model, u) = variables(&vec![FREE; n * n], &vec![0.0; n * n]);
+    #[allow(clippy::cast_precision_loss)]
+    let h = 1.0 / (n + 1) as f64;
+    let at = |i: Option<usize>, j: Option<usize>| match (i, j) {
+        (Some(i), Some(j)) if i < n && j < n => Expr::from(u[i * n + j]),
+        _ => Expr::constant(0.0),
+    };
+    for i in 0..n {
+        for j in 0..n {
+            let center = u[i * n + j];
+            let laplacian = 4.0 * center
+                - at(i.checked_sub(1), Some(j))
+                - at(Some(i + 1), Some(j))
+                - at(Some(i), j.checked_sub(1))
+                - at(Some(i), Some(j + 1));
+            model.add_constraint(laplacian - h * h * lambda * center.exp(), 0.0, 0.0);
+        }
+    }
+    TestProblem::new(format!("BRATU2D{n}"), &model, 0.0)
+}
+
+/// Gets the forward Euler discretization of the optimal control problem
+///
+/// ```text
+/// minimize    ∫₀¹ x(t)² + u(t)² dt
+/// subject to  x'(t) = u(t), x(0) = 1
+/// ```
+///
+/// with `n` steps. The variables are the states `x_0, ..., x_n` followed by the controls
+/// `u_0, ..., u_{n-1}`, and the optimal objective and solution are found with the discrete Riccati
+/// equation.
+///
+/// # Parameters
+///
+/// - `n` - The number of steps, which must be at least one.
+///
+/// # Panics
+///
+/// `n` is zero.
+#[must_use]
+pub fn optimal_control(n: usize) -> TestProblem {
+    assert!(
+        n >= 1,
+        "The optimal control problem needs at least one step."
+    );
+    #[allow(clippy::cast_precision_loss)]
+    let h = 1.0 / n as f64;
+    let mut bounds = vec![FREE; 2 * n + 1];
+    bounds[0] = (1.0, 1.0);
+    let mut start = vec![0.0; 2 * n + 1];
+    start[0] = 1.0;
+    let (mut model, v) = variables(&bounds, &start);
+    let (x, u) = v.split_at(n + 1);
+
+    model.minimize(Expr::sum((0..n).map(|k| h * (x[k].powi(2) + u[k].powi(2)))));
+    for k in 0..n {
+        model.add_constraint(x[k + 1] - x[k] - h * u[k], 0.0, 0.0);
+    }
+
+    // The cost-to-go is p_k x_k², with p_n = 0 and the optimal control u_k = -p_{k+1} x_k /
+    // (1 + h p_{k+1}).
+    let mut cost_to_go = vec![0.0; n + 1];
+    for k in (0..n).rev() {
+        let p = cost_to_go[k + 1];
+        cost_to_go[k] = h + p - p * p * h / (1.0 + h * p);
+    }
+    let mut solution = vec![0.0; 2 * n + 1];
+    solution[0] = 1.0;
+    for k in 0..n {
+        let p = cost_to_go[k + 1];
+        solution[n + 1 + k] = -p * solution[k] / (1.0 + h * p);
+        solution[k + 1] = solution[k] + h * solution[n + 1 + k];
+    }
+
+    TestProblem::new(format!("CONTROL{n}"), &model, cost_to_go[0]).with_solution(solution)
+}
+
+/// Creates a model with variables with the given bounds and starting point.
+fn variables(bounds: &[(f64, f64)], start: &[f64]) -> (Model, Vec<Var>) {
+    let mut model = Model::new();
+    let x = bounds
+        .iter()
+        .zip(start)
+        .enumerate()
+        .map(|(j, (&(lower, upper), &initial))| {
+            model.add_var(format!("x{}", j + 1), lower, upper, initial)
+        })
+        .collect();
+    (model, x)
+}
+
+/// Creates a Hock-Schittkowski problem from its model, optimal objective and solution.
+fn hs(number: u32, model: &Model, optimal_objective: f64, solution: Vec<f64>) -> TestProblem {
+    TestProblem::new(format!("HS{number:03}"), model, optimal_objective).with_solution(solution)
+}
+
+/// The Rosenbrock function of HS001-HS002, HS015-HS017 and HS020.
+fn rosenbrock_2d(x: &[Var]) -> Expr {
+    100.0 * (x[1] - x[0].powi(2)).powi(2) + (1.0 - x[0]).powi(2)
+}
+
+/// The problems of the Hock-Schittkowski collection, by number.
+const HOCK_SCHITTKOWSKI: [(u32, fn() -> TestProblem); 96] = [
+    (1, hs001),
+    (2, hs002),
+    (3, hs003),
+    (4, hs004),
+    (5, hs005),
+    (6, hs006),
+    (7, hs007),
+    (8, hs008),
+    (9, hs009),
+    (10, hs010),
+    (11, hs011),
+    (12, hs012),
+    (13, hs013),
+    (14, hs014),
+    (15, hs015),
+    (16, hs016),
+    (17, hs017),
+    (18, hs018),
+    (19, hs019),
+    (20, hs020),
+    (21, hs021),
+    (22, hs022),
+    (23, hs023),
+    (24, hs024),
+    (26, hs026),
+    (27, hs027),
+    (28, hs028),
+    (29, hs029),
+    (30, hs030),
+    (31, hs031),
+    (32, hs032),
+    (33, hs033),
+    (34, hs034),
+    (35, hs035),
+    (36, hs036),
+    (37, hs037),
+    (38, hs038),
+    (39, hs039),
+    (40, hs040),
+    (41, hs041),
+    (42, hs042),
+    (43, hs043),
+    (44, hs044),
+    (45, hs045),
+    (46, hs046),
+    (47, hs047),
+    (48, hs048),
+    (49, hs049),
+    (50, hs050),
+    (51, hs051),
+    (52, hs052),
+    (53, hs053),
+    (54, hs054),
+    (55, hs055),
+    (56, hs056),
+    (59, hs059),
+    (60, hs060),
+    (61, hs061),
+    (62, hs062),
+    (63, hs063),
+    (64, hs064),
+    (65, hs065),
+    (66, hs066),
+    (71, hs071),
+    (72, hs072),
+    (73, hs073),
+    (74, hs074),
+    (75, hs075),
+    (76, hs076),
+    (77, hs077),
+    (78, hs078),
+    (79, hs079),
+    (80, hs080),
+    (81, hs081),
+    (83, hs083),
+    (84, hs084),
+    (93, hs093),
+    (95, hs095),
+    (96, hs096),
+    (97, hs097),
+    (98, hs098),
+    (99, hs099),
+    (100, hs100),
+    (106, hs106),
+    (107, hs107),
+    (108, hs108),
+    (109, hs109),
+    (110, hs110),
+    (111, hs111),
+    (112, hs112),
+    (113, hs113),
+    (114, hs114),
+    (116, hs116),
+    (117, hs117),
+    (118, hs118),
+    (119, hs119),
+];
+
+fn hs001() -> TestProblem {
+    let (mut model, x) = variables(&[FREE, (-1.5, INF)], &[-2.0, 1.0]);
+    model.minimize(rosenbrock_2d(&x));
+    hs(1, &model, 0.0, vec![1.0, 1.0])
+}
+
+fn hs002() -> TestProblem {
+    let (mut model, x) = variables(&[FREE, (1.5, INF)], &[-2.0, 1.0]);
+    model.minimize(rosenbrock_2d(&x));
+    hs(2, &model, 0.050_426_187_9, vec![1.224_370_748_7, 1.5])
+}
+
+fn hs003() -> TestProblem {
+    let (mut model, x) = variables(&[FREE, (0.0, INF)], &[10.0, 1.0]);
+    model.minimize(x[1] + 1e-5 * (x[1] - x[0]).powi(2));
+    hs(3, &model, 0.0, vec![0.0, 0.0])
+}
+
+fn hs004() -> TestProblem {
+    let (mut model, x) = variables(&[(1.0, INF), (0.0, INF)], &[1.125, 0.125]);
+    model.minimize((x[0] + 1.0).powi(3) / 3.0 + x[1]);
+    hs(4, &model, 8.0 / 3.0, vec![1.0, 0.0])
+}
+
+fn hs005() -> TestProblem {
+    let (mut model, x) = variables(&[(-1.5, 4.0), (-3.0, 3.0)], &[0.0, 0.0]);
+    model.minimize((x[0] + x[1]).sin() + (x[0] - x[1]).powi(2) - 1.5 * x[0] + 2.5 * x[1] + 1.0);
+    let solution = vec![0.5 - PI / 3.0, -0.5 - PI / 3.0];
+    hs(5, &model, -(3.0_f64.sqrt()) / 2.0 - PI / 3.0, solution)
+}
+
+fn hs006() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 2], &[-1.2, 1.0]);
+    model.minimize((1.0 - x[0]).powi(2));
+    model.add_constraint(10.0 * (x[1] - x[0].powi(2)), 0.0, 0.0);
+    hs(6, &model, 0.0, vec![1.0, 1.0])
+}
+
+fn hs007() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 2], &[2.0, 2.0]);
+    model.minimize((1.0 + x[0].powi(2)).ln() - x[1]);
+    model.add_constraint((1.0 + x[0].powi(2)).powi(2) + x[1].powi(2) - 4.0, 0.0, 0.0);
+    hs(7, &model, -(3.0_f64.sqrt()), vec![0.0, 3.0_f64.sqrt()])
+}
+
+fn hs008() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 2], &[2.0, 1.0]);
+    model.minimize(-1.0);
+    model.add_constraint(x[0].powi(2) + x[1].powi(2) - 25.0, 0.0, 0.0);
+    model.add_constraint(x[0] * x[1] - 9.0, 0.0, 0.0);
+    let x1 = f64::midpoint(25.0, 301.0_f64.sqrt()).sqrt();
+    hs(8, &model, -1.0, vec![x1, 9.0 / x1])
+}
+
+fn hs009() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 2], &[0.0, 0.0]);
+    model.minimize((PI / 12.0 * x[0]).sin() * (PI / 16.0 * x[1]).cos());
+    model.add_constraint(4.0 * x[0] - 3.0 * x[1], 0.0, 0.0);
+    hs(9, &model, -0.5, vec![-3.0, -4.0])
+}
+
+fn hs010() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 2], &[-10.0, 10.0]);
+    model.minimize(x[0] - x[1]);
+    model.add_constraint(
+        -3.0 * x[0].powi(2) + 2.0 * x[0] * x[1] - x[1].powi(2) + 1.0,
+        0.0,
+        INF,
+    );
+    hs(10, &model, -1.0, vec![0.0, 1.0])
+}
+
+fn hs011() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 2], &[4.9, 0.1]);
+    model.minimize((x[0] - 5.0).powi(2) + x[1].powi(2) - 25.0);
+    model.add_constraint(-x[0].powi(2) + x[1], 0.0, INF);
+    hs(
+        11,
+        &model,
+        -8.498_464_223,
+        vec![1.234_779_580, 1.524_680_558],
+    )
+}
+
+fn hs012() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 2], &[0.0, 0.0]);
+    model.minimize(0.5 * x[0].powi(2) + x[1].powi(2) - x[0] * x[1] - 7.0 * x[0] - 7.0 * x[1]);
+    model.add_constraint(25.0 - 4.0 * x[0].powi(2) - x[1].powi(2), 0.0, INF);
+    hs(12, &model, -30.0, vec![2.0, 3.0])
+}
+
+fn hs013() -> TestProblem {
+    let (mut model, x) = variables(&[(0.0, INF); 2], &[-2.0, -2.0]);
+    model.minimize((x[0] - 2.0).powi(2) + x[1].powi(2));
+    model.add_constraint((1.0 - x[0]).powi(3) - x[1], 0.0, INF);
+    hs(13, &model, 1.0, vec![1.0, 0.0])
+}
+
+fn hs014() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 2], &[2.0, 2.0]);
+    model.minimize((x[0] - 2.0).powi(2) + (x[1] - 1.0).powi(2));
+    model.add_constraint(-x[0].powi(2) / 4.0 - x[1].powi(2) + 1.0, 0.0, INF);
+    model.add_constraint(x[0] - 2.0 * x[1] + 1.0, 0.0, 0.0);
+    let sqrt_7 = 7.0_f64.sqrt();
+    let solution = vec![0.5 * (sqrt_7 - 1.0), 0.25 * (sqrt_7 + 1.0)];
+    hs(14, &model, 9.0 - 23.0 / 8.0 * sqrt_7, solution)
+}
+
+fn hs015() -> TestProblem {
+    let (mut model, x) = variables(&[(f64::NEG_INFINITY, 0.5), FREE], &[-2.0, 1.0]);
+    model.minimize(rosenbrock_2d(&x));
+    model.add_constraint(x[0] * x[1] - 1.0, 0.0, INF);
+    model.add_constraint(x[0] + x[1].powi(2), 0.0, INF);
+    hs(15, &model, 306.5, vec![0.5, 2.0])
+}
+
+fn hs016() -> TestProblem {
+    let (mut model, x) = variables(&[(-0.5, 0.5), (f64::NEG_INFINITY, 1.0)], &[-2.0, 1.0]);
+    model.minimize(rosenbrock_2d(&x));
+    model.add_constraint(x[0] + x[1].powi(2), 0.0, INF);
+    model.add_constraint(x[0].powi(2) + x[1], 0.0, INF);
+    hs(16, &model, 0.25, vec![0.5, 0.25])
+}
+
+fn hs017() -> TestProblem {
+    let (mut model, x) = variables(&[(-0.5, 0.5), (f64::NEG_INFINITY, 1.0)], &[-2.0, 1.0]);
+    model.minimize(rosenbrock_2d(&x));
+    model.add_constraint(x[1].powi(2) - x[0], 0.0, INF);
+    model.add_constraint(x[0].powi(2) - x[1], 0.0, INF);
+    hs(17, &model, 1.0, vec![0.0, 0.0])
+}
+
+fn hs018() -> TestProblem {
+    let (mut model, x) = variables(&[(2.0, 50.0), (0.0, 50.0)], &[2.0, 2.0]);
+    model.minimize(0.01 * x[0].powi(2) + x[1].powi(2));
+    model.add_constraint(x[0] * x[1] - 25.0, 0.0, INF);
+    model.add_constraint(x[0].powi(2) + x[1].powi(2) - 25.0, 0.0, INF);
+    hs(18, &model, 5.0, vec![250.0_f64.sqrt(), 2.5_f64.sqrt()])
+}
+
+fn hs019() -> TestProblem {
+    let (mut model, x) = variables(&[(13.0, 100.0), (0.0, 100.0)], &[20.1, 5.84]);
+    model.minimize((x[0] - 10.0).powi(3) + (x[1] - 20.0).powi(3));
+    model.add_constraint(
+        (x[0] - 5.0).powi(2) + (x[1] - 5.0).powi(2) - 100.0,
+        0.0,
+        INF,
+    );
+    model.add_constraint(
+        -(x[1] - 5.0).powi(2) - (x[0] - 6.0).powi(2) + 82.81,
+        0.0,
+        INF,
+    );
+    hs(19, &model, -6_961.813_81, vec![14.095, 0.842_960_79])
+}
+
+fn hs020() -> TestProblem {
+    let (mut model, x) = variables(&[(-0.5, 0.5), FREE], &[-2.0, 1.0]);
+    model.minimize(rosenbrock_2d(&x));
+    model.add_constraint(x[0] + x[1].powi(2), 0.0, INF);
+    model.add_constraint(x[0].powi(2) + x[1], 0.0, INF);
+    model.add_constraint(x[0].powi(2) + x[1].powi(2) - 1.0, 0.0, INF);
+    let sqrt_3 = 3.0_f64.sqrt();
+    hs(20, &model, 81.5 - 25.0 * sqrt_3, vec![0.5, sqrt_3 / 2.0])
+}
+
+fn hs021() -> TestProblem {
+    let (mut model, x) = variables(&[(2.0, 50.0), (-50.0, 50.0)], &[-1.0, -1.0]);
+    model.minimize(0.01 * x[0].powi(2) + x[1].powi(2) - 100.0);
+    model.add_constraint(10.0 * x[0] - x[1] - 10.0, 0.0, INF);
+    hs(21, &model, -99.96, vec![2.0, 0.0])
+}
+
+fn hs022() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 2], &[2.0, 2.0]);
+    model.minimize((x[0] - 2.0).powi(2) + (x[1] - 1.0).powi(2));
+    model.add_constraint(-x[0] - x[1] + 2.0, 0.0, INF);
+    model.add_constraint(-x[0].powi(2) + x[1], 0.0, INF);
+    hs(22, &model, 1.0, vec![1.0, 1.0])
+}
+
+fn hs023() -> TestProblem {
+    let (mut model, x) = variables(&[(-50.0, 50.0); 2], &[3.0, 1.0]);
+    model.minimize(x[0].powi(2) + x[1].powi(2));
+    model.add_constraint(x[0] + x[1] - 1.0, 0.0, INF);
+    model.add_constraint(x[0].powi(2) + x[1].powi(2) - 1.0, 0.0, INF);
+    model.add_constraint(9.0 * x[0].powi(2) + x[1].powi(2) - 9.0, 0.0, INF);
+    model.add_constraint(x[0].powi(2) - x[1], 0.0, INF);
+    model.add_constraint(x[1].powi(2) - x[0], 0.0, INF);
+    hs(23, &model, 2.0, vec![1.0, 1.0])
+}
+
+fn hs024() -> TestProblem {
+    let sqrt_3 = 3.0_f64.sqrt();
+    let (mut model, x) = variables(&[(0.0, INF); 2], &[1.0, 0.5]);
+    model.minimize(((x[0] - 3.0).powi(2) - 9.0) * x[1].powi(3) / (27.0 * sqrt_3));
+    model.add_constraint(x[0] / sqrt_3 - x[1], 0.0, INF);
+    model.add_constraint(x[0] + sqrt_3 * x[1], 0.0, INF);
+    model.add_constraint(-x[0] - sqrt_3 * x[1] + 6.0, 0.0, INF);
+    hs(24, &model, -1.0, vec![3.0, sqrt_3])
+}
+
+fn hs026() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 3], &[-2.6, 2.0, 2.0]);
+    model.minimize((x[0] - x[1]).powi(2) + (x[1] - x[2]).powi(4));
+    model.add_constraint((1.0 + x[1].powi(2)) * x[0] + x[2].powi(4) - 3.0, 0.0, 0.0);
+    hs(26, &model, 0.0, vec![1.0; 3])
+}
+
+fn hs027() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 3], &[2.0; 3]);
+    model.minimize(0.01 * (x[0] - 1.0).powi(2) + (x[1] - x[0].powi(2)).powi(2));
+    model.add_constraint(x[0] + x[2].powi(2) + 1.0, 0.0, 0.0);
+    hs(27, &model, 0.04, vec![-1.0, 1.0, 0.0])
+}
+
+fn hs028() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 3], &[-4.0, 1.0, 1.0]);
+    model.minimize((x[0] + x[1]).powi(2) + (x[1] + x[2]).powi(2));
+    model.add_constraint(x[0] + 2.0 * x[1] + 3.0 * x[2] - 1.0, 0.0, 0.0);
+    hs(28, &model, 0.0, vec![0.5, -0.5, 0.5])
+}
+
+fn hs029() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 3], &[1.0; 3]);
+    model.minimize(-x[0] * x[1] * x[2]);
+    model.add_constraint(
+        -x[0].powi(2) - 2.0 * x[1].powi(2) - 4.0 * x[2].powi(2) + 48.0,
+        0.0,
+        INF,
+    );
+    hs(29, &model, -16.0 * SQRT_2, vec![4.0, 2.0 * SQRT_2, 2.0])
+}
+
+fn hs030() -> TestProblem {
+    let (mut model, x) = variables(&[(1.0, 10.0), (-10.0, 10.0), (-10.0, 10.0)], &[1.0; 3]);
+    model.minimize(x[0].powi(2) + x[1].powi(2) + x[2].powi(2));
+    model.add_constraint(x[0].powi(2) + x[1].powi(2) - 1.0, 0.0, INF);
+    hs(30, &model, 1.0, vec![1.0, 0.0, 0.0])
+}
+
+fn hs031() -> TestProblem {
+    let (mut model, x) = variables(&[(-10.0, 10.0), (1.0, 10.0), (-10.0, 1.0)], &[1.0; 3]);
+    model.minimize(9.0 * x[0].powi(2) + x[1].powi(2) + 9.0 * x[2].powi(2));
+    model.add_constraint(x[0] * x[1] - 1.0, 0.0, INF);
+    let sqrt_3 = 3.0_f64.sqrt();
+    hs(31, &model, 6.0, vec![1.0 / sqrt_3, sqrt_3, 0.0])
+}
+
+fn hs032() -> TestProblem {
+    let (mut model, x) = variables(&[(0.0, INF); 3], &[0.1, 0.7, 0.2]);
+    model.minimize((x[0] + 3.0 * x[1] + x[2]).powi(2) + 4.0 * (x[0] - x[1]).powi(2));
+    model.add_constraint(6.0 * x[1] + 4.0 * x[2] - x[0].powi(3) - 3.0, 0.0, INF);
+    model.add_constraint(1.0 - x[0] - x[1] - x[2], 0.0, 0.0);
+    hs(32, &model, 1.0, vec![0.0, 0.0, 1.0])
+}
+
+fn hs033() -> TestProblem {
+    let (mut model, x) = variables(&[(0.0, INF), (0.0, INF), (0.0, 5.0)], &[0.0, 0.0, 3.0]);
+    model.minimize((x[0] - 1.0) * (x[0] - 2.0) * (x[0] - 3.0) + x[2]);
+    model.add_constraint(x[2].powi(2) - x[1].powi(2) - x[0].powi(2), 0.0, INF);
+    model.add_constraint(x[0].powi(2) + x[1].powi(2) + x[2].powi(2) - 4.0, 0.0, INF);
+    hs(33, &model, SQRT_2 - 6.0, vec![0.0, SQRT_2, SQRT_2])
+}
+
+fn hs034() -> TestProblem {
+    let (mut model, x) = variables(
+        &[(0.0, 100.0), (0.0, 100.0), (0.0, 10.0)],
+        &[0.0, 1.05, 2.9],
+    );
+    model.minimize(-x[0]);
+    model.add_constraint(x[1] - x[0].exp(), 0.0, INF);
+    model.add_constraint(x[2] - x[1].exp(), 0.0, INF);
+    let ln_10 = 10.0_f64.ln();
+    hs(34, &model, -ln_10.ln(), vec![ln_10.ln(), ln_10, 10.0])
+}
+
+fn hs035() -> TestProblem {
+    let (mut model, x) = variables(&[(0.0, INF); 3], &[0.5; 3]);
+    model.minimize(
+        9.0 - 8.0 * x[0] - 6.0 * x[1] - 4.0 * x[2]
+            + 2.0 * x[0].powi(2)
+            + 2.0 * x[1].powi(2)
+            + x[2].powi(2)
+            + 2.0 * x[0] * x[1]
+            + 2.0 * x[0] * x[2],
+    );
+    model.add_constraint(3.0 - x[0] - x[1] - 2.0 * x[2], 0.0, INF);
+    hs(35, &model, 1.0 / 9.0, vec![4.0 / 3.0, 7.0 / 9.0, 4.0 / 9.0])
+}
+
+fn hs036() -> TestProblem {
+    let (mut model, x) = variables(&[(0.0, 20.0), (0.0, 11.0), (0.0, 42.0)], &[10.0; 3]);
+    model.minimize(-x[0] * x[1] * x[2]);
+    model.add_constraint(72.0 - x[0] - 2.0 * x[1] - 2.0 * x[2], 0.0, INF);
+    hs(36, &model, -3300.0, vec![20.0, 11.0, 15.0])
+}
+
+fn hs037() -> TestProblem {
+    let (mut model, x) = variables(&[(0.0, 42.0); 3], &[10.0; 3]);
+    model.minimize(-x[0] * x[1] * x[2]);
+    model.add_constraint(72.0 - x[0] - 2.0 * x[1] - 2.0 * x[2], 0.0, INF);
+    model.add_constraint(x[0] + 2.0 * x[1] + 2.0 * x[2], 0.0, INF);
+    hs(37, &model, -3456.0, vec![24.0, 12.0, 12.0])
+}
+
+fn hs038() -> TestProblem {
+    let (mut model, x) = variables(&[(-10.0, 10.0); 4], &[-3.0, -1.0, -3.0, -1.0]);
+    model.minimize(
+        100.0 * (x[1] - x[0].powi(2)).powi(2)
+            + (1.0 - x[0]).powi(2)
+            + 90.0 * (x[3] - x[2].powi(2)).powi(2)
+            + (1.0 - x[2]).powi(2)
+            + 10.1 * ((x[1] - 1.0).powi(2) + (x[3] - 1.0).powi(2))
+            + 19.8 * (x[1] - 1.0) * (x[3] - 1.0),
+    );
+    hs(38, &model, 0.0, vec![1.0; 4])
+}
+
+fn hs039() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 4], &[2.0; 4]);
+    model.minimize(-x[0]);
+    model.add_constraint(x[1] - x[0].powi(3) - x[2].powi(2), 0.0, 0.0);
+    model.add_constraint(x[0].powi(2) - x[1] - x[3].powi(2), 0.0, 0.0);
+    hs(39, &model, -1.0, vec![1.0, 1.0, 0.0, 0.0])
+}
+
+fn hs040() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 4], &[0.8; 4]);
+    model.minimize(-x[0] * x[1] * x[2] * x[3]);
+    model.add_constraint(x[0].powi(3) + x[1].powi(2) - 1.0, 0.0, 0.0);
+    model.add_constraint(x[0].powi(2) * x[3] - x[2], 0.0, 0.0);
+    model.add_constraint(x[3].powi(2) - x[1], 0.0, 0.0);
+    let solution = [-1.0 / 3.0, -0.5, -11.0 / 12.0, -0.25]
+        .map(|power| 2.0_f64.powf(power))
+        .to_vec();
+    hs(40, &model, -0.25, solution)
+}
+
+fn hs041() -> TestProblem {
+    let (mut model, x) = variables(&[(0.0, 1.0), (0.0, 1.0), (0.0, 1.0), (0.0, 2.0)], &[2.0; 4]);
+    model.minimize(2.0 - x[0] * x[1] * x[2]);
+    model.add_constraint(x[0] + 2.0 * x[1] + 2.0 * x[2] - x[3], 0.0, 0.0);
+    hs(
+        41,
+        &model,
+        52.0 / 27.0,
+        vec![2.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0, 2.0],
+    )
+}
+
+fn hs042() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 4], &[1.0; 4]);
+    model.minimize(
+        (x[0] - 1.0).powi(2) + (x[1] - 2.0).powi(2) + (x[2] - 3.0).powi(2) + (x[3] - 4.0).powi(2),
+    );
+    model.add_constraint(x[0] - 2.0, 0.0, 0.0);
+    model.add_constraint(x[2].powi(2) + x[3].powi(2) - 2.0, 0.0, 0.0);
+    let solution = vec![2.0, 2.0, 0.6 * SQRT_2, 0.8 * SQRT_2];
+    hs(42, &model, 28.0 - 10.0 * SQRT_2, solution)
+}
+
+fn hs043() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 4], &[0.0; 4]);
+    let squares: Vec<Expr> = x.iter().map(|x| x.powi(2)).collect();
+    model.minimize(
+        &squares[0] + &squares[1] + 2.0 * &squares[2] + &squares[3]
+            - 5.0 * x[0]
+            - 5.0 * x[1]
+            - 21.0 * x[2]
+            + 7.0 * x[3],
+    );
+    model.add_constraint(
+        8.0 - Expr::sum(&squares) - x[0] + x[1] - x[2] + x[3],
+        0.0,
+        INF,
+    );
+    model.add_constraint(
+        10.0 - &squares[0] - 2.0 * &squares[1] - &squares[2] - 2.0 * &squares[3] + x[0] + x[3],
+        0.0,
+        INF,
+    );
+    model.add_constraint(
+        5.0 - 2.0 * &squares[0] - &squares[1] - &squares[2] - 2.0 * x[0] + x[1] + x[3],
+        0.0,
+        INF,
+    );
+    hs(43, &model, -44.0, vec![0.0, 1.0, 2.0, -1.0])
+}
+
+fn hs044() -> TestProblem {
+    let (mut model, x) = variables(&[(0.0, INF); 4], &[0.0; 4]);
+    model.minimize(x[0] - x[1] - x[2] - x[0] * x[2] + x[0] * x[3] + x[1] * x[2] - x[1] * x[3]);
+    let rows = [
+        (8.0, [1.0, 2.0, 0.0, 0.0]),
+        (12.0, [4.0, 1.0, 0.0, 0.0]),
+        (12.0, [3.0, 4.0, 0.0, 0.0]),
+        (8.0, [0.0, 0.0, 2.0, 1.0]),
+        (8.0, [0.0, 0.0, 1.0, 2.0]),
+        (5.0, [0.0, 0.0, 1.0, 1.0]),
+    ];
+    for (rhs, coefficients) in rows {
+        let lhs = Expr::sum(coefficients.iter().zip(&x).map(|(&a, &x)| a * x));
+        model.add_constraint(rhs - lhs, 0.0, INF);
+    }
+    hs(44, &model, -15.0, vec![0.0, 3.0, 0.0, 4.0])
+}
+
+fn hs045() -> TestProblem {
+    let (mut model, x) = variables(
+        &[(0.0, 1.0), (0.0, 2.0), (0.0, 3.0), (0.0, 4.0), (0.0, 5.0)],
+        &[2.0; 5],
+    );
+    model.minimize(2.0 - x[0] * x[1] * x[2] * x[3] * x[4] / 120.0);
+    hs(45, &model, 1.0, vec![1.0, 2.0, 3.0, 4.0, 5.0])
+}
+
+fn hs046() -> TestProblem {
+    let start = [SQRT_2 / 2.0, 1.75, 0.5, 2.0, 2.0];
+    let (mut model, x) = variables(&[FREE; 5], &start);
+    model.minimize(
+        (x[0] - x[1]).powi(2) + (x[2] - 1.0).powi(2) + (x[3] - 1.0).powi(4) + (x[4] - 1.0).powi(6),
+    );
+    model.add_constraint(x[0].powi(2) * x[3] + (x[3] - x[4]).sin() - 1.0, 0.0, 0.0);
+    model.add_constraint(x[1] + x[2].powi(4) * x[3].powi(2) - 2.0, 0.0, 0.0);
+    hs(46, &model, 0.0, vec![1.0; 5])
+}
+
+fn hs047() -> TestProblem {
+    let start = [2.0, SQRT_2, -1.0, 2.0 - SQRT_2, 0.5];
+    let (mut model, x) = variables(&[FREE; 5], &start);
+    model.minimize(
+        (x[0] - x[1]).powi(2)
+            + (x[1] - x[2]).powi(3)
+            + (x[2] - x[3]).powi(4)
+            + (x[3] - x[4]).powi(4),
+    );
+    model.add_constraint(x[0] + x[1].powi(2) + x[2].powi(3) - 3.0, 0.0, 0.0);
+    model.add_constraint(x[1] - x[2].powi(2) + x[3] - 1.0, 0.0, 0.0);
+    model.add_constraint(x[0] * x[4] - 1.0, 0.0, 0.0);
+    hs(47, &model, 0.0, vec![1.0; 5])
+}
+
+fn hs048() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 5], &[3.0, 5.0, -3.0, 2.0, -2.0]);
+    model.minimize((x[0] - 1.0).powi(2) + (x[1] - x[2]).powi(2) + (x[3] - x[4]).powi(2));
+    model.add_constraint(Expr::sum(x.iter().copied()) - 5.0, 0.0, 0.0);
+    model.add_constraint(x[2] - 2.0 * (x[3] + x[4]) + 3.0, 0.0, 0.0);
+    hs(48, &model, 0.0, vec![1.0; 5])
+}
+
+fn hs049() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 5], &[10.0, 7.0, 2.0, -3.0, 0.8]);
+    model.minimize(
+        (x[0] - x[1]).powi(2) + (x[2] - 1.0).powi(2) + (x[3] - 1.0).powi(4) + (x[4] - 1.0).powi(6),
+    );
+    model.add_constraint(x[0] + x[1] + x[2] + 4.0 * x[3] - 7.0, 0.0, 0.0);
+    model.add_constraint(x[2] + 5.0 * x[4] - 6.0, 0.0, 0.0);
+    hs(49, &model, 0.0, vec![1.0; 5])
+}
+
+fn hs050() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 5], &[35.0, -31.0, 11.0, 5.0, -5.0]);
+    model.minimize(
+        (x[0] - x[1]).powi(2)
+            + (x[1] - x[2]).powi(2)
+            + (x[2] - x[3]).powi(4)
+            + (x[3] - x[4]).powi(2),
+    );
+    for i in 0..3 {
+        model.add_constraint(x[i] + 2.0 * x[i + 1] + 3.0 * x[i + 2] - 6.0, 0.0, 0.0);
+    }
+    hs(50, &model, 0.0, vec![1.0; 5])
+}
+
+/// The objective of HS051 and HS053.
+fn hs051_objective(x: &[Var]) -> Expr {
+    (x[0] - x[1]).powi(2)
+        + (x[1] + x[2] - 2.0).powi(2)
+        + (x[3] - 1.0).powi(2)
+        + (x[4] - 1.0).powi(2)
+}
+
+/// The constraints of HS051-HS053, with the right-hand side of the first constraint.
+fn hs051_constraints(model: &mut Model, x: &[Var], rhs: f64) {
+    model.add_constraint(x[0] + 3.0 * x[1] - rhs, 0.0, 0.0);
+    model.add_constraint(x[2] + x[3] - 2.0 * x[4], 0.0, 0.0);
+    model.add_constraint(x[1] - x[4], 0.0, 0.0);
+}
+
+fn hs051() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 5], &[2.5, 0.5, 2.0, -1.0, 0.5]);
+    model.minimize(hs051_objective(&x));
+    hs051_constraints(&mut model, &x, 4.0);
+    hs(51, &model, 0.0, vec![1.0; 5])
+}
+
+fn hs052() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 5], &[2.0; 5]);
+    model.minimize(
+        (4.0 * x[0] - x[1]).powi(2)
+            + (x[1] + x[2] - 2.0).powi(2)
+            + (x[3] - 1.0).powi(2)
+            + (x[4] - 1.0).powi(2),
+    );
+    hs051_constraints(&mut model, &x, 0.0);
+    let solution = [-33.0, 11.0, 180.0, -158.0, 11.0]
+        .map(|value| value / 349.0)
+        .to_vec();
+    hs(52, &model, 1859.0 / 349.0, solution)
+}
+
+fn hs053() -> TestProblem {
+    let (mut model, x) = variables(&[(-10.0, 10.0); 5], &[2.0; 5]);
+    model.minimize(hs051_objective(&x));
+    hs051_constraints(&mut model, &x, 0.0);
+    let solution = [-33.0, 11.0, 27.0, -5.0, 11.0]
+        .map(|value| value / 43.0)
+        .to_vec();
+    hs(53, &model, 176.0 / 43.0, solution)
+}
+
+fn hs054() -> TestProblem {
+    let bounds = [
+        (0.0, 2e4),
+        (-10.0, 10.0),
+        (0.0, 1e7),
+        (0.0, 20.0),
+        (-1.0, 1.0),
+        (0.0, 2e8),
+    ];
+    let (mut model, x) = variables(&bounds, &[6e3, 1.5, 4e6, 2.0, 3e-3, 5e7]);
+    let (u, v) = (x[0] - 1e4, x[1] - 1.0);
+    let h = (u.powi(2) / 6.4e7 + &u * &v / 2e4 + v.powi(2)) / 0.96
+        + (x[2] - 2e6).powi(2) / 4.9e13
+        + (x[3] - 10.0).powi(2) / 2.5e3
+        + (x[4] - 1e-3).powi(2) / 2.5e-3
+        + (x[5] - 1e8).powi(2) / 2.5e17;
+    model.minimize(-(-0.5 * h).exp());
+    model.add_constraint(x[0] + 4000.0 * x[1] - 17_600.0, 0.0, 0.0);
+    let solution = vec![91_600.0 / 7.0, 79.0 / 70.0, 2e6, 10.0, 1e-3, 1e8];
+    hs(54, &model, -0.908_074_757_8, solution)
+}
+
+fn hs055() -> TestProblem {
+    let bounds = [
+        (0.0, 1.0),
+        (0.0, INF),
+        (0.0, INF),
+        (0.0, 1.0),
+        (0.0, INF),
+        (0.0, INF),
+    ];
+    let (mut model, x) = variables(&bounds, &[1.0, 2.0, 0.0, 0.0, 0.0, 2.0]);
+    model.minimize(x[0] + 2.0 * x[1] + 4.0 * x[4] + (x[0] * x[3]).exp());
+    model.add_constraint(x[0] + 2.0 * x[1] + 5.0 * x[4] - 6.0, 0.0, 0.0);
+    model.add_constraint(x[0] + x[1] + x[2] - 3.0, 0.0, 0.0);
+    model.add_constraint(x[3] + x[4] + x[5] - 2.0, 0.0, 0.0);
+    model.add_constraint(x[0] + x[3] - 1.0, 0.0, 0.0);
+    model.add_constraint(x[1] + x[4] - 2.0, 0.0, 0.0);
+    model.add_constraint(x[2] + x[5] - 2.0, 0.0, 0.0);
+    let solution = vec![0.0, 4.0 / 3.0, 5.0 / 3.0, 1.0, 2.0 / 3.0, 1.0 / 3.0];
+    hs(55, &model, 19.0 / 3.0, solution)
+}
+
+fn hs056() -> TestProblem {
+    let a = (1.0 / 4.2_f64).sqrt().asin();
+    let b = (5.0 / 7.2_f64).sqrt().asin();
+    let (mut model, x) = variables(&[FREE; 7], &[1.0, 1.0, 1.0, a, a, a, b]);
+    model.minimize(-x[0] * x[1] * x[2]);
+    for i in 0..3 {
+        model.add_constraint(x[i] - 4.2 * x[i + 3].sin().powi(2), 0.0, 0.0);
+    }
+    model.add_constraint(
+        x[0] + 2.0 * x[1] + 2.0 * x[2] - 7.2 * x[6].sin().powi(2),
+        0.0,
+        0.0,
+    );
+    let (c, d) = ((4.0 / 7.0_f64).sqrt().asin(), (2.0 / 7.0_f64).sqrt().asin());
+    hs(56, &model, -3.456, vec![2.4, 1.2, 1.2, c, d, d, FRAC_PI_2])
+}
+
+fn hs059() -> TestProblem {
+    let (mut model, x) = variables(&[(0.0, 75.0), (0.0, 65.0)], &[90.0, 10.0]);
+    let (x1, x2) = (x[0], x[1]);
+    model.minimize(
+        -75.196 + 3.8112 * x1 - 0.126_94 * x1.powi(2) + 0.002_056_7 * x1.powi(3)
+            - 1.0345e-5 * x1.powi(4)
+            + 6.8306 * x2
+            - 0.030_234 * x1 * x2
+            + 1.281_34e-3 * x2 * x1.powi(2)
+            + 2.266e-7 * x1.powi(4) * x2
+            - 0.256_45 * x2.powi(2)
+            + 0.003_460_4 * x2.powi(3)
+            - 1.3514e-5 * x2.powi(4)
+            + 28.106 / (x2 + 1.0)
+            + 5.2375e-6 * x1.powi(2) * x2.powi(2)
+            + 6.3e-8 * x1.powi(3) * x2.powi(2)
+            - 7e-10 * x1.powi(3) * x2.powi(3)
+            - 3.405e-4 * x1 * x2.powi(2)
+            + 1.6638e-6 * x1 * x2.powi(3)
+            + 2.8673 * (0.0005 * x1 * x2).exp()
+            - 3.5256e-5 * x1.powi(3) * x2,
+    );
+    model.add_constraint(x1 * x2 - 700.0, 0.0, INF);
+    model.add_constraint(x2 - x1.powi(2) / 125.0, 0.0, INF);
+    model.add_constraint((x2 - 50.0).powi(2) - 5.0 * (x1 - 55.0), 0.0, INF);
+    hs(
+        59,
+        &model,
+        -7.802_789_472,
+        vec![13.550_142_32, 51.659_973_99],
+    )
+}
+
+fn hs060() -> TestProblem {
+    let (mut model, x) = variables(&[(-10.0, 10.0); 3], &[2.0; 3]);
+    model.minimize((x[0] - 1.0).powi(2) + (x[0] - x[1]).powi(2) + (x[1] - x[2]).powi(4));
+    model.add_constraint(
+        x[0] * (1.0 + x[1].powi(2)) + x[2].powi(4) - 4.0 - 3.0 * SQRT_2,
+        0.0,
+        0.0,
+    );
+    let solution = vec![1.104_859_024, 1.196_674_194, 1.535_262_257];
+    hs(60, &model, 0.032_568_200_25, solution)
+}
+
+fn hs061() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 3], &[0.0; 3]);
+    model.minimize(
+        4.0 * x[0].powi(2) + 2.0 * x[1].powi(2) + 2.0 * x[2].powi(2) - 33.0 * x[0] + 16.0 * x[1]
+            - 24.0 * x[2],
+    );
+    model.add_constraint(3.0 * x[0] - 2.0 * x[1].powi(2) - 7.0, 0.0, 0.0);
+    model.add_constraint(4.0 * x[0] - x[2].powi(2) - 11.0, 0.0, 0.0);
+    let solution = vec![5.326_770_157, -2.118_998_639, 3.210_464_239];
+    hs(61, &model, -143.646_142_2, solution)
+}
+
+fn hs062() -> TestProblem {
+    let (mut model, x) = variables(&[(0.0, 1.0); 3], &[0.7, 0.2, 0.1]);
+    let term = |weight: f64, numerator: Expr, denominator: Expr| {
+        weight * ((numerator + 0.03) / (denominator + 0.03)).ln()
+    };
+    model.minimize(
+        -32.174
+            * (term(255.0, x[0] + x[1] + x[2], 0.09 * x[0] + x[1] + x[2])
+                + term(280.0, x[1] + x[2], 0.07 * x[1] + x[2])
+                + term(290.0, x[2].into(), 0.13 * x[2])),
+    );
+    model.add_constraint(x[0] + x[1] + x[2] - 1.0, 0.0, 0.0);
+    let solution = vec![0.617_812_6, 0.328_216_4, 0.053_970_98];
+    hs(62, &model, -26_272.514_48, solution)
+}
+
+fn hs063() -> TestProblem {
+    let (mut model, x) = variables(&[(0.0, INF); 3], &[2.0; 3]);
+    model.minimize(
+        1000.0 - x[0].powi(2) - 2.0 * x[1].powi(2) - x[2].powi(2) - x[0] * x[1] - x[0] * x[2],
+    );
+    model.add_constraint(8.0 * x[0] + 14.0 * x[1] + 7.0 * x[2] - 56.0, 0.0, 0.0);
+    model.add_constraint(x[0].powi(2) + x[1].powi(2) + x[2].powi(2) - 25.0, 0.0, 0.0);
+    let solution = vec![3.512_118_414, 0.216_988_174_1, 3.552_174_034];
+    hs(63, &model, 961.715_172_1, solution)
+}
+
+fn hs064() -> TestProblem {
+    let (mut model, x) = variables(&[(1e-5, INF); 3], &[1.0; 3]);
+    model.minimize(
+        5.0 * x[0]
+            + 50_000.0 / x[0]
+            + 20.0 * x[1]
+            + 72_000.0 / x[1]
+            + 10.0 * x[2]
+            + 144_000.0 / x[2],
+    );
+    model.add_constraint(1.0 - 4.0 / x[0] - 32.0 / x[1] - 120.0 / x[2], 0.0, INF);
+    let solution = vec![108.734_717_5, 85.126_139_42, 204.324_707_8];
+    hs(64, &model, 6_299.842_428, solution)
+}
+
+fn hs065() -> TestProblem {
+    let (mut model, x) = variables(&[(-4.5, 4.5), (-4.5, 4.5), (-5.0, 5.0)], &[-5.0, 5.0, 0.0]);
+    model.minimize(
+        (x[0] - x[1]).powi(2) + (x[0] + x[1] - 10.0).powi(2) / 9.0 + (x[2] - 5.0).powi(2),
+    );
+    model.add_constraint(48.0 - x[0].powi(2) - x[1].powi(2) - x[2].powi(2), 0.0, INF);
+    let solution = vec![3.650_461_821, 3.650_461_821, 4.620_417_051];
+    hs(65, &model, 0.953_528_856_7, solution)
+}
+
+fn hs066() -> TestProblem {
+    let (mut model, x) = variables(
+        &[(0.0, 100.0), (0.0, 100.0), (0.0, 10.0)],
+        &[0.0, 1.05, 2.9],
+    );
+    model.minimize(0.2 * x[2] - 0.8 * x[0]);
+    model.add_constraint(x[1] - x[0].exp(), 0.0, INF);
+    model.add_constraint(x[2] - x[1].exp(), 0.0, INF);
+    let solution = vec![0.184_126_487_9, 1.202_167_873, 3.327_322_322];
+    hs(66, &model, 0.518_163_274_1, solution)
+}
+
+fn hs071() -> TestProblem {
+    let (mut model, x) = variables(&[(1.0, 5.0); 4], &[1.0, 5.0, 5.0, 1.0]);
+    model.minimize(x[0] * x[3] * (x[0] + x[1] + x[2]) + x[2]);
+    model.add_constraint(x[0] * x[1] * x[2] * x[3], 25.0, INF);
+    model.add_constraint(Expr::sum(x.iter().map(|x| x.powi(2))), 40.0, 40.0);
+    let solution = vec![1.0, 4.742_999_4, 3.821_150_3, 1.379_408_2];
+    hs(71, &model, 17.014_017_3, solution)
+}
+
+fn hs072() -> TestProblem {
+    let bounds = [(0.001, 4e5), (0.001, 3e5), (0.001, 2e5), (0.001, 1e5)];
+    let (mut model, x) = variables(&bounds, &[1.0; 4]);
+    model.minimize(1.0 + x[0] + x[1] + x[2] + x[3]);
+    model.add_constraint(
+        0.0401 - 4.0 / x[0] - 2.25 / x[1] - 1.0 / x[2] - 0.25 / x[3],
+        0.0,
+        INF,
+    );
+    model.add_constraint(
+        0.010_085 - 0.16 / x[0] - 0.36 / x[1] - 0.64 / x[2] - 0.64 / x[3],
+        0.0,
+        INF,
+    );
+    let solution = vec![193.407_427_3, 179.547_076_0, 185.018_063_4, 168.706_791_1];
+    hs(72, &model, 727.679_357_8, solution)
+}
+
+fn hs073() -> TestProblem {
+    let (mut model, x) = variables(&[(0.0, INF); 4], &[1.0; 4]);
+    model.minimize(24.55 * x[0] + 26.75 * x[1] + 39.0 * x[2] + 40.50 * x[3]);
+    model.add_constraint(
+        2.3 * x[0] + 5.6 * x[1] + 11.1 * x[2] + 1.3 * x[3] - 5.0,
+        0.0,
+        INF,
+    );
+    let deviation =
+        (0.28 * x[0].powi(2) + 0.19 * x[1].powi(2) + 20.5 * x[2].powi(2) + 0.62 * x[3].powi(2))
+            .sqrt();
+    model.add_constraint(
+        12.0 * x[0] + 11.9 * x[1] + 41.8 * x[2] + 52.1 * x[3] - 21.0 - 1.645 * deviation,
+        0.0,
+        INF,
+    );
+    model.add_constraint(x[0] + x[1] + x[2] + x[3] - 1.0, 0.0, 0.0);
+    let solution = vec![0.635_521_6, 0.0, 0.312_701_9, 0.051_776_55];
+    hs(73, &model, 29.894_378, solution)
+}
+
+/// The model of HS074 and HS075, with the bound `a` on the difference of the angles.
+fn hs074_model(a: f64) -> Model {
+    let bounds = [(0.0, 1200.0), (0.0, 1200.0), (-a, a), (-a, a)];
+    let (mut model, x) = variables(&bounds, &[0.0; 4]);
+    model.minimize(3.0 * x[0] + 1e-6 * x[0].powi(3) + 2.0 * x[1] + 2e-6 / 3.0 * x[1].powi(3));
+    model.add_constraint(x[3] - x[2] + a, 0.0, INF);
+    model.add_constraint(x[2] - x[3] + a, 0.0, INF);
+    model.add_constraint(
+        1000.0 * (-x[2] - 0.25).sin() + 1000.0 * (-x[3] - 0.25).sin() + 894.8 - x[0],
+        0.0,
+        0.0,
+    );
+    model.add_constraint(
+        1000.0 * (x[2] - 0.25).sin() + 1000.0 * (x[2] - x[3] - 0.25).sin() + 894.8 - x[1],
+        0.0,
+        0.0,
+    );
+    model.add_constraint(
+        1000.0 * (x[3] - 0.25).sin() + 1000.0 * (x[3] - x[2] - 0.25).sin() + 1294.8,
+        0.0,
+        0.0,
+    );
+    model
+}
+
+fn hs074() -> TestProblem {
+    let solution = vec![
+        679.945_319_9,
+        1_026.067_133,
+        0.118_876_364_5,
+        -0.396_233_553_2,
+    ];
+    hs(74, &hs074_model(0.55), 5_126.498_110, solution)
+}
+
+fn hs075() -> TestProblem {
+    let solution = vec![
+        776.159_026_6,
+        925.195_138_5,
+        0.051_108_928_54,
+        -0.428_891_071_5,
+    ];
+    hs(75, &hs074_model(0.48), 5_174.412_695, solution)
+}
+
+fn hs076() -> TestProblem {
+    let (mut model, x) = variables(&[(0.0, INF); 4], &[0.5; 4]);
+    model.minimize(
+        x[0].powi(2) + 0.5 * x[1].powi(2) + x[2].powi(2) + 0.5 * x[3].powi(2) - x[0] * x[2]
+            + x[2] * x[3]
+            - x[0]
+            - 3.0 * x[1]
+            + x[2]
+            - x[3],
+    );
+    model.add_constraint(5.0 - x[0] - 2.0 * x[1] - x[2] - x[3], 0.0, INF);
+    model.add_constraint(4.0 - 3.0 * x[0] - x[1] - 2.0 * x[2] + x[3], 0.0, INF);
+    model.add_constraint(x[1] + 4.0 * x[2] - 1.5, 0.0, INF);
+    let solution = vec![3.0 / 11.0, 23.0 / 11.0, 0.0, 6.0 / 11.0];
+    hs(76, &model, -4.681_818_181, solution)
+}
+
+fn hs077() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 5], &[2.0; 5]);
+    model.minimize(
+        (x[0] - 1.0).powi(2)
+            + (x[0] - x[1]).powi(2)
+            + (x[2] - 1.0).powi(2)
+            + (x[3] - 1.0).powi(4)
+            + (x[4] - 1.0).powi(6),
+    );
+    model.add_constraint(
+        x[0].powi(2) * x[3] + (x[3] - x[4]).sin() - 2.0 * SQRT_2,
+        0.0,
+        0.0,
+    );
+    model.add_constraint(x[1] + x[2].powi(4) * x[3].powi(2) - 8.0 - SQRT_2, 0.0, 0.0);
+    let solution = vec![1.166_172, 1.182_111, 1.380_257, 1.506_036, 0.610_920_3];
+    hs(77, &model, 0.241_505_13, solution)
+}
+
+/// The constraints of HS078, HS080 and HS081.
+fn hs078_constraints(model: &mut Model, x: &[Var]) {
+    model.add_constraint(Expr::sum(x.iter().map(|x| x.powi(2))) - 10.0, 0.0, 0.0);
+    model.add_constraint(x[1] * x[2] - 5.0 * x[3] * x[4], 0.0, 0.0);
+    model.add_constraint(x[0].powi(3) + x[1].powi(3) + 1.0, 0.0, 0.0);
+}
+
+/// The solution of HS080 and HS081.
+const HS080_SOLUTION: [f64; 5] = [-1.717_143, 1.595_709, 1.827_247, -0.763_641_3, -0.763_645];
+
+/// The bounds of HS080 and HS081.
+const HS080_BOUNDS: [(f64, f64); 5] = [
+    (-2.3, 2.3),
+    (-2.3, 2.3),
+    (-3.2, 3.2),
+    (-3.2, 3.2),
+    (-3.2, 3.2),
+];
+
+fn hs078() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 5], &[-2.0, 1.5, 2.0, -1.0, -1.0]);
+    model.minimize(x[0] * x[1] * x[2] * x[3] * x[4]);
+    hs078_constraints(&mut model, &x);
+    let solution = vec![-1.717_142, 1.595_708, 1.827_248, -0.763_642_9, -0.763_643_5];
+    hs(78, &model, -2.919_700, solution)
+}
+
+fn hs079() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 5], &[2.0; 5]);
+    model.minimize(
+        (x[0] - 1.0).powi(2)
+            + (x[0] - x[1]).powi(2)
+            + (x[1] - x[2]).powi(2)
+            + (x[2] - x[3]).powi(4)
+            + (x[3] - x[4]).powi(4),
+    );
+    model.add_constraint(
+        x[0] + x[1].powi(2) + x[2].powi(3) - 2.0 - 3.0 * SQRT_2,
+        0.0,
+        0.0,
+    );
+    model.add_constraint(x[1] - x[2].powi(2) + x[3] + 2.0 - 2.0 * SQRT_2, 0.0, 0.0);
+    model.add_constraint(x[0] * x[4] - 2.0, 0.0, 0.0);
+    let solution = vec![1.191_127, 1.362_603, 1.472_818, 1.635_017, 1.679_081];
+    hs(79, &model, 0.078_776_820_9, solution)
+}
+
+fn hs080() -> TestProblem {
+    let (mut model, x) = variables(&HS080_BOUNDS, &[-2.0, 2.0, 2.0, -1.0, -1.0]);
+    model.minimize((x[0] * x[1] * x[2] * x[3] * x[4]).exp());
+    hs078_constraints(&mut model, &x);
+    hs(80, &model, 0.053_949_847_8, HS080_SOLUTION.to_vec())
+}
+
+fn hs081() -> TestProblem {
+    let (mut model, x) = variables(&HS080_BOUNDS, &[-2.0, 2.0, 2.0, -1.0, -1.0]);
+    model.minimize(
+        (x[0] * x[1] * x[2] * x[3] * x[4]).exp()
+            - 0.5 * (x[0].powi(3) + x[1].powi(3) + 1.0).powi(2),
+    );
+    hs078_constraints(&mut model, &x);
+    hs(81, &model, 0.053_949_847_8, HS080_SOLUTION.to_vec())
+}
+
+fn hs083() -> TestProblem {
+    let bounds = [
+        (78.0, 102.0),
+        (33.0, 45.0),
+        (27.0, 45.0),
+        (27.0, 45.0),
+        (27.0, 45.0),
+    ];
+    let (mut model, x) = variables(&bounds, &[78.0, 33.0, 27.0, 27.0, 27.0]);
+    model.minimize(
+        5.357_854_7 * x[2].powi(2) + 0.835_689_1 * x[0] * x[4] + 37.293_239 * x[0] - 40_792.141,
+    );
+    model.add_constraint(
+        85.334_407 + 0.005_685_8 * x[1] * x[4] + 0.000_626_2 * x[0] * x[3]
+            - 0.002_205_3 * x[2] * x[4],
+        0.0,
+        92.0,
+    );
+    model.add_constraint(
+        80.512_49
+            + 0.007_131_7 * x[1] * x[4]
+            + 0.002_995_5 * x[0] * x[1]
+            + 0.002_181_3 * x[2].powi(2),
+        90.0,
+        110.0,
+    );
+    model.add_constraint(
+        9.300_961
+            + 0.004_702_6 * x[2] * x[4]
+            + 0.001_254_7 * x[0] * x[2]
+            + 0.001_908_5 * x[2] * x[3],
+        20.0,
+        25.0,
+    );
+    let solution = vec![78.0, 33.0, 29.995_26, 45.0, 36.775_81];
+    hs(83, &model, -30_665.538_67, solution)
+}
+
+fn hs084() -> TestProblem {
+    const A: [f64; 21] = [
+        -24_345.0,
+        -8_720_288.849,
+        150_512.525_3,
+        -156.695_032_5,
+        476_470.322_2,
+        729_482.827_1,
+        -145_421.402,
+        2_931.150_6,
+        -40.427_932,
+        5_106.192,
+        15_711.36,
+        -155_011.108_4,
+        4_360.533_52,
+        12.949_234_4,
+        10_236.884,
+        13_176.786,
+        -326_669.510_4,
+        7_390.684_12,
+        -27.898_697_6,
+        16_643.076,
+        30_988.146,
+    ];
+    let bounds = [
+        (0.0, 1000.0),
+        (1.2, 2.4),
+        (20.0, 60.0),
+        (9.0, 9.3),
+        (6.5, 7.0),
+    ];
+    let (mut model, x) = variables(&bounds, &[2.52, 2.0, 37.5, 9.25, 6.8]);
+    // Each function is a_0 x_1 + x_1 (a_1 x_2 + ... + a_4 x_5) for five consecutive coefficients.
+    let bilinear = |a: &[f64]| {
+        a[0] * x[0] + x[0] * Expr::sum(a[1..].iter().zip(&x[1..]).map(|(&a, &x)| a * x))
+    };
+    model.minimize(-A[0] - bilinear(&A[1..6]));
+    model.add_constraint(bilinear(&A[6..11]), 0.0, 294_000.0);
+    model.add_constraint(bilinear(&A[11..16]), 0.0, 294_000.0);
+    model.add_constraint(bilinear(&A[16..21]), 0.0, 277_200.0);
+    let solution = vec![4.537_430_97, 2.4, 60.0, 9.3, 7.0];
+    hs(84, &model, -5_280_335.133, solution)
+}
+
+fn hs093() -> TestProblem {
+    let (mut model, x) = variables(&[(0.0, INF); 6], &[5.54, 4.4, 12.02, 11.82, 0.702, 0.852]);
+    let a = x[0] * x[3] * (x[0] + x[1] + x[2]);
+    let b = x[1] * x[2] * (x[0] + 1.57 * x[1] + x[3]);
+    model.minimize(
+        0.0204 * &a + 0.0187 * &b + 0.0607 * &a * x[4].powi(2) + 0.0437 * &b * x[5].powi(2),
+    );
+    model.add_constraint(
+        0.001 * x[0] * x[1] * x[2] * x[3] * x[4] * x[5] - 2.07,
+        0.0,
+        INF,
+    );
+    model.add_constraint(
+        1.0 - 0.000_62 * &a * x[4].powi(2) - 0.000_58 * &b * x[5].powi(2),
+        0.0,
+        INF,
+    );
+    let solution = vec![
+        5.332_666_336,
+        4.656_744_059,
+        10.432_991_94,
+        12.082_306_34,
+        0.752_607_436_2,
+        0.878_650_874_7,
+    ];
+    hs(93, &model, 135.075_962_8, solution)
+}
+
+/// The model of HS095-HS098, with the right-hand sides `b` of the constraints.
+fn hs095_model(b: [f64; 4]) -> Model {
+    let bounds = [
+        (0.0, 0.31),
+        (0.0, 0.046),
+        (0.0, 0.068),
+        (0.0, 0.042),
+        (0.0, 0.028),
+        (0.0, 0.0134),
+    ];
+    let (mut model, x) = variables(&bounds, &[0.0; 6]);
+    model.minimize(4.3 * x[0] + 31.8 * x[1] + 63.3 * x[2] + 15.8 * x[3] + 68.5 * x[4] + 4.7 * x[5]);
+    let constraints = [
+        17.1 * x[0] + 38.2 * x[1] + 204.2 * x[2] + 212.3 * x[3] + 623.4 * x[4] + 1495.5 * x[5]
+            - 169.0 * x[0] * x[2]
+            - 3580.0 * x[2] * x[4]
+            - 3810.0 * x[3] * x[4]
+            - 18_500.0 * x[3] * x[5]
+            - 24_300.0 * x[4] * x[5],
+        17.9 * x[0] + 36.8 * x[1] + 113.9 * x[2] + 169.7 * x[3] + 337.8 * x[4] + 1385.2 * x[5]
+            - 139.0 * x[0] * x[2]
+            - 2450.0 * x[3] * x[4]
+            - 16_600.0 * x[3] * x[5]
+            - 17_200.0 * x[4] * x[5],
+        -273.0 * x[1] - 70.0 * x[3] - 819.0 * x[4] + 26_000.0 * x[3] * x[4],
+        159.9 * x[0] - 311.0 * x[1] + 587.0 * x[3] + 391.0 * x[4] + 2198.0 * x[5]
+            - 14_000.0 * x[0] * x[5],
+    ];
+    for (constraint, b) in constraints.into_iter().zip(b) {
+        model.add_constraint(constraint - b, 0.0, INF);
+    }
+    model
+}
+
+/// The solution of HS095 and HS096.
+const HS095_SOLUTION: [f64; 6] = [0.0, 0.0, 0.0, 0.0, 0.0, 4.97 / 1495.5];
+
+/// The solution of HS097 and HS098.
+const HS097_SOLUTION: [f64; 6] = [0.268_564_912_3, 0.0, 0.0, 0.0, 0.028, 0.0134];
+
+fn hs095() -> TestProblem {
+    let model = hs095_model([4.97, -1.88, -29.08, -78.02]);
+    hs(95, &model, 0.015_619_525_24, HS095_SOLUTION.to_vec())
+}
+
+fn hs096() -> TestProblem {
+    let model = hs095_model([4.97, -1.88, -69.08, -118.02]);
+    hs(96, &model, 0.015_619_525_24, HS095_SOLUTION.to_vec())
+}
+
+fn hs097() -> TestProblem {
+    let model = hs095_model([32.97, 25.12, -29.08, -78.02]);
+    hs(97, &model, 3.135_809_123, HS097_SOLUTION.to_vec())
+}
+
+fn hs098() -> TestProblem {
+    let model = hs095_model([32.97, 25.12, -124.08, -173.02]);
+    hs(98, &model, 3.135_809_123, HS097_SOLUTION.to_vec())
+}
+
+fn hs099() -> TestProblem {
+    const A: [f64; 7] = [50.0, 50.0, 75.0, 75.0, 75.0, 100.0, 100.0];
+    const T: [f64; 8] = [0.0, 25.0, 50.0, 100.0, 150.0, 200.0, 290.0, 380.0];
+    const B: f64 = 32.0;
+    let (mut model, x) = variables(&[(0.0, 1.58); 7], &[0.5; 7]);
+    // The recursions r_i, s_i and q_i of the problem, from r_1 = s_1 = q_1 = 0.
+    let zero = || Expr::constant(0.0);
+    let (mut r, mut s, mut q) = (zero(), zero(), zero());
+    for (i, &x) in x.iter().enumerate() {
+        let step = T[i + 1] - T[i];
+        let acceleration = A[i] * x.sin() - B;
+        q = q + 0.5 * step.powi(2) * &acceleration + step * &s;
+        s = s + step * acceleration;
+        r = r + A[i] * step * x.cos();
+    }
+    model.minimize(-r.powi(2));
+    model.add_constraint(q, 1e5, 1e5);
+    model.add_constraint(s, 1000.0, 1000.0);
+    let solution = vec![
+        0.542_467_816_2,
+        0.529_021_424_2,
+        0.508_449_155_3,
+        0.480_268_851_0,
+        0.451_236_349_8,
+        0.409_183_082_4,
+        0.352_787_891_8,
+    ];
+    hs(99, &model, -831_079_891.5, solution)
+}
+
+fn hs100() -> TestProblem {
+    let (mut model, x) = variables(&[FREE; 7], &[1.0, 2.0, 0.0, 4.0, 0.0, 1.0, 1.0]);
+    model.minimize(
+        (x[0] - 10.0).powi(2)
+            + 5.0 * (x[1] - 12.0).powi(2)
+            + x[2].powi(4)
+            + 3.0 * (x[3] - 11.0).powi(2)
+            + 10.0 * x[4].powi(6)
+            + 7.0 * x[5].powi(2)
+            + x[6].powi(4)
+            - 4.0 * x[5] * x[6]
+            - 10.0 * x[5]
+            - 8.0 * x[6],
+    );
+    model.add_constraint(
+        127.0 - 2.0 * x[0].powi(2) - 3.0 * x[1].powi(4) - x[2] - 4.0 * x[3].powi(2) - 5.0 * x[4],
+        0.0,
+        INF,
+    );
+    model.add_constraint(
+        282.0 - 7.0 * x[0] - 3.0 * x[1] - 10.0 * x[2].powi(2) - x[3] + x[4],
+        0.0,
+        INF,
+    );
+    model.add_constraint(
+        196.0 - 23.0 * x[0] - x[1].powi(2) - 6.0 * x[5].powi(2) + 8.0 * x[6],
+        0.0,
+        INF,
+    );
+    model.add_constraint(
+        -4.0 * x[0].powi(2) - x[1].powi(2) + 3.0 * x[0] * x[1] - 2.0 * x[2].powi(2) - 5.0 * x[5]
+            + 11.0 * x[6],
+        0.0,
+        INF,
+    );
+    let solution = vec![
+        2.330_499,
+        1.951_372,
+        -0.477_541_4,
+        4.365_726,
+        -0.624_487_0,
+        1.038_131,
+        1.594_227,
+    ];
+    hs(100, &model, 680.630_057_3, solution)
+}
+
+fn hs106() -> TestProblem {
+    let mut bounds = vec![(10.0, 1000.0); 8];
+    bounds[0] = (100.0, 10_000.0);
+    bounds[1] = (1000.0, 10_000.0);
+    bounds[2] = (1000.0, 10_000.0);
+    let start = [5000.0, 5000.0, 5000.0, 200.0, 350.0, 150.0, 225.0, 425.0];
+    let (mut model, x) = variables(&bounds, &start);
+    model.minimize(x[0] + x[1] + x[2]);
+    model.add_constraint(1.0 - 0.0025 * (x[3] + x[5]), 0.0, INF);
+    model.add_constraint(1.0 - 0.0025 * (x[4] + x[6] - x[3]), 0.0, INF);
+    model.add_constraint(1.0 - 0.01 * (x[7] - x[4]), 0.0, INF);
+    model.add_constraint(
+        x[0] * x[5] - 833.332_52 * x[3] - 100.0 * x[0] + 83_333.333,
+        0.0,
+        INF,
+    );
+    model.add_constraint(
+        x[1] * x[6] - 1250.0 * x[4] - x[1] * x[3] + 1250.0 * x[3],
+        0.0,
+        INF,
+    );
+    model.add_constraint(
+        x[2] * x[7] - 1_250_000.0 - x[2] * x[4] + 2500.0 * x[4],
+        0.0,
+        INF,
+    );
+    let solution = vec![
+        579.306_685_1,
+        1_359.970_678,
+        5_109.970_657,
+        182.017_699_6,
+        295.601_173_7,
+        217.982_300_4,
+        286.416_525_9,
+        395.601_173_7,
+    ];
+    hs(106, &model, 7_049.248_021, solution)
+}
+
+fn hs107() -> TestProblem {
+    let mut bounds = vec![FREE; 9];
+    bounds[0] = (0.0, INF);
+    bounds[1] = (0.0, INF);
+    bounds[4..7].fill((0.909_09, 1.090_9));
+    let start = [0.8, 0.8, 0.2, 0.2, 1.0454, 1.0454, 1.0454, 0.0, 0.0];
+    let (mut model, x) = variables(&bounds, &start);
+    model.minimize(3000.0 * x[0] + 1000.0 * x[0].powi(3) + 2000.0 * x[1] + 666.667 * x[1].powi(3));
+    let c = 48.4 / 50.176 * 0.25_f64.sin();
+    let d = 48.4 / 50.176 * 0.25_f64.cos();
+    let (y1, y2) = (x[7].sin(), x[7].cos());
+    let (y3, y4) = (x[8].sin(), x[8].cos());
+    let (y5, y6) = ((x[7] - x[8]).sin(), (x[7] - x[8]).cos());
+    let constraints = [
+        0.4 - x[0] + 2.0 * c * x[4].powi(2)
+            - x[4] * x[5] * (d * &y1 + c * &y2)
+            - x[4] * x[6] * (d * &y3 + c * &y4),
+        0.4 - x[1]
+            + 2.0 * c * x[5].powi(2)
+            + x[4] * x[5] * (d * &y1 - c * &y2)
+            + x[5] * x[6] * (d * &y5 - c * &y6),
+        0.8 + 2.0 * c * x[6].powi(2) + x[4] * x[6] * (d * &y3 - c * &y4)
+            - x[5] * x[6] * (d * &y5 + c * &y6),
+        0.2 - x[2]
+            + 2.0 * d * x[4].powi(2)
+            + x[4] * x[5] * (c * &y1 - d * &y2)
+            + x[4] * x[6] * (c * &y3 - d * &y4),
+        0.2 - x[3] + 2.0 * d * x[5].powi(2)
+            - x[4] * x[5] * (c * &y1 + d * &y2)
+            - x[5] * x[6] * (c * &y5 + d * &y6),
+        -0.337 + 2.0 * d * x[6].powi(2) - x[4] * x[6] * (c * &y3 + d * &y4)
+            + x[5] * x[6] * (c * &y5 - d * &y6),
+    ];
+    for constraint in constraints {
+        model.add_constraint(constraint, 0.0, 0.0);
+    }
+    let solution = vec![
+        0.667_009_2,
+        1.022_388,
+        0.228_279_0,
+        0.184_823_0,
+        1.090_9,
+        1.090_9,
+        1.069_036,
+        0.106_612_6,
+        -0.338_786_7,
+    ];
+    hs(107, &model, 5_055.011_803, solution)
+}
+
+fn hs108() -> TestProblem {
+    let mut bounds = vec![FREE; 9];
+    bounds[8] = (0.0, INF);
+    let (mut model, x) = variables(&bounds, &[1.0; 9]);
+    model.minimize(
+        -0.5 * (x[0] * x[3] - x[1] * x[2] + x[2] * x[8] - x[4] * x[8] + x[4] * x[7] - x[5] * x[6]),
+    );
+    let distance = |a: Expr, b: Expr| 1.0 - a.powi(2) - b.powi(2);
+    let constraints = [
+        distance(x[2].into(), x[3].into()),
+        1.0 - x[8].powi(2),
+        distance(x[4].into(), x[5].into()),
+        distance(x[0].into(), x[1] - x[8]),
+        distance(x[0] - x[4], x[1] - x[5]),
+        distance(x[0] - x[6], x[1] - x[7]),
+        distance(x[2] - x[4], x[3] - x[5]),
+        distance(x[2] - x[6], x[3] - x[7]),
+        distance(x[6].into(), x[7] - x[8]),
+        x[0] * x[3] - x[1] * x[2],
+        x[2] * x[8],
+        -x[4] * x[8],
+        x[4] * x[7] - x[5] * x[6],
+    ];
+    for constraint in constraints {
+        model.add_constraint(constraint, 0.0, INF);
+    }
+    let solution = vec![
+        0.884_129_2,
+        0.467_242_5,
+        0.037_420_76,
+        0.999_299_6,
+        0.884_129_2,
+        0.467_242_5,
+        0.037_420_76,
+        0.999_299_6,
+        0.0,
+    ];
+    hs(108, &model, -0.866_025_403_8, solution)
+}
+
+fn hs109() -> TestProblem {
+    let mut bounds = vec![(0.0, INF); 9];
+    bounds[2..4].fill((-0.55, 0.55));
+    bounds[4..7].fill((196.0, 252.0));
+    bounds[7..9].fill((-400.0, 800.0));
+    let (mut model, x) = variables(&bounds, &[0.0; 9]);
+    model.minimize(3.0 * x[0] + 1e-6 * x[0].powi(3) + 2.0 * x[1] + 0.522_074e-6 * x[1].powi(3));
+    let a = 50.176;
+    let b = 0.25_f64.sin();
+    let c = 0.25_f64.cos();
+    model.add_constraint(x[3] - x[2] + 0.55, 0.0, INF);
+    model.add_constraint(x[2] - x[3] + 0.55, 0.0, INF);
+    model.add_constraint(2_250_000.0 - x[0].powi(2) - x[7].powi(2), 0.0, INF);
+    model.add_constraint(2_250_000.0 - x[1].powi(2) - x[8].powi(2), 0.0, INF);
+    let constraints = [
+        x[4] * x[5] * (-x[2] - 0.25).sin()
+            + x[4] * x[6] * (-x[3] - 0.25).sin()
+            + 2.0 * b * x[4].powi(2)
+            - a * x[0]
+            + 400.0 * a,
+        x[4] * x[5] * (x[2] - 0.25).sin()
+            + x[5] * x[6] * (x[2] - x[3] - 0.25).sin()
+            + 2.0 * b * x[5].powi(2)
+            - a * x[1]
+            + 400.0 * a,
+        x[4] * x[6] * (x[3] - 0.25).sin()
+            + x[5] * x[6] * (x[3] - x[2] - 0.25).sin()
+            + 2.0 * b * x[6].powi(2)
+            + 881.779 * a,
+        a * x[7] + x[4] * x[5] * (-x[2] - 0.25).cos() + x[4] * x[6] * (-x[3] - 0.25).cos()
+            - 200.0 * a
+            - 2.0 * c * x[4].powi(2)
+            + 0.7533e-3 * a * x[4].powi(2),
+        a * x[8] + x[4] * x[5] * (x[2] - 0.25).cos() + x[5] * x[6] * (x[2] - x[3] - 0.25).cos()
+            - 2.0 * c * x[5].powi(2)
+            + 0.7533e-3 * a * x[5].powi(2)
+            - 200.0 * a,
+        x[4] * x[6] * (x[3] - 0.25).cos() + x[5] * x[6] * (x[3] - x[2] - 0.25).cos()
+            - 2.0 * c * x[6].powi(2)
+            - 22.938 * a
+            + 0.7533e-3 * a * x[6].powi(2),
+    ];
+    for constraint in constraints {
+        model.add_constraint(constraint, 0.0, 0.0);
+    }
+    let solution = vec![
+        675.025_339_3,
+        1_134.021_090,
+        0.133_485_050_9,
+        -0.371_190_262_6,
+        252.0,
+        252.0,
+        201.465_857_7,
+        426.619_005_7,
+        368.488_199_1,
+    ];
+    hs(109, &model, 5_362.069_181, solution)
+}
+
+fn hs110() -> TestProblem {
+    let (mut model, x) = variables(&[(2.001, 9.999); 10], &[9.0; 10]);
+    let logs = Expr::sum(
+        x.iter()
+            .map(|&x| (x - 2.0).ln().powi(2) + (10.0 - x).ln().powi(2)),
+    );
+    let product = (0.2 * Expr::sum(x.iter().map(|x| x.ln()))).exp();
+    model.minimize(logs - product);
+    hs(110, &model, -45.778_469_71, vec![9.350_256_55; 10])
+}
+
+/// The free energy coefficients of the chemical equilibrium problems HS111 and HS112.
+const HS111_C: [f64; 10] = [
+    -6.089, -17.164, -34.054, -5.914, -24.721, -14.986, -24.1, -10.708, -26.662, -22.179,
+];
+
+fn hs111() -> TestProblem {
+    let (mut model, x) = variables(&[(-100.0, 100.0); 10], &[-2.3; 10]);
+    let e: Vec<Expr> = x.iter().map(|x| x.exp()).collect();
+    let log_total = Expr::sum(&e).ln();
+    model.minimize(Expr::sum(
+        e.iter()
+            .zip(&x)
+            .zip(HS111_C)
+            .map(|((e, &x), c)| e * (c + x - &log_total)),
+    ));
+    model.add_constraint(
+        &e[0] + 2.0 * &e[1] + 2.0 * &e[2] + &e[5] + &e[9] - 2.0,
+        0.0,
+        0.0,
+    );
+    model.add_constraint(&e[3] + 2.0 * &e[4] + &e[5] + &e[6] - 1.0, 0.0, 0.0);
+    model.add_constraint(&e[2] + &e[6] + &e[7] + 2.0 * &e[8] + &e[9] - 1.0, 0.0, 0.0);
+    // The solution is the logarithm of the solution of HS112.
+    let solution = vec![
+        -3.202_311_588,
+        -1.912_366_597,
+        -0.244_426_747_8,
+        -6.561_177_272,
+        -0.723_097_963_3,
+        -7.274_232_28,
+        -3.597_237_422,
+        -4.020_316_731,
+        -3.288_376_881,
+        -2.334_371_739,
+    ];
+    hs(111, &model, -47.761_090_86, solution)
+}
+
+fn hs112() -> TestProblem {
+    let (mut model, x) = variables(&[(1e-6, INF); 10], &[0.1; 10]);
+    let log_total = Expr::sum(x.iter().copied()).ln();
+    model.minimize(Expr::sum(
+        x.iter()
+            .zip(HS111_C)
+            .map(|(&x, c)| x * (c + x.ln() - &log_total)),
+    ));
+    model.add_constraint(x[0] + 2.0 * x[1] + 2.0 * x[2] + x[5] + x[9] - 2.0, 0.0, 0.0);
+    model.add_constraint(x[3] + 2.0 * x[4] + x[5] + x[6] - 1.0, 0.0, 0.0);
+    model.add_constraint(x[2] + x[6] + x[7] + 2.0 * x[8] + x[9] - 1.0, 0.0, 0.0);
+    let solution = vec![
+        0.040_668_087_36,
+        0.147_730_354_3,
+        0.783_153_354_0,
+        0.001_414_219_809,
+        0.485_246_648_7,
+        0.000_693_172_078_4,
+        0.027_399_310_71,
+        0.017_947_279_58,
+        0.037_314_365_91,
+        0.096_871_323_87,
+    ];
+    hs(112, &model, -47.761_090_86, solution)
+}
+
+fn hs113() -> TestProblem {
+    let (mut model, x) = variables(
+        &[FREE; 10],
+        &[2.0, 3.0, 5.0, 5.0, 1.0, 2.0, 7.0, 3.0, 6.0, 10.0],
+    );
+    model.minimize(
+        x[0].powi(2) + x[1].powi(2) + x[0] * x[1] - 14.0 * x[0] - 16.0 * x[1]
+            + (x[2] - 10.0).powi(2)
+            + 4.0 * (x[3] - 5.0).powi(2)
+            + (x[4] - 3.0).powi(2)
+            + 2.0 * (x[5] - 1.0).powi(2)
+            + 5.0 * x[6].powi(2)
+            + 7.0 * (x[7] - 11.0).powi(2)
+            + 2.0 * (x[8] - 10.0).powi(2)
+            + (x[9] - 7.0).powi(2)
+            + 45.0,
+    );
+    let constraints = [
+        105.0 - 4.0 * x[0] - 5.0 * x[1] + 3.0 * x[6] - 9.0 * x[7],
+        -10.0 * x[0] + 8.0 * x[1] + 17.0 * x[6] - 2.0 * x[7],
+        8.0 * x[0] - 2.0 * x[1] - 5.0 * x[8] + 2.0 * x[9] + 12.0,
+        -3.0 * (x[0] - 2.0).powi(2) - 4.0 * (x[1] - 3.0).powi(2) - 2.0 * x[2].powi(2)
+            + 7.0 * x[3]
+            + 120.0,
+        -5.0 * x[0].powi(2) - 8.0 * x[1] - (x[2] - 6.0).powi(2) + 2.0 * x[3] + 40.0,
+        -0.5 * (x[0] - 8.0).powi(2) - 2.0 * (x[1] - 4.0).powi(2) - 3.0 * x[4].powi(2) + x[5] + 30.0,
+        -x[0].powi(2) - 2.0 * (x[1] - 2.0).powi(2) + 2.0 * x[0] * x[1] - 14.0 * x[4] + 6.0 * x[5],
+        3.0 * x[0] - 6.0 * x[1] - 12.0 * (x[8] - 8.0).powi(2) + 7.0 * x[9],
+    ];
+    for constraint in constraints {
+        model.add_constraint(constraint, 0.0, INF);
+    }
+    let solution = vec![
+        2.171_996,
+        2.363_683,
+        8.773_926,
+        5.095_984,
+        0.990_654_8,
+        1.430_574,
+        1.321_644,
+        9.828_726,
+        8.280_092,
+        8.375_927,
+    ];
+    hs(113, &model, 24.306_209_1, solution)
+}
+
+fn hs114() -> TestProblem {
+    let bounds = [
+        (1e-5, 2000.0),
+        (1e-5, 16_000.0),
+        (1e-5, 120.0),
+        (1e-5, 5000.0),
+        (1e-5, 2000.0),
+        (85.0, 93.0),
+        (90.0, 95.0),
+        (3.0, 12.0),
+        (1.2, 4.0),
+        (145.0, 162.0),
+    ];
+    let start = [
+        1745.0, 12_000.0, 110.0, 3048.0, 1974.0, 89.2, 92.8, 8.0, 3.6, 145.0,
+    ];
+    let (mut model, x) = variables(&bounds, &start);
+    model.minimize(5.04 * x[0] + 0.035 * x[1] + 10.0 * x[2] + 3.36 * x[4] - 0.063 * x[3] * x[6]);
+    let a = 0.99;
+    let b = 0.9;
+    let g1 = 35.82 - 0.222 * x[9] - b * x[8];
+    let g2 = -133.0 + 3.0 * x[6] - a * x[9];
+    let g5 = 1.12 * x[0] + 0.131_67 * x[0] * x[7] - 0.006_67 * x[0] * x[7].powi(2) - a * x[3];
+    let g6 = 57.425 + 1.098 * x[7] - 0.038 * x[7].powi(2) + 0.325 * x[5] - a * x[6];
+    let constraints = [
+        -&g1 + (1.0 / b - b) * x[8],
+        -&g2 + (1.0 / a - a) * x[9],
+        -&g5 + (1.0 / a - a) * x[3],
+        -&g6 + (1.0 / a - a) * x[6],
+        g1,
+        g2,
+        g5,
+        g6,
+    ];
+    for constraint in constraints {
+        model.add_constraint(constraint, 0.0, INF);
+    }
+    model.add_constraint(1.22 * x[3] - x[0] - x[4], 0.0, 0.0);
+    model.add_constraint(
+        98_000.0 * x[2] / (x[3] * x[8] + 1000.0 * x[2]) - x[5],
+        0.0,
+        0.0,
+    );
+    model.add_constraint((x[1] + x[4]) / x[0] - x[7], 0.0, 0.0);
+    let solution = vec![
+        1_698.094_765,
+        15_818.614_92,
+        54.102_682_33,
+        3_031.225_217,
+        2_000.0,
+        90.115_422_20,
+        95.0,
+        10.493_298_31,
+        1.561_636_364,
+        153.535_353_5,
+    ];
+    hs(114, &model, -1_768.806_964, solution)
+}
+
+fn hs116() -> TestProblem {
+    let bounds = [
+        (0.1, 1.0),
+        (0.1, 1.0),
+        (0.1, 1.0),
+        (1e-4, 0.1),
+        (0.1, 0.9),
+        (0.1, 0.9),
+        (0.1, 1000.0),
+        (0.1, 1000.0),
+        (500.0, 1000.0),
+        (0.1, 500.0),
+        (1.0, 150.0),
+        (1e-4, 150.0),
+        (1e-4, 150.0),
+    ];
+    let start = [
+        0.5, 0.8, 0.9, 0.1, 0.14, 0.5, 489.0, 80.0, 650.0, 450.0, 150.0, 150.0, 150.0,
+    ];
+    let (mut model, x) = variables(&bounds, &start);
+    model.minimize(x[10] + x[11] + x[12]);
+    let a = 0.002;
+    let b = 1.262_626;
+    let c = 1.231_059;
+    let d = 0.034_75;
+    let e = 0.975;
+    let f = 0.009_75;
+    let constraints = [
+        x[2] - x[1],
+        x[1] - x[0],
+        1.0 - a * x[6] + a * x[7],
+        x[10] + x[11] + x[12] - 50.0,
+        x[12] - b * x[9] + c * x[2] * x[9],
+        x[4] - d * x[1] - e * x[1] * x[4] + f * x[1].powi(2),
+        x[5] - d * x[2] - e * x[2] * x[5] + f * x[2].powi(2),
+        x[3] - d * x[0] - e * x[0] * x[3] + f * x[0].powi(2),
+        x[11] - b * x[8] + c * x[1] * x[8],
+        x[10] - b * x[7] + c * x[0] * x[7],
+        x[4] * x[6] - x[0] * x[7] - x[3] * x[6] + x[3] * x[7],
+        1.0 - a * (x[1] * x[8] + x[4] * x[7] - x[0] * x[7] - x[5] * x[8]) - x[4] - x[5],
+        x[1] * x[8] - x[2] * x[9] - x[5] * x[8] - 500.0 * x[1] + 500.0 * x[5] + x[1] * x[9],
+        x[1] - 0.9 - a * (x[1] * x[9] - x[2] * x[9]),
+        250.0 - x[10] - x[11] - x[12],
+    ];
+    for constraint in constraints {
+        model.add_constraint(constraint, 0.0, INF);
+    }
+    let solution = vec![
+        0.803_770_3,
+        0.899_986_0,
+        0.970_972_4,
+        0.099_999_52,
+        0.190_815_4,
+        0.460_571_7,
+        574.080_3,
+        74.080_43,
+        500.016_2,
+        0.1,
+        20.234_13,
+        77.347_55,
+        0.006_730_39,
+    ];
+    hs(116, &model, 97.588_409, solution)
+}
+
+fn hs117() -> TestProblem {
+    const A: [[f64; 5]; 10] = [
+        [-16.0, 2.0, 0.0, 1.0, 0.0],
+        [0.0, -2.0, 0.0, 0.4, 2.0],
+        [-3.5, 0.0, 2.0, 0.0, 0.0],
+        [0.0, -2.0, 0.0, -4.0, -1.0],
+        [0.0, -9.0, -2.0, 1.0, -2.8],
+        [2.0, 0.0, -4.0, 0.0, 0.0],
+        [-1.0, -1.0, -1.0, -1.0, -1.0],
+        [-1.0, -2.0, -3.0, -2.0, -1.0],
+        [1.0, 2.0, 3.0, 4.0, 5.0],
+        [1.0, 1.0, 1.0, 1.0, 1.0],
+    ];
+    const B: [f64; 10] = [-40.0, -2.0, -0.25, -4.0, -4.0, -1.0, -40.0, -60.0, 5.0, 1.0];
+    const C: [[f64; 5]; 5] = [
+        [30.0, -20.0, -10.0, 32.0, -10.0],
+        [-20.0, 39.0, -6.0, -31.0, 32.0],
+        [-10.0, -6.0, 10.0, -6.0, -10.0],
+        [32.0, -31.0, -6.0, 39.0, -20.0],
+        [-10.0, 32.0, -10.0, -20.0, 30.0],
+    ];
+    const D: [f64; 5] = [4.0, 8.0, 10.0, 6.0, 2.0];
+    const E: [f64; 5] = [-15.0, -27.0, -36.0, -18.0, -12.0];
+    let mut start = [0.001; 15];
+    start[6] = 60.0;
+    let (mut model, v) = variables(&[(0.0, INF); 15], &start);
+    let (x, y) = v.split_at(10);
+    let quadratic = Expr::sum((0..5).flat_map(|j| (0..5).map(move |k| C[k][j] * y[k] * y[j])));
+    model.minimize(
+        -Expr::sum(B.iter().zip(x).map(|(&b, &x)| b * x))
+            + quadratic
+            + 2.0 * Expr::sum(D.iter().zip(y).map(|(&d, &y)| d * y.powi(3))),
+    );
+    for j in 0..5 {
+        model.add_constraint(
+            2.0 * Expr::sum((0..5).map(|k| C[k][j] * y[k])) + 3.0 * D[j] * y[j].powi(2) + E[j]
+                - Expr::sum((0..10).map(|k| A[k][j] * x[k])),
+            0.0,
+            INF,
+        );
+    }
+    let solution = vec![
+        0.0,
+        0.0,
+        5.174_136,
+        0.0,
+        3.061_093,
+        11.839_68,
+        0.0,
+        0.0,
+        0.103_907_1,
+        0.0,
+        0.299_992_9,
+        0.333_470_9,
+        0.399_991_0,
+        0.428_314_5,
+        0.223_960_7,
+    ];
+    hs(117, &model, 32.348_678_97, solution)
+}
+
+fn hs118() -> TestProblem {
+    let mut bounds = [(0.0, 90.0), (0.0, 120.0), (0.0, 60.0)].repeat(5);
+    bounds[..3].copy_from_slice(&[(8.0, 21.0), (43.0, 57.0), (3.0, 16.0)]);
+    let start = [20.0, 55.0, 15.0].repeat(5);
+    let (mut model, x) = variables(&bounds, &start);
+    model.minimize(Expr::sum(x.chunks(3).map(|x| {
+        2.3 * x[0]
+            + 0.0001 * x[0].powi(2)
+            + 1.7 * x[1]
+            + 0.0001 * x[1].powi(2)
+            + 2.2 * x[2]
+            + 0.000_15 * x[2].powi(2)
+    })));
+    // The production of each product may change by at most a limit between periods.
+    for periods in x.windows(6).step_by(3) {
+        for (i, upper) in [13.0, 14.0, 13.0].into_iter().enumerate() {
+            model.add_constraint(periods[i + 3] - periods[i] + 7.0, 0.0, upper);
+        }
+    }
+    for (period, demand) in x.chunks(3).zip([60.0, 50.0, 70.0, 85.0, 100.0]) {
+        model.add_constraint(Expr::sum(period.iter().copied()) - demand, 0.0, INF);
+    }
+    let solution = vec![
+        8.0, 49.0, 3.0, 1.0, 56.0, 0.0, 1.0, 63.0, 6.0, 3.0, 70.0, 12.0, 5.0, 77.0, 18.0,
+    ];
+    hs(118, &model, 664.820_45, solution)
+}
+
+fn hs119() -> TestProblem {
+    // The nonzero entries (i, j) of the upper triangular matrix of the objective, from one.
+    const A: [(usize, usize); 46] = [
+        (1, 1),
+        (1, 4),
+        (1, 7),
+        (1, 8),
+        (1, 16),
+        (2, 2),
+        (2, 3),
+        (2, 7),
+        (2, 10),
+        (3, 3),
+        (3, 7),
+        (3, 9),
+        (3, 10),
+        (3, 14),
+        (4, 4),
+        (4, 7),
+        (4, 11),
+        (4, 15),
+        (5, 5),
+        (5, 6),
+        (5, 10),
+        (5, 12),
+        (5, 16),
+        (6, 6),
+        (6, 8),
+        (6, 15),
+        (7, 7),
+        (7, 11),
+        (7, 13),
+        (8, 8),
+        (8, 10),
+        (8, 15),
+        (9, 9),
+        (9, 12),
+        (9, 16),
+        (10, 10),
+        (10, 14),
+        (11, 11),
+        (11, 13),
+        (12, 12),
+        (12, 14),
+        (13, 13),
+        (13, 14),
+        (14, 14),
+        (15, 15),
+        (16, 16),
+    ];
+    // The coefficients of x1-x9 in the equality constraints, each of which also has a coefficient
+    // of one for one of x9-x16.
+    const B: [[f64; 9]; 8] = [
+        [0.22, 0.20, 0.19, 0.25, 0.15, 0.11, 0.12, 0.13, 0.0],
+        [-1.46, 0.0, -1.30, 1.82, -1.15, 0.0, 0.80, 0.0, 0.0],
+        [1.29, -0.89, 0.0, 0.0, -1.16, -0.96, 0.0, -0.49, 0.0],
+        [-1.10, -1.06, 0.95, -0.54, 0.0, -1.78, -0.41, 0.0, 0.0],
+        [0.0, 0.0, 0.0, -1.43, 1.51, 0.59, -0.33, -0.43, 0.0],
+        [0.0, -1.72, -0.33, 0.0, 1.62, 1.24, 0.21, -0.26, 0.0],
+        [1.12, 0.0, 0.0, 0.31, 0.0, 0.0, 1.12, 0.0, -0.36],
+        [0.0, 0.45, 0.26, -1.10, 0.58, 0.0, -1.03, 0.10, 0.0],
+    ];
+    const C: [f64; 8] = [2.5, 1.1, -3.1, -3.5, 1.3, 2.1, 2.3, -1.5];
+    let (mut model, x) = variables(&[(0.0, 5.0); 16], &[10.0; 16]);
+    let u: Vec<Expr> = x.iter().map(|&x| x.powi(2) + x + 1.0).collect();
+    model.minimize(Expr::sum(A.iter().map(|&(i, j)| &u[i - 1] * &u[j - 1])));
+    for (i, (b, c)) in B.iter().zip(C).enumerate() {
+        let linear = Expr::sum(b.iter().zip(&x).map(|(&b, &x)| b * x));
+        model.add_constraint(linear + x[8 + i] - c, 0.0, 0.0);
+    }
+    let solution = vec![
+        0.039_847_351_41,
+        0.791_983_155_7,
+        0.202_870_330_3,
+        0.844_357_916_4,
+        1.269_906_453,
+        0.934_738_707_8,
+        1.681_961_969,
+        0.155_300_877_4,
+        1.567_870_334,
+        0.0,
+        0.0,
+        0.0,
+        0.660_204_066_1,
+        0.0,
+        0.674_255_926_9,
+        0.0,
+    ];
+    hs(119, &model, 244.899_697_5, solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tnlp::ProblemBounds;
+
+    /// Checks that the known solution of a problem is feasible and attains its optimal objective.
+    fn check_solution(mut problem: TestProblem) {
+        let name = problem.name().to_string();
+        let solution = problem.solution().unwrap().to_vec();
+        let bounds = ProblemBounds::from_problem(&problem).unwrap();
+        let (mut f, mut g) = (0.0, vec![0.0; bounds.g_l.len()]);
+
+        assert!(problem.eval_f(&solution, &mut f), "{name}");
+        assert!(problem.eval_g(&solution, &mut g), "{name}");
+
+        let expected = problem.optimal_objective();
+        assert!(
+            (f - expected).abs() <= 1e-5 * expected.abs().max(1.0),
+            "{name}: f(x*) = {f}, but the optimal objective is {expected}."
+        );
+        let violations = [
+            (&bounds.x_l, &solution, &bounds.x_u),
+            (&bounds.g_l, &g, &bounds.g_u),
+        ];
+        for (lower, values, upper) in violations {
+            for ((&lower, &value), &upper) in lower.iter().zip(values).zip(upper) {
+                let tolerance = 1e-4 * value.abs().max(1.0);
+                assert!(
+                    lower - tolerance <= value && value <= upper + tolerance,
+                    "{name}: {value} is outside of [{lower}, {upper}]."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hock_schittkowski_solutions_attain_optimal_objectives() {
+        for problem in hock_schittkowski_problems() {
+            check_solution(problem);
+        }
+    }
+
+    #[test]
+    fn hock_schittkowski_finds_problems_by_number() {
+        assert_eq!(
+            Some("HS071"),
+            hock_schittkowski(71).as_ref().map(TestProblem::name)
+        );
+        assert!(hock_schittkowski(25).is_none());
+        assert_eq!(96, hock_schittkowski_problems().len());
+    }
+
+    #[test]
+    fn scalable_problem_solutions_attain_optimal_objectives() {
+        for problem in [rosenbrock(10), chained_wood(8), optimal_control(20)] {
+            check_solution(problem);
+        }
+    }
+
+    #[test]
+    fn scalable_problems_have_requested_dimensions() {
+        let sizes: Vec<ProblemSize> = [broyden_tridiagonal(5), bratu(3, 1.0)]
+            .iter()
+            .map(Tnlp::get_nlp_info)
+            .collect();
+
+        assert_eq!((5, 5, 13), (sizes[0].n, sizes[0].m, sizes[0].nnz_jac));
+        assert_eq!((9, 9, 33), (sizes[1].n, sizes[1].m, sizes[1].nnz_jac));
+    }
+
+    #[test]
+    fn test_problem_is_solved_by_checks_status_and_objective() {
+        let problem = hs071();
+        let mut result = OptimizationResult {
+            solution: crate::results::Solution {
+                objective: 17.014_02,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert!(problem.is_solved_by(&result, 1e-6));
+        result.solution.objective = 17.1;
+        assert!(!problem.is_solved_by(&result, 1e-6));
+    }
+}
@@ -3,11 +3,14 @@
 //! Defines an idiomatic Rust type that emulates the `Ipopt::Tnlp` C++ type via the C interface - a
 //! base class for all NLP's that use standard triplet matrix form and dense vectors.
 
+use std::error::Error;
+
 /// The dimensions of a nonlinear problem.
 ///
 /// Whilst values in this structure should not be negative, `i32` is used to represent the sizes
 /// for Ipopt compatibility.
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProblemSize {
     /// The number of primal variables in the problem.
     pub n: i32,
@@ -23,25 +26,73 @@ pub struct ProblemSize {
     pub nnz_hess: i32,
 }
 
+/// The bounds on the variables and constraints of a nonlinear problem.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProblemBounds {
+    /// The variable lower bounds.
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::vec"))]
+    pub x_l: Vec<f64>,
+
+    /// The variable upper bounds.
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::vec"))]
+    pub x_u: Vec<f64>,
+
+    /// The constraint lower bounds.
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::vec"))]
+    pub g_l: Vec<f64>,
+
+    /// The constraint upper bounds.
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::vec"))]
+    pub g_u: Vec<f64>,
+}
+
+/// The names of the variables and constraints of a nonlinear problem.
+///
+/// Variables and constraints without a name are referred to as `x[j]` and `g[i]` respectively.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProblemNames {
+    /// The variable names, in index order.
+    pub variables: Option<Vec<String>>,
+
+    /// The constraint names, in index order.
+    pub constraints: Option<Vec<String>>,
+}
+
 /// An initial solution to a nonlinear problem.
 #[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InitialSolution {
     /// The initial values of the primal variables.
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::vec"))]
     pub x: Vec<f64>,
 
     /// The initial values of the lower bound multipliers.
     ///
     /// This value can be `None` if there are no initial values.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::float_serde::option_vec")
+    )]
     pub z_l: Option<Vec<f64>>,
 
     /// The initial values of the upper bound multipliers.
     ///
     /// This value can be `None` if there are no initial values.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::float_serde::option_vec")
+    )]
     pub z_u: Option<Vec<f64>>,
 
     /// The initial values of the constraint multipliers.
     ///
     /// This value can be `None` if there are no initial values.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::float_serde::option_vec")
+    )]
     pub lambda: Option<Vec<f64>>,
 }
 
@@ -49,17 +100,40 @@ pub struct InitialSolution {
 ///
 /// Use `None` for the scaling factors to use the Ipopt default.
 #[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UserScaling {
     /// The objective scaling. Use a negative value to maximize.
+    #[cfg_attr(feature = "serde", serde(default, with = "crate::float_serde::option"))]
     pub objective: Option<f64>,
 
     /// The variable scaling factors.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::float_serde::option_vec")
+    )]
     pub x: Option<Vec<f64>>,
 
     /// The constraint scaling factors.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::float_serde::option_vec")
+    )]
     pub g: Option<Vec<f64>>,
 }
 
+/// The value of an Ipopt option.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionValue {
+    /// An integer option value.
+    Integer(i32),
+
+    /// A numeric option value.
+    Numeric(f64),
+
+    /// A string option value.
+    String(String),
+}
+
 impl InitialSolution {
     /// Creates a new `InitialSolution` that only contains initial values for the variables.
     ///
@@ -82,20 +156,184 @@ impl InitialSolution {
             ..Default::default()
         }
     }
+
+    /// Checks that the dimensions of the initial solution match the problem dimensions.
+    pub(crate) fn validate(&self, n: usize, m: usize) -> Result<(), String> {
+        let check = |name: &str, len: Option<usize>, expected: usize| match len {
+            Some(len) if len != expected => Err(format!(
+                "Initial solution `{name}` has length {len}, but the problem expects {expected}."
+            )),
+            _ => Ok(()),
+        };
+
+        check("x", Some(self.x.len()), n)?;
+        check("z_l", self.z_l.as_ref().map(Vec::len), n)?;
+        check("z_u", self.z_u.as_ref().map(Vec::len), n)?;
+        check("lambda", self.lambda.as_ref().map(Vec::len), m)
+    }
+}
+
+impl ProblemBounds {
+    /// Gets the bounds of a problem, as given by `Tnlp::get_bounds_info`.
+    ///
+    /// # Parameters
+    /// - `problem` - The problem.
+    ///
+    /// # Errors
+    ///
+    /// - The problem dimensions are not representable as a usize.
+    pub fn from_problem<P: Tnlp + ?Sized>(problem: &P) -> Result<Self, Box<dyn Error>> {
+        let problem_size = problem.get_nlp_info();
+        let n = usize::try_from(problem_size.n)?;
+        let m = usize::try_from(problem_size.m)?;
+
+        let mut bounds = ProblemBounds {
+            x_l: vec![0.0; n],
+            x_u: vec![0.0; n],
+            g_l: vec![0.0; m],
+            g_u: vec![0.0; m],
+        };
+        problem.get_bounds_info(
+            &mut bounds.x_l,
+            &mut bounds.x_u,
+            &mut bounds.g_l,
+            &mut bounds.g_u,
+        );
+
+        Ok(bounds)
+    }
+
+    /// Replaces infinite bounds with the values that Ipopt treats as infinite.
+    ///
+    /// # Parameters
+    ///
+    /// - `lower_infinity` - The value of the `nlp_lower_bound_inf` option.
+    /// - `upper_infinity` - The value of the `nlp_upper_bound_inf` option.
+    pub(crate) fn replace_infinities(&mut self, lower_infinity: f64, upper_infinity: f64) {
+        for lower in self.x_l.iter_mut().chain(&mut self.g_l) {
+            if *lower == f64::NEG_INFINITY {
+                *lower = lower_infinity;
+            }
+        }
+        for upper in self.x_u.iter_mut().chain(&mut self.g_u) {
+            if *upper == f64::INFINITY {
+                *upper = upper_infinity;
+            }
+        }
+    }
+
+    /// Checks that no lower bound is greater than its upper bound, referring to variables and
+    /// constraints by name.
+    pub(crate) fn validate(&self, names: &ProblemNames) -> Result<(), String> {
+        let check = |kind: &str, name: String, lower: f64, upper: f64| {
+            if lower <= upper {
+                Ok(())
+            } else {
+                Err(format!(
+                    "{kind} `{name}` has lower bound {lower}, which is not less than or equal to its upper bound {upper}."
+                ))
+            }
+        };
+
+        for (j, (&lower, &upper)) in self.x_l.iter().zip(&self.x_u).enumerate() {
+            check("Variable", names.variable(j), lower, upper)?;
+        }
+        for (i, (&lower, &upper)) in self.g_l.iter().zip(&self.g_u).enumerate() {
+            check("Constraint", names.constraint(i), lower, upper)?;
+        }
+        Ok(())
+    }
+}
+
+impl ProblemNames {
+    /// Gets the names of the variables and constraints of a problem.
+    ///
+    /// # Parameters
+    ///
+    /// - `problem` - The problem to get the names of.
+    ///
+    /// # Errors
+    ///
+    /// - The problem dimensions are not representable as a usize.
+    /// - The problem returns a different number of names than it has variables or constraints.
+    pub fn from_problem<P: Tnlp + ?Sized>(problem: &P) -> Result<Self, Box<dyn Error>> {
+        let problem_size = problem.get_nlp_info();
+        let n = usize::try_from(problem_size.n)?;
+        let m = usize::try_from(problem_size.m)?;
+
+        let names = ProblemNames {
+            variables: problem.variable_names(),
+            constraints: problem.constraint_names(),
+        };
+        let check = |kind: &str, names: Option<&Vec<String>>, expected: usize| match names {
+            Some(names) if names.len() != expected => Err(format!(
+                "The problem has {expected} {kind}s, but {0} {kind} names.",
+                names.len()
+            )),
+            _ => Ok(()),
+        };
+        check("variable", names.variables.as_ref(), n)?;
+        check("constraint", names.constraints.as_ref(), m)?;
+
+        Ok(names)
+    }
+
+    /// Gets the name of a variable, or `x[j]` if it does not have one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ipopt_bindgen::ProblemNames;
+    ///
+    /// let names = ProblemNames {
+    ///     variables: Some(vec!["flow[pipe_12]".to_string()]),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!("flow[pipe_12]", names.variable(0));
+    /// assert_eq!("x[1]", names.variable(1));
+    /// ```
+    #[must_use]
+    pub fn variable(&self, j: usize) -> String {
+        index_name(self.variables.as_deref(), 'x', j)
+    }
+
+    /// Gets the name of a constraint, or `g[i]` if it does not have one.
+    #[must_use]
+    pub fn constraint(&self, i: usize) -> String {
+        index_name(self.constraints.as_deref(), 'g', i)
+    }
+}
+
+/// Gets the name at an index, falling back to `prefix[index]`.
+fn index_name(names: Option<&[String]>, prefix: char, index: usize) -> String {
+    names
+        .and_then(|names| names.get(index))
+        .cloned()
+        .unwrap_or_else(|| format!("{prefix}[{index}]"))
 }
 
 /// Ipopt intermediate callback data packed into a struct.
 #[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IntermediateData {
     pub alg_mod: i32,
     pub iter_count: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::value"))]
     pub obj_value: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::value"))]
     pub inf_pr: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::value"))]
     pub inf_du: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::value"))]
     pub mu: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::value"))]
     pub d_norm: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::value"))]
     pub regularization_size: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::value"))]
     pub alpha_du: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::float_serde::value"))]
     pub alpha_pr: f64,
     pub ls_trials: i32,
 }
@@ -226,6 +464,100 @@ pub trait Tnlp {
     fn intermediate(&mut self, _data: IntermediateData) -> bool {
         true
     }
+
+    /// Gets the names of the variables, which are used in diagnostics and reports.
+    ///
+    /// The default implementation returns `None`, so variables are referred to by index.
+    fn variable_names(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Gets the names of the constraints, which are used in diagnostics and reports.
+    ///
+    /// The default implementation returns `None`, so constraints are referred to by index.
+    fn constraint_names(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Gets the Ipopt options that suit the structure of the problem, e.g. `hessian_constant` for
+    /// a problem with a constant Hessian.
+    ///
+    /// These options are applied before the options of the `Application`, so an option that is
+    /// set on the `Application` takes precedence. The default implementation returns no options.
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        Vec::new()
+    }
+}
+
+/// Mutable references to problems are also problems, so that a problem can be optimized and then
+/// used again, e.g. to analyze the solution.
+impl<T: Tnlp + ?Sized> Tnlp for &mut T {
+    fn get_nlp_info(&self) -> ProblemSize {
+        (**self).get_nlp_info()
+    }
+
+    fn get_bounds_info(&self, x_l: &mut [f64], x_u: &mut [f64], g_l: &mut [f64], g_u: &mut [f64]) {
+        (**self).get_bounds_info(x_l, x_u, g_l, g_u);
+    }
+
+    fn get_scaling(&self) -> UserScaling {
+        (**self).get_scaling()
+    }
+
+    fn get_starting_point(&self) -> InitialSolution {
+        (**self).get_starting_point()
+    }
+
+    fn eval_f(&mut self, x: &[f64], obj_value: &mut f64) -> bool {
+        (**self).eval_f(x, obj_value)
+    }
+
+    fn eval_grad_f(&mut self, x: &[f64], grad_f: &mut [f64]) -> bool {
+        (**self).eval_grad_f(x, grad_f)
+    }
+
+    fn eval_g(&mut self, x: &[f64], g: &mut [f64]) -> bool {
+        (**self).eval_g(x, g)
+    }
+
+    fn get_jacobian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        (**self).get_jacobian_sparsity(n, m, i_row, j_col);
+    }
+
+    fn eval_jac_g(&mut self, x: &[f64], m: i32, values: &mut [f64]) -> bool {
+        (**self).eval_jac_g(x, m, values)
+    }
+
+    fn get_hessian_sparsity(&mut self, n: i32, m: i32, i_row: &mut [i32], j_col: &mut [i32]) {
+        (**self).get_hessian_sparsity(n, m, i_row, j_col);
+    }
+
+    fn eval_h(
+        &mut self,
+        x: &[f64],
+        obj_factor: f64,
+        lambda: &[f64],
+        m: i32,
+        values: &mut [f64],
+    ) -> bool {
+        (**self).eval_h(x, obj_factor, lambda, m, values)
+    }
+
+    fn intermediate(&mut self, data: IntermediateData) -> bool {
+        (**self).intermediate(data)
+    }
+
+    fn variable_names(&self) -> Option<Vec<String>> {
+        (**self).variable_names()
+    }
+
+    fn constraint_names(&self) -> Option<Vec<String>> {
+        (**self).constraint_names()
+    }
+
+    fn default_options(&self) -> Vec<(String, OptionValue)> {
+        (**self).default_options()
+    }
 }
 
 #[cfg(test)]
@@ -240,4 +572,58 @@ mod tests {
 
         assert_eq!(x, initial_solution.x);
     }
+
+    #[test]
+    fn initial_solution_validate_rejects_mismatched_dimensions() {
+        let mut initial_solution = InitialSolution::from_variables(vec![1.0, 2.0]);
+
+        assert!(initial_solution.validate(2, 1).is_ok());
+        assert!(initial_solution.validate(3, 1).is_err());
+
+        initial_solution.lambda = Some(vec![0.0; 2]);
+        assert!(initial_solution.validate(2, 1).is_err());
+    }
+
+    #[test]
+    fn problem_bounds_validate_names_inconsistent_bounds() {
+        let bounds = ProblemBounds {
+            x_l: vec![0.0, 3.0],
+            x_u: vec![1.0, 1.0],
+            ..Default::default()
+        };
+        let names = ProblemNames {
+            variables: Some(vec![
+                "flow[pipe_11]".to_string(),
+                "flow[pipe_12]".to_string(),
+            ]),
+            constraints: None,
+        };
+
+        let error = bounds.validate(&names).unwrap_err();
+
+        assert!(error.starts_with("Variable `flow[pipe_12]` has lower bound 3"));
+        assert!(
+            bounds
+                .validate(&ProblemNames::default())
+                .unwrap_err()
+                .contains("`x[1]`")
+        );
+    }
+
+    #[test]
+    fn problem_bounds_replace_infinities_only_replaces_infinite_values() {
+        let mut bounds = ProblemBounds {
+            x_l: vec![f64::NEG_INFINITY, 1.0],
+            x_u: vec![f64::INFINITY, 2e19],
+            g_l: vec![f64::NEG_INFINITY],
+            g_u: vec![3.0],
+        };
+
+        bounds.replace_infinities(-1e10, 1e10);
+
+        assert_eq!(vec![-1e10, 1.0], bounds.x_l);
+        assert_eq!(vec![1e10, 2e19], bounds.x_u);
+        assert_eq!(vec![-1e10], bounds.g_l);
+        assert_eq!(vec![3.0], bounds.g_u);
+    }
 }